@@ -20,4 +20,116 @@ pub enum ErrorCode {
     StakedNotZero, // 6007
     #[msg("Zero Input")]
     ZeroInput, // 6008
+    #[msg("Invalid lock tier")]
+    InvalidLockTier, // 6009
+    #[msg("Stake is locked")]
+    StakeIsLocked, // 6010
+    #[msg("Lock tier lower than current lock")]
+    LockTierDowngrade, // 6011
+    #[msg("Invalid withdrawal ticket")]
+    InvalidWithdrawalTicket, // 6012
+    #[msg("Unbonding cooldown has not passed")]
+    UnbondingCooldownNotPassed, // 6013
+    #[msg("User have open withdrawal tickets")]
+    OpenTicketsNotZero, // 6014
+    #[msg("Invalid remaining accounts")]
+    InvalidRemainingAccounts, // 6015
+    #[msg("Invalid reward stream")]
+    InvalidRewardStream, // 6016
+    #[msg("Reward stream already opened")]
+    RewardStreamAlreadyOpened, // 6017
+    #[msg("Reward stream limit reached")]
+    RewardStreamLimitReached, // 6018
+    #[msg("Reward exceeds funded balance")]
+    RewardExceedsFunds, // 6019
+    #[msg("Instruction is paused")]
+    InstructionPaused, // 6020
+    #[msg("Caller not pending governance")]
+    InvalidPendingGovernance, // 6021
+    #[msg("No pending governance")]
+    NoPendingGovernance, // 6022
+    #[msg("Action must be queued in the timelock")]
+    TimelockRequired, // 6023
+    #[msg("Timelock delay has not passed")]
+    TimelockNotPassed, // 6024
+    #[msg("Timelock delay lower than minimum")]
+    InvalidTimelockDelay, // 6025
+    #[msg("Invalid timelock action accounts")]
+    InvalidTimelockAction, // 6026
+    #[msg("Caller not reward manager")]
+    InvalidRewardManager, // 6027
+    #[msg("Caller not pauser")]
+    InvalidPauser, // 6028
+    #[msg("Caller not treasurer")]
+    InvalidTreasurer, // 6029
+    #[msg("Role can not be granted or revoked")]
+    InvalidRole, // 6030
+    #[msg("Caller does not hold the role of the action")]
+    InvalidProposer, // 6031
+    #[msg("Unstake fee higher than maximum")]
+    InvalidUnstakeFee, // 6032
+    #[msg("Invalid treasury token account")]
+    InvalidTreasury, // 6033
+    #[msg("Unclaimed owed rewards must be zero")]
+    UnclaimedOwedNotZero, // 6034
+    #[msg("Safe mode is active")]
+    SafeModeActive, // 6035
+    #[msg("Safe mode is not active")]
+    SafeModeNotActive, // 6036
+    #[msg("Math overflow")]
+    MathOverflow, // 6037
+    #[msg("Division by zero")]
+    DivisionByZero, // 6038
+    #[msg("Timestamp earlier than expected")]
+    NegativeTime, // 6039
+    #[msg("Integer conversion failed")]
+    ConversionFailed, // 6040
+    #[msg("Reward schedule is full")]
+    RewardScheduleFull, // 6041
+    #[msg("Scheduled cycle starts in the past or overlaps another cycle")]
+    InvalidScheduledCycle, // 6042
+    #[msg("Scheduled cycle not found")]
+    ScheduledCycleNotFound, // 6043
+    #[msg("Reward cycle overlaps a scheduled cycle")]
+    OverlapsScheduledCycle, // 6044
+    #[msg("All reward campaign slots are in use")]
+    RewardCampaignsFull, // 6045
+    #[msg("Campaign starts in the past or ends before it starts")]
+    InvalidCampaignWindow, // 6046
+    #[msg("Reward campaign has not ended")]
+    RewardCampaignNotEnded, // 6047
+    #[msg("Invalid reward campaign")]
+    InvalidRewardCampaign, // 6048
+    #[msg("Reward mint not allowed in campaigns")]
+    InvalidCampaignMint, // 6049
+    #[msg("Campaign reward lower than minimum")]
+    CampaignRewardTooLow, // 6050
+    #[msg("Invalid campaign config")]
+    InvalidCampaignConfig, // 6051
+    #[msg("Invalid distribution")]
+    InvalidDistribution, // 6052
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof, // 6053
+    #[msg("Distribution already claimed")]
+    DistributionAlreadyClaimed, // 6054
+    #[msg("Distribution expired")]
+    DistributionExpired, // 6055
+    #[msg("Distribution not expired")]
+    DistributionNotExpired, // 6056
+    #[msg("Distribution not funded")]
+    DistributionNotFunded, // 6057
+    #[msg("Snapshot unavailable for the given time")]
+    SnapshotUnavailable, // 6058
+    #[msg("No expired lock to release")]
+    NoExpiredLock, // 6059
+    #[msg("Unbonding cooldown higher than maximum")]
+    InvalidUnbondingCooldown, // 6060
+    #[msg("Unclaimed stream rewards must be zero")]
+    StreamPendingRewardsNotZero, // 6061
+    #[msg("Reward cycle already scheduled at start time")]
+    DuplicateScheduledCycle, // 6062
+    #[msg("All sponsored reward campaign slots are in use")]
+    SponsoredCampaignsFull, // 6063
+    #[msg("Account is not in the legacy layout")]
+    InvalidLegacyAccount, // 6064
 }
@@ -0,0 +1,459 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    CampaignConfig,
+    GovernanceAction,
+    LockTier,
+    Role,
+    UnstakeFee,
+    MAX_LOCK_TIERS,
+    MAX_REWARD_STREAMS,
+};
+
+/// Emitted when a user stakes tokens in a stake pool.
+#[event]
+pub struct Staked {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    /// Amount credited to the stake, after any transfer fee of the mint.
+    pub amount: u64,
+    pub lock_multiplier: u64,
+    pub lock_end_time: u64,
+    pub user_staked: u64,
+    pub pool_total_staked: u64,
+    pub total_weighted_stake: u64,
+    pub global_acc_reward: u128,
+    pub pool_acc_reward: u128,
+}
+
+/// Emitted when a user unstakes tokens into a withdrawal ticket.
+#[event]
+pub struct Unstaked {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    /// Amount moved into the withdrawal ticket, after the unstake fee.
+    pub amount: u64,
+    /// Unstake fee deducted from the unstaked amount.
+    pub fee: u64,
+    pub ticket_id: u64,
+    pub unlock_time: u64,
+    pub is_emergency: bool,
+    pub user_staked: u64,
+    pub pool_total_staked: u64,
+    pub total_weighted_stake: u64,
+    pub global_acc_reward: u128,
+    pub pool_acc_reward: u128,
+}
+
+/// Emitted when a user redeems a withdrawal ticket.
+#[event]
+pub struct Withdrawn {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub ticket_id: u64,
+    pub amount: u64,
+}
+
+/// Emitted when a user cancels a withdrawal ticket and stakes its amount again.
+#[event]
+pub struct WithdrawalCancelled {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub ticket_id: u64,
+    pub amount: u64,
+    pub user_staked: u64,
+    pub pool_total_staked: u64,
+    pub total_weighted_stake: u64,
+    pub global_acc_reward: u128,
+    pub pool_acc_reward: u128,
+}
+
+/// Emitted when a user claims the pending rewards of one or more stake pools.
+#[event]
+pub struct Claimed {
+    pub user: Pubkey,
+    /// SOL rewards paid to the user.
+    pub reward: u64,
+    /// Reward stream tokens paid to the user, indexed by the reward stream slot.
+    pub stream_rewards: [u64; MAX_REWARD_STREAMS],
+    pub total_weighted_stake: u64,
+    pub global_acc_reward: u128,
+}
+
+/// Emitted when an expired lock of a user is released back to the liquid multiplier.
+#[event]
+pub struct LockReleased {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    /// SOL rewards owed to the user after settling the rewards of the released lock.
+    pub unclaimed_owed: u64,
+    pub user_weighted: u64,
+    pub pool_total_weighted: u64,
+    pub total_weighted_stake: u64,
+    pub global_acc_reward: u128,
+    pub pool_acc_reward: u128,
+}
+
+/// Emitted when a user closes their userInfo PDA.
+#[event]
+pub struct UserInfoClosed {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub total_claimed_reward: u64,
+}
+
+/// Emitted when governance registers a new stake pool.
+#[event]
+pub struct StakePoolAdded {
+    pub mint: Pubkey,
+    pub weightage: u64,
+    pub pool_count: u64,
+}
+
+/// Emitted when governance updates the weightage of a stake pool.
+#[event]
+pub struct WeightageUpdated {
+    pub mint: Pubkey,
+    pub prev_weightage: u64,
+    pub weightage: u64,
+    pub total_weighted_stake: u64,
+    pub global_acc_reward: u128,
+    pub pool_acc_reward: u128,
+}
+
+/// Emitted when reward SOLs are funded.
+#[event]
+pub struct RewardsFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub total_funded: u64,
+}
+
+/// Emitted when the reward manager updates the SOL reward cycle.
+#[event]
+pub struct RewardsUpdated {
+    /// Newly committed SOL rewards.
+    pub added_reward: u64,
+    /// Stranded SOL rewards rolled over into the cycle.
+    pub rolled_over_stranded: u64,
+    /// SOL rewards of the current cycle, including the rewards rolled over from the previous cycle.
+    pub reward: u64,
+    pub distribution_time: u64,
+    pub period_end_time: u64,
+    pub total_distributed_reward: u64,
+    pub global_acc_reward: u128,
+}
+
+/// Emitted when the reward manager updates the end of the SOL reward cycle.
+#[event]
+pub struct PeriodEndUpdated {
+    pub period_end_time: u64,
+    pub total_distributed_reward: u64,
+    pub global_acc_reward: u128,
+}
+
+/// Emitted when the reward manager queues a future SOL reward cycle.
+#[event]
+pub struct RewardCycleScheduled {
+    pub reward: u64,
+    pub distribution_time: u64,
+    pub start_time: u64,
+    pub total_distributed_reward: u64,
+}
+
+/// Emitted when the reward manager edits a queued SOL reward cycle.
+#[event]
+pub struct RewardCycleEdited {
+    pub prev_start_time: u64,
+    pub reward: u64,
+    pub distribution_time: u64,
+    pub start_time: u64,
+    pub total_distributed_reward: u64,
+}
+
+/// Emitted when the reward manager cancels a queued SOL reward cycle.
+#[event]
+pub struct RewardCycleCancelled {
+    pub start_time: u64,
+    pub reward: u64,
+    pub total_distributed_reward: u64,
+}
+
+/// Emitted when the reward manager opens a reward campaign.
+#[event]
+pub struct RewardCampaignOpened {
+    pub campaign: Pubkey,
+    pub id: u64,
+    pub slot: u8,
+    pub creator: Pubkey,
+    /// Default pubkey means the campaign distributes SOL.
+    pub reward_mint: Pubkey,
+    pub sponsored: bool,
+    pub reward: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub total_distributed_reward: u64,
+}
+
+/// Emitted when an ended reward campaign is closed.
+#[event]
+pub struct RewardCampaignClosed {
+    pub campaign: Pubkey,
+    pub id: u64,
+    /// Campaign rewards emitted while nothing was staked. They are refunded to the sponsor of a
+    /// sponsored campaign, and added to the stranded rewards otherwise.
+    pub stranded_reward: u64,
+    pub refunded_reward: u64,
+}
+
+/// Emitted when governance updates the bounds of the sponsored reward campaigns.
+#[event]
+pub struct CampaignConfigUpdated {
+    pub campaign_config: CampaignConfig,
+}
+
+/// Emitted when governance opens a reward stream.
+#[event]
+pub struct RewardStreamAdded {
+    pub mint: Pubkey,
+    pub stream_index: u8,
+}
+
+/// Emitted when reward tokens of a reward stream are funded.
+#[event]
+pub struct StreamRewardsFunded {
+    pub funder: Pubkey,
+    pub mint: Pubkey,
+    pub stream_index: u8,
+    pub amount: u64,
+    pub total_funded: u64,
+}
+
+/// Emitted when the reward manager updates the reward cycle of a reward stream.
+#[event]
+pub struct StreamRewardsUpdated {
+    pub mint: Pubkey,
+    pub stream_index: u8,
+    /// Newly committed stream rewards.
+    pub added_reward: u64,
+    /// Stream rewards of the current cycle, including the rewards rolled over from the previous cycle.
+    pub reward: u64,
+    pub distribution_time: u64,
+    pub period_end_time: u64,
+    pub total_distributed_reward: u64,
+    pub global_acc_reward: u128,
+}
+
+/// Emitted when governance updates the lock tiers.
+#[event]
+pub struct LockTiersUpdated {
+    pub lock_tiers: [LockTier; MAX_LOCK_TIERS],
+}
+
+/// Emitted when governance updates the unbonding cooldown.
+#[event]
+pub struct UnbondingCooldownUpdated {
+    pub unbonding_cooldown: u64,
+}
+
+/// Emitted when governance updates the unstake fee.
+#[event]
+pub struct UnstakeFeeUpdated {
+    pub unstake_fee: UnstakeFee,
+}
+
+/// Emitted when the unstake fees accrued by a stake pool are collected to the treasury.
+#[event]
+pub struct UnstakeFeesCollected {
+    pub mint: Pubkey,
+    pub treasury_token_account: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when the pauser updates the paused instructions.
+#[event]
+pub struct PauseFlagsUpdated {
+    pub prev_pause_flags: u8,
+    pub pause_flags: u8,
+}
+
+/// Emitted when governance turns the safe mode on or off.
+#[event]
+pub struct SafeModeUpdated {
+    pub safe_mode: bool,
+}
+
+/// Emitted when a user withdraws their staked tokens in safe mode.
+#[event]
+pub struct EmergencyWithdrawn {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub unclaimed_owed: u64,
+    pub unclaimed_fee_owed: u64,
+}
+
+/// Emitted when a role holder queues an action in the timelock.
+#[event]
+pub struct ActionQueued {
+    pub id: u64,
+    pub action: GovernanceAction,
+    pub eta: u64,
+}
+
+/// Emitted when a queued action is executed.
+#[event]
+pub struct ActionExecuted {
+    pub id: u64,
+    pub executor: Pubkey,
+}
+
+/// Emitted when governance cancels a queued action.
+#[event]
+pub struct ActionCancelled {
+    pub id: u64,
+}
+
+/// Emitted when the timelock delay is updated.
+#[event]
+pub struct TimelockDelayUpdated {
+    pub prev_timelock_delay: u64,
+    pub timelock_delay: u64,
+}
+
+/// Emitted when governance proposes a new governance.
+#[event]
+pub struct GovernanceProposed {
+    pub governance: Pubkey,
+    pub pending_governance: Pubkey,
+}
+
+/// Emitted when governance cancels the proposed governance before it is accepted.
+#[event]
+pub struct GovernanceProposalCancelled {
+    pub governance: Pubkey,
+    pub pending_governance: Pubkey,
+}
+
+/// Emitted when the pending governance accepts and becomes the governance.
+#[event]
+pub struct GovernanceChanged {
+    pub prev_governance: Pubkey,
+    pub governance: Pubkey,
+}
+
+/// Emitted when governance grants a role.
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub prev_account: Pubkey,
+    pub account: Pubkey,
+}
+
+/// Emitted when governance revokes a role.
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub account: Pubkey,
+}
+
+/// Emitted when the treasurer withdraws the SOL rewards stranded while nothing was staked.
+#[event]
+pub struct StrandedRewardWithdrawn {
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub total_funded: u64,
+    pub total_distributed_reward: u64,
+}
+
+/// Emitted when the treasurer migrates reward SOLs to the new version of the program.
+#[event]
+pub struct FundsMigrated {
+    pub new_version: Pubkey,
+    pub amount: u64,
+    pub total_funded: u64,
+}
+
+/// Emitted when governance publishes a merkle distribution.
+#[event]
+pub struct DistributionCreated {
+    pub distribution: Pubkey,
+    pub id: u64,
+    /// Default pubkey means the distribution pays SOL from the fundsController.
+    pub mint: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub num_leaves: u64,
+    pub total_amount: u64,
+    pub expiry_time: u64,
+}
+
+/// Emitted when the SOLs of a merkle distribution are committed from the funded SOLs.
+#[event]
+pub struct DistributionFunded {
+    pub distribution: Pubkey,
+    pub total_amount: u64,
+    pub total_distributed_reward: u64,
+}
+
+/// Emitted when a user claims their leaf of a merkle distribution.
+#[event]
+pub struct DistributionClaimed {
+    pub distribution: Pubkey,
+    pub user: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub claimed_amount: u64,
+}
+
+/// Emitted when governance sweeps the unclaimed amount of an expired merkle distribution.
+#[event]
+pub struct DistributionSwept {
+    pub distribution: Pubkey,
+    /// Unclaimed SOLs are released back to the funded SOLs, unclaimed tokens are sent to governance.
+    pub amount: u64,
+}
+
+/// Emitted when governance opens a snapshot epoch, from which the stake balance changes are checkpointed.
+#[event]
+pub struct SnapshotEpochOpened {
+    pub snapshot_epoch: u64,
+    pub start_time: u64,
+}
+
+/// Emitted when the voter weight of a user is written for an SPL Governance realm.
+#[event]
+pub struct VoterWeightRecordUpdated {
+    pub user: Pubkey,
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub voter_weight: u64,
+    pub voter_weight_expiry: u64,
+}
+
+/// Emitted when the max voter weight is written for an SPL Governance realm.
+#[event]
+pub struct MaxVoterWeightRecordUpdated {
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub max_voter_weight: u64,
+    pub max_voter_weight_expiry: u64,
+}
+
+/// Emitted when governance converts the legacy global state into the current layout and the stake pools.
+#[event]
+pub struct GlobalStateMigrated {
+    pub mesh_pool: Pubkey,
+    pub index_mesh_pool: Pubkey,
+    pub total_weighted_stake: u64,
+    pub layout_version: u8,
+}
+
+/// Emitted when a legacy position of a user is merged into their userInfo of the stake pool.
+#[event]
+pub struct UserInfoMigrated {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub user_staked: u64,
+    pub unclaimed_owed: u64,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::GovernanceChanged;
+use crate::state::GlobalState;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptGovernance<'info> {
+    /// Only the pending governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub pending_governance: Signer<'info>,
+
+    /// Global state PDA to store the new governance.
+    #[account(
+      mut,
+      seeds = [GlobalState::SEEDS],
+      bump,
+      has_one = pending_governance @ ErrorCode::InvalidPendingGovernance,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn accept_governance_handler(ctx: Context<AcceptGovernance>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let prev_governance = global_state.governance;
+
+    global_state.governance = global_state.pending_governance;
+    global_state.pending_governance = Pubkey::default();
+
+    emit_cpi!(GovernanceChanged { prev_governance, governance: global_state.governance });
+
+    Ok(())
+}
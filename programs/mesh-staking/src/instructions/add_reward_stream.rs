@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{ Mint, Token, TokenAccount };
+
+use crate::errors::ErrorCode;
+use crate::events::RewardStreamAdded;
+use crate::math::SafeCast;
+use crate::state::{ GlobalState, FundsController, RewardStream };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddRewardStream<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to store the opened reward stream.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// PDA that holds the ownership of the reward vault.
+    #[account(seeds = [FundsController::SEEDS], bump)]
+    pub funds_controller: Account<'info, FundsController>,
+
+    /// Mint address of the token distributed by the stream.
+    pub reward_mint: Account<'info, Mint>,
+
+    /// Vault PDA of the stream to hold its reward tokens, kept apart from the stake vault of the same mint.
+    #[account(
+        init,
+        payer = governance,
+        seeds = [RewardStream::VAULT_SEEDS, reward_mint.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = funds_controller
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// The program used to create the reward vault.
+    pub token_program: Program<'info, Token>,
+
+    /// The program used to create the reward vault.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_reward_stream_handler(ctx: Context<AddRewardStream>) -> Result<()> {
+    let reward_mint = ctx.accounts.reward_mint.key();
+    let global_state = &mut ctx.accounts.global_state;
+
+    if global_state.reward_streams.iter().any(|reward_stream| reward_stream.mint == reward_mint) {
+        return Err(ErrorCode::RewardStreamAlreadyOpened.into());
+    }
+
+    let current_time = global_state.get_current_time()?;
+
+    let stream_index = global_state.reward_streams
+        .iter()
+        .position(|reward_stream| !reward_stream.is_opened())
+        .ok_or(ErrorCode::RewardStreamLimitReached)?;
+
+    global_state.reward_streams[stream_index] = RewardStream {
+        mint: reward_mint,
+        last_updated_time: current_time,
+        ..RewardStream::default()
+    };
+
+    emit_cpi!(RewardStreamAdded {
+        mint: reward_mint,
+        stream_index: stream_index.safe_cast()?,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::errors::ErrorCode;
+use crate::events::StakePoolAdded;
+use crate::math::SafeMath;
+use crate::state::{ GlobalState, RewardSchedule, StakePool };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddStakePool<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to keep track of the registered pools.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to start the scheduled reward cycles that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+
+    /// Mint address of the token that becomes stakeable.
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA to store the stake pool state of the mint.
+    #[account(
+        init,
+        seeds = [StakePool::SEEDS, mint.key().as_ref()],
+        bump,
+        payer = governance,
+        space = StakePool::LEN
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// The program used to create the stake pool account.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_stake_pool_handler(ctx: Context<AddStakePool>, _weightage: u64) -> Result<()> {
+    ctx.accounts.global_state.update_reward_per_share(&mut ctx.accounts.reward_schedule)?;
+
+    let global_state = &mut ctx.accounts.global_state;
+    let stake_pool = &mut ctx.accounts.stake_pool;
+
+    // The pool only accumulates rewards emitted after its registration.
+    stake_pool.mint = ctx.accounts.mint.key();
+    stake_pool.weightage = _weightage;
+    stake_pool.global_acc_reward_checkpoint = global_state.global_acc_reward;
+    stake_pool.stream_acc_reward_checkpoints = global_state.get_stream_acc_rewards();
+
+    global_state.pool_count = global_state.pool_count.safe_add(1)?;
+
+    emit_cpi!(StakePoolAdded {
+        mint: stake_pool.mint,
+        weightage: stake_pool.weightage,
+        pool_count: global_state.pool_count,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ GlobalState, StakeCheckpoint, StakePool, UserInfo };
+
+#[derive(Accounts)]
+#[instruction(_user:Pubkey)]
+pub struct BalanceAt<'info> {
+    /// Global state PDA to read the current snapshot epoch.
+    #[account(seeds = [GlobalState::SEEDS], bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Stake pool PDA of the user stake.
+    #[account(seeds = [StakePool::SEEDS, stake_pool.mint.as_ref()], bump)]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// UserInfo PDA to read the balance checkpoints.
+    #[account(seeds = [_user.as_ref(), stake_pool.key().as_ref(), UserInfo::SEEDS], bump)]
+    pub user_info: Account<'info, UserInfo>,
+}
+
+pub fn balance_at_handler(
+    ctx: Context<BalanceAt>,
+    _user: Pubkey,
+    _timestamp: u64
+) -> Result<StakeCheckpoint> {
+    ctx.accounts.user_info.balance_at(&ctx.accounts.global_state, _timestamp)
+}
@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
-use crate::state::{ GlobalState, FundsController, UserInfo, SCALE_FACTOR };
+use crate::math::SafeMath;
+use crate::state::{ GlobalState, FundsController, LockTier, RewardSchedule, StakePool, UserInfo };
 
-/* Base instruction that will calculate user pending rewards and distribute SOLs 
+/* Base instruction that will calculate user pending rewards and distribute SOLs
 according to it, on the action of Stake, Unstake, or Claim. */
 
 #[derive(Accounts)]
@@ -12,23 +13,39 @@ pub struct Base<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
-    /// Init userInfo PDA, if it's the first time stake by user, else just load mutably.
+    /// Init userInfo PDA, if it's the first time stake by user in the pool, else just load mutably.
     #[account(
         init_if_needed,
-        seeds = [user.key().as_ref(), b"user_info"],
+        seeds = [user.key().as_ref(), stake_pool.key().as_ref(), UserInfo::SEEDS],
         bump,
         payer = user,
         space = UserInfo::LEN
     )]
     pub user_info: Account<'info, UserInfo>,
 
+    /// Update the pool state of reward accumulation and stake.
+    #[account(
+        mut,
+        seeds = [StakePool::SEEDS, stake_pool.mint.as_ref()],
+        bump,
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
     /// Update the global state of reward accumulation.
     #[account(
         mut,
         seeds = [GlobalState::SEEDS],
         bump,
     )]
-    pub global_state: Account<'info, GlobalState>,
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to start the scheduled reward cycles that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
 
     /// Transfer reward SOLs to the user.
     #[account(
@@ -45,62 +62,40 @@ pub struct Base<'info> {
 impl<'info> Base<'info> {
     /// Transer SOL from fund controller to user account.
     fn transfer_sol_to_user(&self, _amount: u64) -> Result<()> {
-        **self.funds_controller.to_account_info().try_borrow_mut_lamports()? -= _amount;
-        **self.user.to_account_info().try_borrow_mut_lamports()? += _amount;
-
-        Ok(())
+        transfer_sol_from_controller(&self.funds_controller, &self.user, _amount)
     }
 
     /// Check if fund controller has enough SOL after deducting the reward amount.
     pub fn not_have_enough_sol(&self, _amount_to_deduct: u64) -> Result<bool> {
-        let controller_sol_balance = self.funds_controller
-            .to_account_info()
-            .lamports()
-            .checked_sub(_amount_to_deduct)
-            .unwrap();
-
-        let controller_rent_exempt = Rent::get()?.minimum_balance(FundsController::LEN);
-
-        Ok(controller_sol_balance <= controller_rent_exempt)
+        not_have_enough_sol(&self.funds_controller, _amount_to_deduct)
     }
 
-    /// Calculate total pending rewards of the user, accumulated on their staked $MESH & $indexMESH.
-    pub fn calculate_pending_rewards(&self) -> Result<u128> {
-        let user_info = &self.user_info;
-        let global_state = &self.global_state;
-
-        let mesh_reward = global_state.global_acc_reward_mesh
-            .checked_sub(user_info.acc_reward_mesh)
-            .unwrap()
-            .checked_mul(GlobalState::to_u128(user_info.staked_mesh))
-            .unwrap()
-            .checked_div(SCALE_FACTOR)
-            .unwrap();
-
-        let index_mesh_reward = global_state.global_acc_reward_index_mesh
-            .checked_sub(user_info.acc_reward_index_mesh)
-            .unwrap()
-            .checked_mul(GlobalState::to_u128(user_info.staked_index_mesh))
-            .unwrap()
-            .checked_div(SCALE_FACTOR)
-            .unwrap();
-
-        let total_reward = mesh_reward.checked_add(index_mesh_reward).unwrap();
-
-        Ok(total_reward)
+    /// Update the global and pool state of reward accumulation.
+    pub fn update_reward_per_share(&mut self) -> Result<()> {
+        self.global_state.update_reward_per_share(&mut self.reward_schedule)?;
+        self.stake_pool.update_reward_per_share(&self.global_state)
     }
 
     /// Calculate pending reward, distribute it, and update the states.
+    /// Reward stream tokens are only settled into the user pending rewards, they are paid out on claim.
+    /// Redistributed unstake fees are compounded into the user stake. On emergency, the SOL rewards are
+    /// owed to the user instead of being paid, so they can be claimed once the fundsController is solvent.
     pub fn harvest_user_rewards(&mut self, _is_emergency: bool) -> Result<()> {
-        let user_rewards: u64 = self.calculate_pending_rewards()?.try_into().unwrap();
+        // Every balance change goes through the harvest, so the snapshot epoch is synced before it.
+        self.user_info.sync_snapshot_epoch(&self.global_state);
+
+        let user_rewards = self.user_info.settle_rewards(self.stake_pool.acc_reward)?;
 
         {
             let user_info = &mut self.user_info;
-            user_info.acc_reward_mesh = self.global_state.global_acc_reward_mesh;
-            user_info.acc_reward_index_mesh = self.global_state.global_acc_reward_index_mesh;
+            user_info.settle_stream_rewards(&self.stake_pool)?;
+            user_info.settle_fee_rewards(&mut self.stake_pool)?;
         }
 
-        if !_is_emergency && user_rewards > 0 {
+        if _is_emergency {
+            let user_info = &mut self.user_info;
+            user_info.unclaimed_owed = user_info.unclaimed_owed.safe_add(user_rewards)?;
+        } else if user_rewards > 0 {
             if self.not_have_enough_sol(user_rewards)? {
                 return Err(ErrorCode::InsufficientFunds.into());
             }
@@ -109,10 +104,100 @@ impl<'info> Base<'info> {
 
             let user_info = &mut self.user_info;
             user_info.total_claimed_reward = user_info.total_claimed_reward
-                .checked_add(user_rewards)
-                .unwrap();
+                .safe_add(user_rewards)?;
         }
 
         Ok(())
     }
+
+    /// Add the amount to the user and pool stake.
+    pub fn increase_stake(&mut self, _amount: u64) -> Result<()> {
+        let user_info = &mut self.user_info;
+        let stake_pool = &mut self.stake_pool;
+
+        user_info.staked = user_info.staked.safe_add(_amount)?;
+        stake_pool.total_staked = stake_pool.total_staked.safe_add(_amount)?;
+
+        Ok(())
+    }
+
+    /// Deduct the amount from the user and pool stake.
+    pub fn decrease_stake(&mut self, _amount: u64) -> Result<()> {
+        let user_info = &mut self.user_info;
+        let stake_pool = &mut self.stake_pool;
+
+        user_info.staked = user_info.staked.safe_sub(_amount)?;
+        stake_pool.total_staked = stake_pool.total_staked.safe_sub(_amount)?;
+
+        Ok(())
+    }
+
+    /// Reset the user lock multiplier back to liquid once the lock duration has passed.
+    pub fn release_expired_lock(&mut self) -> Result<()> {
+        let current_time = self.global_state.get_current_time()?;
+        release_expired_lock(&mut self.user_info, current_time)
+    }
+
+    /// Recalculate the weighted stake of the user and sync it with the pool and global weighted totals.
+    pub fn update_weighted_stake(&mut self) -> Result<()> {
+        update_weighted_stake(&mut self.global_state, &mut self.stake_pool, &mut self.user_info)
+    }
+}
+
+/// Transer SOL from fund controller to the receiver account.
+pub fn transfer_sol_from_controller(
+    _funds_controller: &Account<FundsController>,
+    _receiver: &AccountInfo,
+    _amount: u64
+) -> Result<()> {
+    **_funds_controller.to_account_info().try_borrow_mut_lamports()? -= _amount;
+    **_receiver.try_borrow_mut_lamports()? += _amount;
+
+    Ok(())
+}
+
+/// Check if fund controller has enough SOL after deducting the amount.
+pub fn not_have_enough_sol(
+    _funds_controller: &Account<FundsController>,
+    _amount_to_deduct: u64
+) -> Result<bool> {
+    let controller_sol_balance = _funds_controller
+        .to_account_info()
+        .lamports()
+        .safe_sub(_amount_to_deduct)?;
+
+    let controller_rent_exempt = Rent::get()?.minimum_balance(FundsController::LEN);
+
+    Ok(controller_sol_balance <= controller_rent_exempt)
+}
+
+/// Reset the user lock multiplier back to liquid once the lock duration has passed.
+pub fn release_expired_lock(_user_info: &mut UserInfo, _current_time: u64) -> Result<()> {
+    if !_user_info.is_locked(_current_time) {
+        _user_info.lock_multiplier = LockTier::liquid().multiplier;
+    }
+
+    Ok(())
+}
+
+/// Recalculate the weighted stake of the user and sync it with the pool and global weighted totals.
+/// The pool must be updated to the latest reward state before calling this.
+pub fn update_weighted_stake(
+    _global_state: &mut GlobalState,
+    _stake_pool: &mut StakePool,
+    _user_info: &mut UserInfo
+) -> Result<()> {
+    let weighted = _user_info.to_weighted(_user_info.staked)?;
+    let prev_pool_weighted_stake = _stake_pool.get_weighted_stake()?;
+
+    _stake_pool.total_weighted = _stake_pool.total_weighted
+        .safe_sub(_user_info.weighted)?
+        .safe_add(weighted)?;
+    _user_info.weighted = weighted;
+
+    _global_state.total_weighted_stake = _global_state.total_weighted_stake
+        .safe_sub(prev_pool_weighted_stake)?
+        .safe_add(_stake_pool.get_weighted_stake()?)?;
+
+    _user_info.write_checkpoint(_global_state)
 }
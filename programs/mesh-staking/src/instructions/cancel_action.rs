@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::ActionCancelled;
+use crate::state::{ GlobalState, TimelockAction };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelAction<'info> {
+    /// Only governance can execute the instruction.
+    pub governance: Signer<'info>,
+
+    /// CHECK: Role holder that queued the action, receives the rent of the cancelled action.
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// Global state PDA to validate the governance.
+    #[account(
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Timelock action PDA that has to be cancelled.
+    #[account(
+        mut,
+        seeds = [TimelockAction::SEEDS, timelock_action.id.to_le_bytes().as_ref()],
+        bump,
+        has_one = proposer @ ErrorCode::InvalidTimelockAction,
+        close = proposer,
+    )]
+    pub timelock_action: Account<'info, TimelockAction>,
+}
+
+pub fn cancel_action_handler(ctx: Context<CancelAction>) -> Result<()> {
+    emit_cpi!(ActionCancelled { id: ctx.accounts.timelock_action.id });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::GovernanceProposalCancelled;
+use crate::state::GlobalState;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelGovernanceProposal<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to clear the pending governance.
+    #[account(
+      mut,
+      seeds = [GlobalState::SEEDS],
+      bump,
+      has_one = governance @ ErrorCode::InvalidGovernance,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn cancel_governance_proposal_handler(ctx: Context<CancelGovernanceProposal>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+
+    if global_state.pending_governance == Pubkey::default() {
+        return Err(ErrorCode::NoPendingGovernance.into());
+    }
+
+    let pending_governance = global_state.pending_governance;
+    global_state.pending_governance = Pubkey::default();
+
+    emit_cpi!(GovernanceProposalCancelled {
+        governance: global_state.governance,
+        pending_governance,
+    });
+
+    Ok(())
+}
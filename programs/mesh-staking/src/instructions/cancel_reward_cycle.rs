@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::RewardCycleCancelled;
+use crate::math::SafeMath;
+use crate::state::{ GlobalState, RewardSchedule, PAUSE_REWARD_UPDATES };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelRewardCycle<'info> {
+    /// Only reward manager can execute and pay for the instruction.
+    #[account(mut)]
+    pub reward_manager: Signer<'info>,
+
+    /// Global state PDA to commit the scheduled rewards.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = reward_manager @ ErrorCode::InvalidRewardManager,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to store the queued reward cycles.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+}
+
+pub fn cancel_reward_cycle_handler(ctx: Context<CancelRewardCycle>, _start_time: u64) -> Result<()> {
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
+    }
+
+    let event = cancel_reward_cycle(
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.reward_schedule,
+        _start_time
+    )?;
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Remove a queued reward cycle, identified by its start time, and release its committed rewards.
+/// Shared by the direct instruction and the timelock execution.
+pub fn cancel_reward_cycle(
+    _global_state: &mut GlobalState,
+    _reward_schedule: &mut RewardSchedule,
+    _start_time: u64
+) -> Result<RewardCycleCancelled> {
+    if _global_state.is_paused(PAUSE_REWARD_UPDATES) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
+    // A cycle that is already due is started here and can no longer be cancelled.
+    _global_state.update_reward_per_share(_reward_schedule)?;
+
+    let current_time = _global_state.get_current_time()?;
+
+    let mut scheduled_cycles = _reward_schedule.get_scheduled_cycles();
+    let index = scheduled_cycles
+        .iter()
+        .position(|scheduled_cycle| scheduled_cycle.start_time == _start_time)
+        .ok_or(ErrorCode::ScheduledCycleNotFound)?;
+
+    let scheduled_cycle = scheduled_cycles.remove(index);
+    _reward_schedule.set_scheduled_cycles(scheduled_cycles, _global_state.period_end_time, current_time)?;
+
+    _global_state.total_distributed_reward = _global_state.total_distributed_reward
+        .safe_sub(scheduled_cycle.reward)?;
+
+    Ok(RewardCycleCancelled {
+        start_time: _start_time,
+        reward: scheduled_cycle.reward,
+        total_distributed_reward: _global_state.total_distributed_reward,
+    })
+}
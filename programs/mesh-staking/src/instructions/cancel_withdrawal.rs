@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use super::base::*;
+use crate::errors::ErrorCode;
+use crate::events::WithdrawalCancelled;
+use crate::math::SafeMath;
+use crate::state::{ WithdrawalTicket, PAUSE_STAKE };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    /// Base instruction for calculating & distributing user pending rewards.
+    pub base: Base<'info>,
+
+    /// Ticket PDA whose amount is staked again, closed to return the rent to the user.
+    #[account(
+        mut,
+        constraint = withdrawal_ticket.user == base.user.key() @ ErrorCode::InvalidWithdrawalTicket,
+        constraint = withdrawal_ticket.pool == base.stake_pool.key() @ ErrorCode::InvalidWithdrawalTicket
+    )]
+    pub withdrawal_ticket: Account<'info, WithdrawalTicket>,
+}
+
+pub fn cancel_withdrawal_handler(ctx: Context<CancelWithdrawal>) -> Result<()> {
+    if ctx.accounts.base.global_state.is_paused(PAUSE_STAKE) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
+    ctx.accounts.base.update_reward_per_share()?;
+    ctx.accounts.base.harvest_user_rewards(false)?;
+
+    ctx.accounts.base.increase_stake(ctx.accounts.withdrawal_ticket.amount)?;
+    ctx.accounts.base.release_expired_lock()?;
+    ctx.accounts.base.update_weighted_stake()?;
+
+    let user_info = &mut ctx.accounts.base.user_info;
+    user_info.open_tickets = user_info.open_tickets.safe_sub(1)?;
+
+    ctx.accounts.withdrawal_ticket.close(ctx.accounts.base.user.to_account_info())?;
+
+    let base = &ctx.accounts.base;
+
+    emit_cpi!(WithdrawalCancelled {
+        user: base.user.key(),
+        mint: base.stake_pool.mint,
+        ticket_id: ctx.accounts.withdrawal_ticket.id,
+        amount: ctx.accounts.withdrawal_ticket.amount,
+        user_staked: base.user_info.staked,
+        pool_total_staked: base.stake_pool.total_staked,
+        total_weighted_stake: base.global_state.total_weighted_stake,
+        global_acc_reward: base.global_state.global_acc_reward,
+        pool_acc_reward: base.stake_pool.acc_reward,
+    });
+
+    Ok(())
+}
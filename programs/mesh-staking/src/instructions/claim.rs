@@ -1,15 +1,212 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{ self, Token, TokenAccount, Transfer };
 
 use super::base::*;
+use crate::errors::ErrorCode;
+use crate::events::Claimed;
+use crate::math::SafeMath;
+use crate::state::{
+    GlobalState,
+    FundsController,
+    RewardSchedule,
+    RewardStream,
+    StakePool,
+    UserInfo,
+    MAX_REWARD_STREAMS,
+    PAUSE_CLAIM,
+};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Claim<'info> {
-    /// Base instruction for calculating & distributing user pending rewards.
-    pub base: Base<'info>,
+    /// User that is claiming the rewards.
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Update the global state of reward accumulation.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to start the scheduled reward cycles that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+
+    /// Transfer reward SOLs to the user.
+    #[account(
+        mut,
+        seeds = [FundsController::SEEDS],
+        bump,
+    )]
+    pub funds_controller: Account<'info, FundsController>,
+
+    /// The program used to transfer reward stream tokens from vaults to user token accounts.
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
 }
 
-pub fn claim_handler(ctx: Context<Claim>) -> Result<()> {
-    ctx.accounts.base.global_state.update_reward_per_share()?;
-    ctx.accounts.base.harvest_user_rewards(false)?;
+impl<'info> Claim<'info> {
+    /// Transfer reward stream tokens from the stream vault to user token account.
+    fn transfer_stream_rewards_to_user(
+        &self,
+        _reward_vault: &Account<'info, TokenAccount>,
+        _user_token_account: &Account<'info, TokenAccount>,
+        _amount: u64,
+        _funds_controller_bump: u8
+    ) -> Result<()> {
+        let signer: &[&[&[u8]]] = &[&[FundsController::SEEDS, &[_funds_controller_bump]]];
+
+        let cpi_ctx = CpiContext {
+            program: self.token_program.to_account_info(),
+            accounts: Transfer {
+                from: _reward_vault.to_account_info(),
+                to: _user_token_account.to_account_info(),
+                authority: self.funds_controller.to_account_info(),
+            },
+            remaining_accounts: Vec::new(),
+            signer_seeds: signer,
+        };
+        token::transfer(cpi_ctx, _amount)
+    }
+}
+
+/// Pairs of stake pool and userInfo PDAs are passed as remaining accounts, one pair for each pool
+/// the rewards are claimed from. They are followed by pairs of reward vault and user token account,
+/// one pair for each reward stream the tokens are claimed from.
+pub fn claim_handler<'info>(ctx: Context<'_, '_, 'info, 'info, Claim<'info>>) -> Result<()> {
+    if ctx.accounts.global_state.is_paused(PAUSE_CLAIM) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
+    let account_pairs = ctx.remaining_accounts.chunks_exact(2);
+
+    if ctx.remaining_accounts.is_empty() || !account_pairs.remainder().is_empty() {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
+    }
+
+    let (pool_accounts, stream_accounts): (Vec<&[AccountInfo]>, Vec<&[AccountInfo]>) =
+        account_pairs.partition(|accounts| accounts[0].owner == &crate::ID);
+
+    if pool_accounts.is_empty() {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
+    }
+
+    ctx.accounts.global_state.update_reward_per_share(&mut ctx.accounts.reward_schedule)?;
+
+    let global_state = &mut ctx.accounts.global_state;
+    let current_time = global_state.get_current_time()?;
+
+    let mut user_rewards: u64 = 0;
+    let mut user_infos = Vec::with_capacity(pool_accounts.len());
+
+    for accounts in pool_accounts {
+        let mut stake_pool = Account::<StakePool>::try_from(&accounts[0])?;
+        let mut user_info = Account::<UserInfo>::try_from(&accounts[1])?;
+
+        // The userInfo PDAs are only written back at the end, so a repeated pair would be paid twice.
+        if
+            user_info.user != ctx.accounts.user.key() ||
+            user_info.pool != stake_pool.key() ||
+            user_infos.iter().any(|claimed: &Account<UserInfo>| claimed.key() == user_info.key())
+        {
+            return Err(ErrorCode::InvalidRemainingAccounts.into());
+        }
+
+        stake_pool.update_reward_per_share(global_state)?;
+        user_info.sync_snapshot_epoch(global_state);
+
+        // Rewards owed by an earlier emergency unstake are paid along with the pending rewards.
+        let pending_rewards = user_info.settle_rewards(stake_pool.acc_reward)?;
+        let pool_rewards = pending_rewards.safe_add(user_info.unclaimed_owed)?;
+
+        user_info.unclaimed_owed = 0;
+        user_info.settle_stream_rewards(&stake_pool)?;
+        user_info.settle_fee_rewards(&mut stake_pool)?;
+        user_info.total_claimed_reward = user_info.total_claimed_reward
+            .safe_add(pool_rewards)?;
+        user_rewards = user_rewards.safe_add(pool_rewards)?;
+
+        release_expired_lock(&mut user_info, current_time)?;
+        update_weighted_stake(global_state, &mut stake_pool, &mut user_info)?;
+
+        stake_pool.exit(&crate::ID)?;
+        user_infos.push(user_info);
+    }
+
+    let mut claimed_streams = [false; MAX_REWARD_STREAMS];
+    let mut user_stream_rewards = [0u64; MAX_REWARD_STREAMS];
+
+    for accounts in stream_accounts {
+        let reward_vault = Account::<TokenAccount>::try_from(&accounts[0])?;
+        let user_token_account = Account::<TokenAccount>::try_from(&accounts[1])?;
+
+        let index = ctx.accounts.global_state.reward_streams
+            .iter()
+            .position(|reward_stream| {
+                reward_stream.is_opened() && reward_stream.mint == reward_vault.mint
+            })
+            .ok_or(ErrorCode::InvalidRemainingAccounts)?;
+
+        if
+            claimed_streams[index] ||
+            reward_vault.key() != RewardStream::find_vault(&reward_vault.mint) ||
+            user_token_account.mint != reward_vault.mint ||
+            user_token_account.owner != ctx.accounts.user.key()
+        {
+            return Err(ErrorCode::InvalidRemainingAccounts.into());
+        }
+        claimed_streams[index] = true;
+
+        let mut stream_rewards: u64 = 0;
+
+        for user_info in user_infos.iter_mut() {
+            stream_rewards = stream_rewards
+                .safe_add(user_info.stream_pending_rewards[index])?;
+            user_info.stream_pending_rewards[index] = 0;
+        }
+
+        user_stream_rewards[index] = stream_rewards;
+
+        if stream_rewards > 0 {
+            ctx.accounts.transfer_stream_rewards_to_user(
+                &reward_vault,
+                &user_token_account,
+                stream_rewards,
+                ctx.bumps.funds_controller
+            )?;
+        }
+    }
+
+    for user_info in user_infos {
+        user_info.exit(&crate::ID)?;
+    }
+
+    if user_rewards > 0 {
+        if not_have_enough_sol(&ctx.accounts.funds_controller, user_rewards)? {
+            return Err(ErrorCode::InsufficientFunds.into());
+        }
+
+        transfer_sol_from_controller(
+            &ctx.accounts.funds_controller,
+            &ctx.accounts.user.to_account_info(),
+            user_rewards
+        )?;
+    }
+
+    emit_cpi!(Claimed {
+        user: ctx.accounts.user.key(),
+        reward: user_rewards,
+        stream_rewards: user_stream_rewards,
+        total_weighted_stake: ctx.accounts.global_state.total_weighted_stake,
+        global_acc_reward: ctx.accounts.global_state.global_acc_reward,
+    });
+
     Ok(())
 }
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{ self, Token, TokenAccount, Transfer };
+
+use super::base::*;
+use crate::errors::ErrorCode;
+use crate::events::DistributionClaimed;
+use crate::math::SafeMath;
+use crate::state::{ Distribution, GlobalState, FundsController, PAUSE_CLAIM };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimDistribution<'info> {
+    /// User of the claimed leaf.
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Global state PDA to check the paused instructions.
+    #[account(
+        seeds = [GlobalState::SEEDS],
+        bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Distribution PDA to verify the proof and store the claimed leaf.
+    #[account(
+        mut,
+        seeds = [Distribution::SEEDS, distribution.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub distribution: Box<Account<'info, Distribution>>,
+
+    /// Transfer distributed SOLs to the user.
+    #[account(
+        mut,
+        seeds = [FundsController::SEEDS],
+        bump,
+    )]
+    pub funds_controller: Account<'info, FundsController>,
+
+    /// Distribution vault that holds the distributed tokens. Omitted for SOL distributions.
+    #[account(
+        mut,
+        token::mint = distribution.mint,
+        token::authority = distribution,
+    )]
+    pub distribution_vault: Option<Account<'info, TokenAccount>>,
+
+    /// User token account that receives the distributed tokens. Omitted for SOL distributions.
+    #[account(
+        mut,
+        token::mint = distribution.mint,
+    )]
+    pub user_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The program used to transfer the distributed tokens. Omitted for SOL distributions.
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+impl<'info> ClaimDistribution<'info> {
+    /// Transfer distributed tokens from the distribution vault to the user token account.
+    fn transfer_tokens_to_user(&self, _amount: u64, _distribution_bump: u8) -> Result<()> {
+        let (Some(distribution_vault), Some(user_token_account), Some(token_program)) = (
+            &self.distribution_vault,
+            &self.user_token_account,
+            &self.token_program,
+        ) else {
+            return Err(ErrorCode::InvalidDistribution.into());
+        };
+
+        let id = self.distribution.id.to_le_bytes();
+        let signer: &[&[&[u8]]] = &[&[Distribution::SEEDS, id.as_ref(), &[_distribution_bump]]];
+
+        let cpi_ctx = CpiContext {
+            program: token_program.to_account_info(),
+            accounts: Transfer {
+                from: distribution_vault.to_account_info(),
+                to: user_token_account.to_account_info(),
+                authority: self.distribution.to_account_info(),
+            },
+            remaining_accounts: Vec::new(),
+            signer_seeds: signer,
+        };
+        token::transfer(cpi_ctx, _amount)
+    }
+}
+
+/// The leaf is the keccak hash of `(index, user, amount)` and the proof lists the sibling nodes from the
+/// leaf up to the root.
+pub fn claim_distribution_handler(
+    ctx: Context<ClaimDistribution>,
+    _index: u64,
+    _amount: u64,
+    _proof: Vec<[u8; 32]>
+) -> Result<()> {
+    if ctx.accounts.global_state.is_paused(PAUSE_CLAIM) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
+    let current_time = ctx.accounts.global_state.get_current_time()?;
+    let distribution = &mut ctx.accounts.distribution;
+
+    if !distribution.is_funded {
+        return Err(ErrorCode::DistributionNotFunded.into());
+    }
+
+    if distribution.is_expired(current_time) {
+        return Err(ErrorCode::DistributionExpired.into());
+    }
+
+    if _index >= distribution.num_leaves {
+        return Err(ErrorCode::InvalidDistribution.into());
+    }
+
+    if distribution.is_claimed(_index)? {
+        return Err(ErrorCode::DistributionAlreadyClaimed.into());
+    }
+
+    if !distribution.verify_proof(_index, ctx.accounts.user.key(), _amount, &_proof) {
+        return Err(ErrorCode::InvalidMerkleProof.into());
+    }
+
+    distribution.set_claimed(_index)?;
+    distribution.claimed_amount = distribution.claimed_amount.safe_add(_amount)?;
+
+    // A root with leaves above the deposited amount can not drain other funds.
+    if distribution.claimed_amount > distribution.total_amount {
+        return Err(ErrorCode::InvalidDistribution.into());
+    }
+
+    let is_sol = distribution.mint == Pubkey::default();
+    let claimed_amount = distribution.claimed_amount;
+
+    if is_sol {
+        if not_have_enough_sol(&ctx.accounts.funds_controller, _amount)? {
+            return Err(ErrorCode::InsufficientFunds.into());
+        }
+
+        transfer_sol_from_controller(
+            &ctx.accounts.funds_controller,
+            &ctx.accounts.user.to_account_info(),
+            _amount
+        )?;
+    } else {
+        ctx.accounts.transfer_tokens_to_user(_amount, ctx.bumps.distribution)?;
+    }
+
+    emit_cpi!(DistributionClaimed {
+        distribution: ctx.accounts.distribution.key(),
+        user: ctx.accounts.user.key(),
+        index: _index,
+        amount: _amount,
+        claimed_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{ self, Token, TokenAccount, Transfer };
+
+use super::base::*;
+use crate::errors::ErrorCode;
+use crate::events::RewardCampaignClosed;
+use crate::math::SafeMath;
+use crate::state::{ CampaignState, GlobalState, FundsController, RewardCampaign, RewardSchedule, RewardStream };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseRewardCampaign<'info> {
+    /// Anyone can close an ended campaign, as the rent and refund are only returned to its creator.
+    pub closer: Signer<'info>,
+
+    /// CHECK: Only the creator of the campaign receives the rent back, along with the SOL refund of a
+    /// sponsored campaign.
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Global state PDA to free the campaign slot.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to start the scheduled reward cycles that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+
+    /// Reward campaign PDA to be closed.
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator,
+        seeds = [RewardCampaign::SEEDS, reward_campaign.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub reward_campaign: Account<'info, RewardCampaign>,
+
+    /// Refund the stranded SOLs of a sponsored campaign to its creator.
+    #[account(
+        mut,
+        seeds = [FundsController::SEEDS],
+        bump,
+    )]
+    pub funds_controller: Account<'info, FundsController>,
+
+    /// Reward stream vault PDA that refunds the stranded tokens. Only passed for sponsored token campaigns.
+    #[account(
+        mut,
+        seeds = [RewardStream::VAULT_SEEDS, reward_vault.mint.as_ref()],
+        bump,
+        token::authority = funds_controller,
+    )]
+    pub reward_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Creator token account that receives the stranded tokens. Only passed for sponsored token campaigns.
+    #[account(
+        mut,
+        token::authority = creator,
+    )]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The program used to refund the stranded tokens. Only passed for sponsored token campaigns.
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+impl<'info> CloseRewardCampaign<'info> {
+    /// Transfer stranded campaign tokens from the reward stream vault to the creator token account.
+    fn transfer_tokens_to_creator(&self, _amount: u64, _funds_controller_bump: u8) -> Result<()> {
+        let (Some(reward_vault), Some(creator_token_account), Some(token_program)) = (
+            &self.reward_vault,
+            &self.creator_token_account,
+            &self.token_program,
+        ) else {
+            return Err(ErrorCode::InvalidCampaignMint.into());
+        };
+
+        if
+            reward_vault.mint != self.reward_campaign.reward_mint ||
+            creator_token_account.mint != self.reward_campaign.reward_mint
+        {
+            return Err(ErrorCode::InvalidMint.into());
+        }
+
+        let signer: &[&[&[u8]]] = &[&[FundsController::SEEDS, &[_funds_controller_bump]]];
+
+        let cpi_ctx = CpiContext {
+            program: token_program.to_account_info(),
+            accounts: Transfer {
+                from: reward_vault.to_account_info(),
+                to: creator_token_account.to_account_info(),
+                authority: self.funds_controller.to_account_info(),
+            },
+            remaining_accounts: Vec::new(),
+            signer_seeds: signer,
+        };
+        token::transfer(cpi_ctx, _amount)
+    }
+}
+
+pub fn close_reward_campaign_handler(ctx: Context<CloseRewardCampaign>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let slot = ctx.accounts.reward_campaign.slot as usize;
+
+    if global_state.reward_campaigns[slot].campaign != ctx.accounts.reward_campaign.key() {
+        return Err(ErrorCode::InvalidRewardCampaign.into());
+    }
+
+    let current_time = global_state.get_current_time()?;
+
+    if !global_state.reward_campaigns[slot].is_ended(current_time) {
+        return Err(ErrorCode::RewardCampaignNotEnded.into());
+    }
+
+    global_state.update_reward_per_share(&mut ctx.accounts.reward_schedule)?;
+
+    let campaign = global_state.reward_campaigns[slot];
+    let stranded_reward = campaign.stranded_reward;
+    let mut refunded_reward: u64 = 0;
+
+    if !campaign.sponsored {
+        // The rewards stranded while nothing was staked are handled with the stranded rewards of the cycle.
+        global_state.stranded_reward = global_state.stranded_reward.safe_add(stranded_reward)?;
+    } else if stranded_reward > 0 {
+        // The sponsor deposited the campaign rewards, so the rewards never allocated to stakers are refunded.
+        refunded_reward = stranded_reward;
+
+        if campaign.is_sol() {
+            if not_have_enough_sol(&ctx.accounts.funds_controller, refunded_reward)? {
+                return Err(ErrorCode::InsufficientFunds.into());
+            }
+
+            global_state.total_funded = global_state.total_funded.saturating_sub(refunded_reward);
+            global_state.total_distributed_reward =
+                global_state.total_distributed_reward.saturating_sub(refunded_reward);
+        } else {
+            let reward_stream = global_state.reward_streams
+                .iter_mut()
+                .find(|reward_stream| reward_stream.mint == campaign.reward_mint)
+                .ok_or(ErrorCode::InvalidCampaignMint)?;
+            reward_stream.total_funded = reward_stream.total_funded.saturating_sub(refunded_reward);
+            reward_stream.total_distributed_reward =
+                reward_stream.total_distributed_reward.saturating_sub(refunded_reward);
+        }
+    }
+
+    global_state.reward_campaigns[slot] = CampaignState::default();
+
+    if refunded_reward > 0 {
+        if campaign.is_sol() {
+            transfer_sol_from_controller(
+                &ctx.accounts.funds_controller,
+                &ctx.accounts.creator,
+                refunded_reward
+            )?;
+        } else {
+            ctx.accounts.transfer_tokens_to_creator(refunded_reward, ctx.bumps.funds_controller)?;
+        }
+    }
+
+    emit_cpi!(RewardCampaignClosed {
+        campaign: ctx.accounts.reward_campaign.key(),
+        id: ctx.accounts.reward_campaign.id,
+        stranded_reward,
+        refunded_reward,
+    });
+
+    Ok(())
+}
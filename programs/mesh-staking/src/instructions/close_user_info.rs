@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
+use crate::events::UserInfoClosed;
 use crate::state::UserInfo;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CloseUserInfo<'info> {
     /// User who had paid for userInfo PDA.
@@ -12,7 +14,7 @@ pub struct CloseUserInfo<'info> {
     /// UserInfo PDA that has to be closed.
     #[account(
         mut,
-        seeds = [user.key().as_ref(), b"user_info"],
+        seeds = [user.key().as_ref(), user_info.pool.as_ref(), UserInfo::SEEDS],
         bump,
     )]
     pub user_info: Account<'info, UserInfo>,
@@ -21,14 +23,35 @@ pub struct CloseUserInfo<'info> {
 pub fn close_user_info_handler(ctx: Context<CloseUserInfo>) -> Result<()> {
     let user_info = &mut ctx.accounts.user_info;
 
-    // Not allowed to close PDA if the user still has some staked $MESH or $indexMESH.
-    if user_info.staked_mesh != 0 || user_info.staked_index_mesh != 0 {
+    // Not allowed to close PDA if the user still has some staked tokens in the pool.
+    if user_info.staked != 0 {
         return Err(ErrorCode::StakedNotZero.into());
     }
 
+    // Ticket ids restart from zero on a new PDA, so all tickets have to be redeemed before closing.
+    if user_info.open_tickets != 0 {
+        return Err(ErrorCode::OpenTicketsNotZero.into());
+    }
+
+    // Rewards owed by an emergency unstake or withdrawal would be lost with the PDA.
+    if user_info.unclaimed_owed != 0 || user_info.unclaimed_fee_owed != 0 {
+        return Err(ErrorCode::UnclaimedOwedNotZero.into());
+    }
+
+    // Unclaimed stream rewards would be lost with the PDA while staying committed in their stream.
+    if user_info.stream_pending_rewards.iter().any(|reward| *reward != 0) {
+        return Err(ErrorCode::StreamPendingRewardsNotZero.into());
+    }
+
     let amount = **user_info.to_account_info().try_borrow_mut_lamports()?;
     **user_info.to_account_info().try_borrow_mut_lamports()? -= amount;
     **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += amount;
 
+    emit_cpi!(UserInfoClosed {
+        user: ctx.accounts.user.key(),
+        pool: ctx.accounts.user_info.pool,
+        total_claimed_reward: ctx.accounts.user_info.total_claimed_reward,
+    });
+
     Ok(())
 }
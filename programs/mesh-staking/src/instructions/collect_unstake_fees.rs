@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{ self, Mint, TokenAccount, TokenInterface, TransferChecked };
+
+use crate::errors::ErrorCode;
+use crate::events::UnstakeFeesCollected;
+use crate::state::{ GlobalState, FundsController, StakePool };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CollectUnstakeFees<'info> {
+    /// Anyone can collect the fees, as they are only sent to the treasury.
+    pub collector: Signer<'info>,
+
+    /// Global state PDA to read the treasury.
+    #[account(seeds = [GlobalState::SEEDS], bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Stake pool PDA holding the accrued fees.
+    #[account(
+        mut,
+        seeds = [StakePool::SEEDS, mint.key().as_ref()],
+        bump,
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// PDA that holds the ownership of the vault.
+    #[account(seeds = [FundsController::SEEDS], bump)]
+    pub funds_controller: Account<'info, FundsController>,
+
+    /// Mint address of the staked token.
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// ATA of fundsConrtoller that is holding mint.
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = funds_controller,
+        associated_token::token_program = token_program
+    )]
+    pub mint_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token account of the treasury which will receive the fees.
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = treasury_token_account.owner == global_state.unstake_fee.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The program used to transfer token from vault to treasury, either Token or Token-2022.
+    #[account(address = mint.to_account_info().owner.key() @ ErrorCode::InvalidMint)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CollectUnstakeFees<'info> {
+    /// Transfer accrued fees from fundsController ATA to the treasury token account.
+    fn transfer_fees_from_vault_to_treasury(
+        &self,
+        _amount: u64,
+        _funds_controller_bump: u8
+    ) -> Result<()> {
+        let signer: &[&[&[u8]]] = &[&[FundsController::SEEDS, &[_funds_controller_bump]]];
+
+        let cpi_ctx = CpiContext {
+            program: self.token_program.to_account_info(),
+            accounts: TransferChecked {
+                from: self.mint_vault.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: self.treasury_token_account.to_account_info(),
+                authority: self.funds_controller.to_account_info(),
+            },
+            remaining_accounts: Vec::new(),
+            signer_seeds: signer,
+        };
+        token_interface::transfer_checked(cpi_ctx, _amount, self.mint.decimals)
+    }
+}
+
+pub fn collect_unstake_fees_handler(ctx: Context<CollectUnstakeFees>) -> Result<()> {
+    let amount = ctx.accounts.stake_pool.accrued_fees;
+
+    if amount == 0 {
+        return Err(ErrorCode::ZeroInput.into());
+    }
+
+    ctx.accounts.stake_pool.accrued_fees = 0;
+    ctx.accounts.transfer_fees_from_vault_to_treasury(amount, ctx.bumps.funds_controller)?;
+
+    emit_cpi!(UnstakeFeesCollected {
+        mint: ctx.accounts.mint.key(),
+        treasury_token_account: ctx.accounts.treasury_token_account.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{ self, Mint, Token, TokenAccount, Transfer };
+
+use crate::errors::ErrorCode;
+use crate::events::DistributionCreated;
+use crate::math::{ SafeCast, SafeMath };
+use crate::state::{ Distribution, GlobalState, MAX_DISTRIBUTION_LEAVES };
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(_merkle_root: [u8; 32], _num_leaves: u64)]
+pub struct CreateDistribution<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to keep track of the distribution ids.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Distribution PDA to store the merkle root and the claimed bitmap.
+    #[account(
+        init,
+        payer = governance,
+        space = Distribution::space(_num_leaves),
+        seeds = [Distribution::SEEDS, global_state.next_distribution_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub distribution: Box<Account<'info, Distribution>>,
+
+    /// Mint address of the distributed token. Omitted for SOL distributions.
+    pub reward_mint: Option<Account<'info, Mint>>,
+
+    /// ATA of the distribution to hold the distributed tokens. Omitted for SOL distributions.
+    #[account(
+        init,
+        payer = governance,
+        associated_token::mint = reward_mint,
+        associated_token::authority = distribution
+    )]
+    pub distribution_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Governance token account that deposits the distributed tokens. Omitted for SOL distributions.
+    #[account(
+        mut,
+        token::authority = governance,
+    )]
+    pub governance_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The program used to create the vault and deposit the tokens. Omitted for SOL distributions.
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// The program used to create the vault. Omitted for SOL distributions.
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    /// The program used to create the distribution PDA.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateDistribution<'info> {
+    /// Transfer the distributed tokens from governance token account to the distribution vault.
+    fn transfer_tokens_to_vault(
+        &self,
+        _governance_token_account: &Account<'info, TokenAccount>,
+        _distribution_vault: &Account<'info, TokenAccount>,
+        _token_program: &Program<'info, Token>,
+        _amount: u64
+    ) -> Result<()> {
+        let cpi_ctx = CpiContext::new(_token_program.to_account_info(), Transfer {
+            from: _governance_token_account.to_account_info(),
+            to: _distribution_vault.to_account_info(),
+            authority: self.governance.to_account_info(),
+        });
+        token::transfer(cpi_ctx, _amount)
+    }
+}
+
+/// Token distributions are deposited by governance on creation. SOL distributions omit the token accounts
+/// and are paid from the fundsController once funded with `fund_distribution`.
+pub fn create_distribution_handler(
+    ctx: Context<CreateDistribution>,
+    _merkle_root: [u8; 32],
+    _num_leaves: u64,
+    _total_amount: u64,
+    _expiry_time: u64
+) -> Result<()> {
+    if _num_leaves == 0 || _num_leaves > MAX_DISTRIBUTION_LEAVES {
+        return Err(ErrorCode::InvalidDistribution.into());
+    }
+
+    if _total_amount == 0 {
+        return Err(ErrorCode::ZeroInput.into());
+    }
+
+    if _expiry_time <= ctx.accounts.global_state.get_current_time()? {
+        return Err(ErrorCode::InvalidDistribution.into());
+    }
+
+    let accounts = &ctx.accounts;
+
+    let mint = match
+        (
+            &accounts.reward_mint,
+            &accounts.distribution_vault,
+            &accounts.governance_token_account,
+            &accounts.token_program,
+        )
+    {
+        (None, None, None, None) => Pubkey::default(),
+        (
+            Some(reward_mint),
+            Some(distribution_vault),
+            Some(governance_token_account),
+            Some(token_program),
+        ) => {
+            if governance_token_account.mint != reward_mint.key() {
+                return Err(ErrorCode::InvalidMint.into());
+            }
+
+            accounts.transfer_tokens_to_vault(
+                governance_token_account,
+                distribution_vault,
+                token_program,
+                _total_amount
+            )?;
+
+            reward_mint.key()
+        }
+        _ => {
+            return Err(ErrorCode::InvalidDistribution.into());
+        }
+    };
+
+    let global_state = &mut ctx.accounts.global_state;
+    let id = global_state.next_distribution_id;
+    global_state.next_distribution_id = id.safe_add(1)?;
+
+    let distribution = &mut ctx.accounts.distribution;
+    distribution.id = id;
+    distribution.mint = mint;
+    distribution.merkle_root = _merkle_root;
+    distribution.num_leaves = _num_leaves;
+    distribution.total_amount = _total_amount;
+    distribution.claimed_amount = 0;
+    distribution.expiry_time = _expiry_time;
+    distribution.is_funded = mint != Pubkey::default();
+    distribution.claimed_bitmap = vec![0; _num_leaves.div_ceil(8).safe_cast()?];
+
+    emit_cpi!(DistributionCreated {
+        distribution: distribution.key(),
+        id,
+        mint,
+        merkle_root: _merkle_root,
+        num_leaves: _num_leaves,
+        total_amount: _total_amount,
+        expiry_time: _expiry_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::RewardCycleEdited;
+use crate::math::SafeMath;
+use crate::state::{ GlobalState, RewardSchedule, ScheduledCycle, PAUSE_REWARD_UPDATES };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EditRewardCycle<'info> {
+    /// Only reward manager can execute and pay for the instruction.
+    #[account(mut)]
+    pub reward_manager: Signer<'info>,
+
+    /// Global state PDA to commit the scheduled rewards.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = reward_manager @ ErrorCode::InvalidRewardManager,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to store the queued reward cycles.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+}
+
+pub fn edit_reward_cycle_handler(
+    ctx: Context<EditRewardCycle>,
+    _start_time: u64,
+    _reward: u64,
+    _distribution_time: u64,
+    _new_start_time: u64
+) -> Result<()> {
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
+    }
+
+    let event = edit_reward_cycle(
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.reward_schedule,
+        _start_time,
+        _reward,
+        _distribution_time,
+        _new_start_time
+    )?;
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Replace a queued reward cycle, identified by its start time, adjusting the committed rewards.
+/// Shared by the direct instruction and the timelock execution.
+pub fn edit_reward_cycle(
+    _global_state: &mut GlobalState,
+    _reward_schedule: &mut RewardSchedule,
+    _start_time: u64,
+    _reward: u64,
+    _distribution_time: u64,
+    _new_start_time: u64
+) -> Result<RewardCycleEdited> {
+    if _global_state.is_paused(PAUSE_REWARD_UPDATES) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
+    if _reward == 0 || _distribution_time == 0 {
+        return Err(ErrorCode::ZeroInput.into());
+    }
+
+    // A cycle that is already due is started here and can no longer be edited.
+    _global_state.update_reward_per_share(_reward_schedule)?;
+
+    let current_time = _global_state.get_current_time()?;
+
+    let mut scheduled_cycles = _reward_schedule.get_scheduled_cycles();
+    let scheduled_cycle = scheduled_cycles
+        .iter_mut()
+        .find(|scheduled_cycle| scheduled_cycle.start_time == _start_time)
+        .ok_or(ErrorCode::ScheduledCycleNotFound)?;
+
+    let prev_reward = scheduled_cycle.reward;
+    *scheduled_cycle = ScheduledCycle {
+        reward: _reward,
+        distribution_time: _distribution_time,
+        start_time: _new_start_time,
+    };
+    _reward_schedule.set_scheduled_cycles(scheduled_cycles, _global_state.period_end_time, current_time)?;
+
+    _global_state.total_distributed_reward = _global_state.total_distributed_reward
+        .safe_sub(prev_reward)?
+        .safe_add(_reward)?;
+
+    if !_global_state.is_reward_funded() {
+        return Err(ErrorCode::RewardExceedsFunds.into());
+    }
+
+    Ok(RewardCycleEdited {
+        prev_start_time: _start_time,
+        reward: _reward,
+        distribution_time: _distribution_time,
+        start_time: _new_start_time,
+        total_distributed_reward: _global_state.total_distributed_reward,
+    })
+}
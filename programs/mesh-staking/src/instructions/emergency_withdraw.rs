@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{ self, Mint, TokenAccount, TokenInterface, TransferChecked };
+
+use crate::errors::ErrorCode;
+use crate::events::EmergencyWithdrawn;
+use crate::state::{ GlobalState, FundsController, StakePool, UserInfo };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    /// User who is withdrawing their staked tokens.
+    pub user: Signer<'info>,
+
+    /// UserInfo PDA whose staked tokens are withdrawn.
+    #[account(
+        mut,
+        seeds = [user.key().as_ref(), stake_pool.key().as_ref(), UserInfo::SEEDS],
+        bump,
+    )]
+    pub user_info: Account<'info, UserInfo>,
+
+    /// Stake pool PDA to deduct the withdrawn tokens from.
+    #[account(
+        mut,
+        seeds = [StakePool::SEEDS, mint.key().as_ref()],
+        bump,
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Global state PDA to deduct the weighted stake from.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// PDA that holds the ownership of the vault.
+    #[account(seeds = [FundsController::SEEDS], bump)]
+    pub funds_controller: Account<'info, FundsController>,
+
+    /// Mint address of the staked token.
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// ATA of fundsConrtoller that is holding mint.
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = funds_controller,
+        associated_token::token_program = token_program
+    )]
+    pub mint_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// ATA of user which will receive mint.
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_mint_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The program used to transfer token from vault to user ATA, either Token or Token-2022.
+    #[account(address = mint.to_account_info().owner.key() @ ErrorCode::InvalidMint)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> EmergencyWithdraw<'info> {
+    /// Transfer staked tokens from fundsController ATA to user ATA.
+    fn transfer_tokens_from_vault_to_user(
+        &self,
+        _amount: u64,
+        _funds_controller_bump: u8
+    ) -> Result<()> {
+        let signer: &[&[&[u8]]] = &[&[FundsController::SEEDS, &[_funds_controller_bump]]];
+
+        let cpi_ctx = CpiContext {
+            program: self.token_program.to_account_info(),
+            accounts: TransferChecked {
+                from: self.mint_vault.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: self.user_mint_token_account.to_account_info(),
+                authority: self.funds_controller.to_account_info(),
+            },
+            remaining_accounts: Vec::new(),
+            signer_seeds: signer,
+        };
+        token_interface::transfer_checked(cpi_ctx, _amount, self.mint.decimals)
+    }
+}
+
+/// Return the staked tokens of the user using only the stored balances. The reward accumulators are
+/// left untouched, the pending rewards of the user up to the stored pool accumulators are kept as owed.
+pub fn emergency_withdraw_handler(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+    if !ctx.accounts.global_state.safe_mode {
+        return Err(ErrorCode::SafeModeNotActive.into());
+    }
+
+    let amount = ctx.accounts.user_info.staked;
+
+    if amount == 0 {
+        return Err(ErrorCode::ZeroInput.into());
+    }
+
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let user_info = &mut ctx.accounts.user_info;
+    let global_state = &mut ctx.accounts.global_state;
+
+    // Only subtractions are used, so the balances can't fail even if the reward state is broken.
+    let prev_pool_weighted_stake = stake_pool.get_weighted_stake();
+
+    stake_pool.total_staked = stake_pool.total_staked.saturating_sub(amount);
+    stake_pool.total_weighted = stake_pool.total_weighted.saturating_sub(user_info.weighted);
+    // The pending rewards are settled against the stored pool accumulator, a failing settlement owes nothing.
+    let user_rewards = user_info.settle_rewards(stake_pool.acc_reward).unwrap_or(0);
+    user_info.unclaimed_owed = user_info.unclaimed_owed.saturating_add(user_rewards);
+    user_info.settle_emergency_rewards(stake_pool);
+
+    user_info.sync_snapshot_epoch(global_state);
+    user_info.staked = 0;
+    user_info.weighted = 0;
+    user_info.write_checkpoint(global_state)?;
+
+    // A failing weighted stake calculation can't block the exit, the global total is then left to reconciliation.
+    if let (Ok(prev_pool_weighted_stake), Ok(pool_weighted_stake)) = (
+        prev_pool_weighted_stake,
+        stake_pool.get_weighted_stake(),
+    ) {
+        global_state.total_weighted_stake = global_state.total_weighted_stake.saturating_sub(
+            prev_pool_weighted_stake.saturating_sub(pool_weighted_stake)
+        );
+    }
+
+    ctx.accounts.transfer_tokens_from_vault_to_user(amount, ctx.bumps.funds_controller)?;
+
+    emit_cpi!(EmergencyWithdrawn {
+        user: ctx.accounts.user.key(),
+        mint: ctx.accounts.mint.key(),
+        amount,
+        unclaimed_owed: ctx.accounts.user_info.unclaimed_owed,
+        unclaimed_fee_owed: ctx.accounts.user_info.unclaimed_fee_owed,
+    });
+
+    Ok(())
+}
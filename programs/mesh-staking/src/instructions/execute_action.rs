@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+
+use super::cancel_reward_cycle::cancel_reward_cycle;
+use super::edit_reward_cycle::edit_reward_cycle;
+use super::fund_distribution::fund_distribution;
+use super::migrate_funds::migrate_funds;
+use super::schedule_reward_cycle::schedule_reward_cycle;
+use super::update_lock_tiers::update_lock_tiers;
+use super::update_period_end::update_period_end;
+use super::update_rewards::update_rewards;
+use super::update_timelock_delay::update_timelock_delay;
+use super::update_unbonding_cooldown::update_unbonding_cooldown;
+use super::update_unstake_fee::update_unstake_fee;
+use super::update_weightage::update_weightage;
+use super::withdraw_stranded_reward::withdraw_stranded_reward;
+use crate::errors::ErrorCode;
+use crate::events::ActionExecuted;
+use crate::state::{
+    Distribution,
+    GlobalState,
+    GovernanceAction,
+    FundsController,
+    RewardSchedule,
+    StakePool,
+    TimelockAction,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteAction<'info> {
+    /// Anyone can execute a queued action once its eta has passed.
+    pub executor: Signer<'info>,
+
+    /// CHECK: Governance that queued the action, receives the rent of the executed action.
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// Global state PDA to apply the parameter change.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to store the queued reward cycles and start the ones that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+
+    /// Timelock action PDA that has to be executed.
+    #[account(
+        mut,
+        seeds = [TimelockAction::SEEDS, timelock_action.id.to_le_bytes().as_ref()],
+        bump,
+        has_one = proposer @ ErrorCode::InvalidTimelockAction,
+        close = proposer,
+    )]
+    pub timelock_action: Account<'info, TimelockAction>,
+
+    /// Transfer reward SOLs to new version, only used by the migrate funds action.
+    #[account(
+        mut,
+        seeds = [FundsController::SEEDS],
+        bump,
+    )]
+    pub funds_controller: Account<'info, FundsController>,
+
+    /// Stake pool PDA to store updated weightage, only required by the update weightage action.
+    #[account(
+        mut,
+        seeds = [StakePool::SEEDS, stake_pool.mint.as_ref()],
+        bump,
+    )]
+    pub stake_pool: Option<Account<'info, StakePool>>,
+
+    /// CHECK: New staking version, only required by the migrate funds action.
+    #[account(mut)]
+    pub new_version: Option<UncheckedAccount<'info>>,
+
+    /// Distribution PDA to be funded, only required by the fund distribution action.
+    #[account(
+        mut,
+        seeds = [Distribution::SEEDS, distribution.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub distribution: Option<Box<Account<'info, Distribution>>>,
+
+    /// CHECK: Treasury set with the unstake fee, only required by the withdraw stranded reward action.
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+}
+
+pub fn execute_action_handler(ctx: Context<ExecuteAction>) -> Result<()> {
+    let timelock_action = &ctx.accounts.timelock_action;
+
+    if ctx.accounts.global_state.get_current_time()? < timelock_action.eta {
+        return Err(ErrorCode::TimelockNotPassed.into());
+    }
+
+    let id = timelock_action.id;
+    let global_state = &mut ctx.accounts.global_state;
+    let reward_schedule = &mut ctx.accounts.reward_schedule;
+
+    match timelock_action.action {
+        GovernanceAction::UpdateWeightage { stake_pool, weightage } => {
+            let stake_pool_account = match ctx.accounts.stake_pool.as_mut() {
+                Some(stake_pool_account) if stake_pool_account.key() == stake_pool => {
+                    stake_pool_account
+                }
+                _ => {
+                    return Err(ErrorCode::InvalidTimelockAction.into());
+                }
+            };
+
+            let event = update_weightage(global_state, reward_schedule, stake_pool_account, weightage)?;
+            emit_cpi!(event);
+        }
+        GovernanceAction::UpdateRewards { reward, distribution_time, roll_over_stranded } => {
+            let event = update_rewards(
+                global_state,
+                reward_schedule,
+                reward,
+                distribution_time,
+                roll_over_stranded
+            )?;
+            emit_cpi!(event);
+        }
+        GovernanceAction::UpdatePeriodEnd { distribution_time } => {
+            let event = update_period_end(global_state, reward_schedule, distribution_time)?;
+            emit_cpi!(event);
+        }
+        GovernanceAction::MigrateFunds { new_version, amount } => {
+            let new_version_account = match ctx.accounts.new_version.as_ref() {
+                Some(new_version_account) if new_version_account.key() == new_version => {
+                    new_version_account
+                }
+                _ => {
+                    return Err(ErrorCode::InvalidTimelockAction.into());
+                }
+            };
+
+            let event = migrate_funds(
+                global_state,
+                &ctx.accounts.funds_controller,
+                new_version_account,
+                amount
+            )?;
+            emit_cpi!(event);
+        }
+        GovernanceAction::UpdateTimelockDelay { timelock_delay } => {
+            let event = update_timelock_delay(global_state, timelock_delay)?;
+            emit_cpi!(event);
+        }
+        GovernanceAction::ScheduleRewardCycle { reward, distribution_time, start_time } => {
+            let event = schedule_reward_cycle(
+                global_state,
+                reward_schedule,
+                reward,
+                distribution_time,
+                start_time
+            )?;
+            emit_cpi!(event);
+        }
+        GovernanceAction::EditRewardCycle { start_time, reward, distribution_time, new_start_time } => {
+            let event = edit_reward_cycle(
+                global_state,
+                reward_schedule,
+                start_time,
+                reward,
+                distribution_time,
+                new_start_time
+            )?;
+            emit_cpi!(event);
+        }
+        GovernanceAction::CancelRewardCycle { start_time } => {
+            let event = cancel_reward_cycle(global_state, reward_schedule, start_time)?;
+            emit_cpi!(event);
+        }
+        GovernanceAction::FundDistribution { distribution } => {
+            let distribution_account = match ctx.accounts.distribution.as_mut() {
+                Some(distribution_account) if distribution_account.key() == distribution => {
+                    distribution_account
+                }
+                _ => {
+                    return Err(ErrorCode::InvalidTimelockAction.into());
+                }
+            };
+
+            let event = fund_distribution(global_state, distribution_account)?;
+            emit_cpi!(event);
+        }
+        GovernanceAction::UpdateUnbondingCooldown { unbonding_cooldown } => {
+            let event = update_unbonding_cooldown(global_state, unbonding_cooldown)?;
+            emit_cpi!(event);
+        }
+        GovernanceAction::UpdateUnstakeFee { unstake_fee } => {
+            let event = update_unstake_fee(global_state, unstake_fee)?;
+            emit_cpi!(event);
+        }
+        GovernanceAction::UpdateLockTiers { lock_tiers } => {
+            let event = update_lock_tiers(global_state, &lock_tiers)?;
+            emit_cpi!(event);
+        }
+        GovernanceAction::WithdrawStrandedReward => {
+            let treasury_account = ctx.accounts.treasury
+                .as_ref()
+                .ok_or(ErrorCode::InvalidTimelockAction)?;
+
+            let event = withdraw_stranded_reward(
+                global_state,
+                reward_schedule,
+                &ctx.accounts.funds_controller,
+                treasury_account
+            )?;
+            emit_cpi!(event);
+        }
+    }
+
+    emit_cpi!(ActionExecuted { id, executor: ctx.accounts.executor.key() });
+
+    Ok(())
+}
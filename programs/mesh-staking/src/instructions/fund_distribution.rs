@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::DistributionFunded;
+use crate::math::SafeMath;
+use crate::state::{ Distribution, GlobalState };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FundDistribution<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to commit the distributed SOLs.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Distribution PDA to be funded.
+    #[account(
+        mut,
+        seeds = [Distribution::SEEDS, distribution.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub distribution: Box<Account<'info, Distribution>>,
+}
+
+pub fn fund_distribution_handler(ctx: Context<FundDistribution>) -> Result<()> {
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
+    }
+
+    let event = fund_distribution(&mut ctx.accounts.global_state, &mut ctx.accounts.distribution)?;
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Commit the SOLs of the distribution from the funded SOLs, so users can claim them from the fundsController.
+/// Shared by the direct instruction and the timelock execution.
+pub fn fund_distribution(
+    _global_state: &mut GlobalState,
+    _distribution: &mut Account<Distribution>
+) -> Result<DistributionFunded> {
+    if _distribution.is_funded || _distribution.mint != Pubkey::default() {
+        return Err(ErrorCode::InvalidDistribution.into());
+    }
+
+    if _distribution.is_expired(_global_state.get_current_time()?) {
+        return Err(ErrorCode::DistributionExpired.into());
+    }
+
+    _global_state.total_distributed_reward = _global_state.total_distributed_reward.safe_add(
+        _distribution.total_amount
+    )?;
+
+    if !_global_state.is_reward_funded() {
+        return Err(ErrorCode::RewardExceedsFunds.into());
+    }
+
+    _distribution.is_funded = true;
+
+    Ok(DistributionFunded {
+        distribution: _distribution.key(),
+        total_amount: _distribution.total_amount,
+        total_distributed_reward: _global_state.total_distributed_reward,
+    })
+}
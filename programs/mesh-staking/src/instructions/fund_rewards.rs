@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{ self, Transfer };
+
+use crate::errors::ErrorCode;
+use crate::events::RewardsFunded;
+use crate::math::SafeMath;
+use crate::state::{ GlobalState, FundsController };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    /// Account that is funding the reward SOLs.
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// Global state PDA to keep track of the funded SOLs.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// PDA that receives the reward SOLs.
+    #[account(
+        mut,
+        seeds = [FundsController::SEEDS],
+        bump,
+    )]
+    pub funds_controller: Account<'info, FundsController>,
+
+    /// The program used to transfer SOLs to the fundsController.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundRewards<'info> {
+    /// Transfer SOLs from funder to fundsController.
+    fn transfer_sol_from_funder_to_controller(&self, _amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), Transfer {
+            from: self.funder.to_account_info(),
+            to: self.funds_controller.to_account_info(),
+        });
+        system_program::transfer(cpi_ctx, _amount)
+    }
+}
+
+pub fn fund_rewards_handler(ctx: Context<FundRewards>, _amount: u64) -> Result<()> {
+    if _amount == 0 {
+        return Err(ErrorCode::ZeroInput.into());
+    }
+
+    ctx.accounts.transfer_sol_from_funder_to_controller(_amount)?;
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_funded = global_state.total_funded.safe_add(_amount)?;
+
+    emit_cpi!(RewardsFunded {
+        funder: ctx.accounts.funder.key(),
+        amount: _amount,
+        total_funded: global_state.total_funded,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{ self, Token, TokenAccount, Transfer };
+
+use crate::errors::ErrorCode;
+use crate::events::StreamRewardsFunded;
+use crate::math::SafeMath;
+use crate::state::{ GlobalState, RewardStream };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FundStreamRewards<'info> {
+    /// Account that is funding the reward tokens.
+    pub funder: Signer<'info>,
+
+    /// Global state PDA to keep track of the funded tokens of the stream.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Vault PDA of the stream that receives the reward tokens.
+    #[account(
+        mut,
+        seeds = [RewardStream::VAULT_SEEDS, reward_vault.mint.as_ref()],
+        bump,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// Funder token account that deposits the reward tokens.
+    #[account(
+        mut,
+        token::mint = reward_vault.mint,
+        token::authority = funder,
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    /// The program used to transfer reward tokens to the vault.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> FundStreamRewards<'info> {
+    /// Transfer reward tokens from funder token account to the stream vault.
+    fn transfer_tokens_from_funder_to_vault(&self, _amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), Transfer {
+            from: self.funder_token_account.to_account_info(),
+            to: self.reward_vault.to_account_info(),
+            authority: self.funder.to_account_info(),
+        });
+        token::transfer(cpi_ctx, _amount)
+    }
+}
+
+pub fn fund_stream_rewards_handler(
+    ctx: Context<FundStreamRewards>,
+    _stream_index: u8,
+    _amount: u64
+) -> Result<()> {
+    if _amount == 0 {
+        return Err(ErrorCode::ZeroInput.into());
+    }
+
+    let mint = ctx.accounts.reward_vault.mint;
+
+    let reward_stream = ctx.accounts.global_state.reward_streams
+        .get_mut(_stream_index as usize)
+        .filter(|reward_stream| reward_stream.is_opened() && reward_stream.mint == mint)
+        .ok_or(ErrorCode::InvalidRewardStream)?;
+    reward_stream.total_funded = reward_stream.total_funded.safe_add(_amount)?;
+
+    let total_funded = reward_stream.total_funded;
+
+    ctx.accounts.transfer_tokens_from_funder_to_vault(_amount)?;
+
+    emit_cpi!(StreamRewardsFunded {
+        funder: ctx.accounts.funder.key(),
+        mint,
+        stream_index: _stream_index,
+        amount: _amount,
+        total_funded,
+    });
+
+    Ok(())
+}
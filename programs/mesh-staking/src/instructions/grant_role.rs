@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::RoleGranted;
+use crate::state::{ GlobalState, Role };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to store the role holder.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn grant_role_handler(ctx: Context<GrantRole>, _role: Role, _account: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let prev_account = global_state.get_role_holder(_role);
+
+    match _role {
+        // The governance only changes through the propose/accept handover.
+        Role::Governance => {
+            return Err(ErrorCode::InvalidRole.into());
+        }
+        Role::RewardManager => {
+            global_state.reward_manager = _account;
+        }
+        Role::Pauser => {
+            global_state.pauser = _account;
+        }
+        Role::Treasurer => {
+            global_state.treasurer = _account;
+        }
+    }
+
+    emit_cpi!(RoleGranted { role: _role, prev_account, account: _account });
+
+    Ok(())
+}
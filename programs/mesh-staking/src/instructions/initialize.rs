@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
-use crate::state::{ GlobalState, FundsController, DEPLOYER };
+use crate::state::{ GlobalState, FundsController, LockTier, RewardSchedule, DEPLOYER, LAYOUT_VERSION };
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -11,7 +11,7 @@ pub struct Initialize<'info> {
 
     /// PDA to store global_state
     #[account(init, seeds = [GlobalState::SEEDS], bump, payer = payer, space = GlobalState::LEN)]
-    pub global_state: Account<'info, GlobalState>,
+    pub global_state: Box<Account<'info, GlobalState>>,
 
     /// PDA to control funds
     #[account(
@@ -23,21 +23,27 @@ pub struct Initialize<'info> {
     )]
     pub funds_controller: Account<'info, FundsController>,
 
-    /// The program used to create global_state & funds_controller PDA.
+    /// PDA to store the future reward cycles
+    #[account(
+        init,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+        payer = payer,
+        space = RewardSchedule::LEN
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+
+    /// The program used to create global_state, funds_controller & reward_schedule PDA.
     pub system_program: Program<'info, System>,
 }
 
-pub fn initialize_handler(
-    ctx: Context<Initialize>,
-    _governance: Pubkey,
-    _mesh_mint: Pubkey,
-    _index_mesh_mint: Pubkey,
-    _weightage: u64
-) -> Result<()> {
+pub fn initialize_handler(ctx: Context<Initialize>, _governance: Pubkey) -> Result<()> {
     let global_state = &mut ctx.accounts.global_state;
     global_state.governance = _governance;
-    global_state.mesh_mint = _mesh_mint;
-    global_state.index_mesh_mint = _index_mesh_mint;
-    global_state.weightage = _weightage;
+    global_state.reward_manager = _governance;
+    global_state.pauser = _governance;
+    global_state.treasurer = _governance;
+    global_state.lock_tiers[0] = LockTier::liquid();
+    global_state.layout_version = LAYOUT_VERSION;
     Ok(())
 }
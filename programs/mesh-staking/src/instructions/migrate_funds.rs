@@ -1,13 +1,17 @@
 use anchor_lang::prelude::*;
 
+use super::base::*;
 use crate::errors::ErrorCode;
+use crate::events::FundsMigrated;
+use crate::math::SafeMath;
 use crate::state::{ GlobalState, FundsController };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct MigrateFunds<'info> {
-    /// Only governance can execute and pay for the instruction.
+    /// Only treasurer can execute and pay for the instruction.
     #[account(mut)]
-    pub governance: Signer<'info>,
+    pub treasurer: Signer<'info>,
 
     /// CHECK: No check required for the new staking version account
     #[account(mut)]
@@ -18,9 +22,9 @@ pub struct MigrateFunds<'info> {
         mut,
         seeds = [GlobalState::SEEDS],
         bump,
-        has_one = governance @ ErrorCode::InvalidGovernance,
+        has_one = treasurer @ ErrorCode::InvalidTreasurer,
       )]
-    pub global_state: Account<'info, GlobalState>,
+    pub global_state: Box<Account<'info, GlobalState>>,
 
     /// Transfer reward SOLs to new version.
     #[account(
@@ -31,34 +35,50 @@ pub struct MigrateFunds<'info> {
     pub funds_controller: Account<'info, FundsController>,
 }
 
-impl<'info> MigrateFunds<'info> {
-    /// Transfer SOLs from fundsController to new version of staking program.
-    fn transfer_sol_from_reward_vault_to_new_version(&self, _amount: u64) -> Result<()> {
-        **self.funds_controller.to_account_info().try_borrow_mut_lamports()? -= _amount;
-        **self.new_version.to_account_info().try_borrow_mut_lamports()? += _amount;
-
-        Ok(())
+pub fn migrate_funds_handler(ctx: Context<MigrateFunds>, _amount: u64) -> Result<()> {
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
     }
+
+    let event = migrate_funds(
+        &mut ctx.accounts.global_state,
+        &ctx.accounts.funds_controller,
+        &ctx.accounts.new_version,
+        _amount
+    )?;
+    emit_cpi!(event);
+
+    Ok(())
 }
 
-pub fn migrate_funds_handler(ctx: Context<MigrateFunds>, _amount: u64) -> Result<()> {
+/// Transfer SOLs not committed to rewards from fundsController to new version of staking program.
+/// Shared by the direct instruction and the timelock execution.
+pub fn migrate_funds(
+    _global_state: &mut GlobalState,
+    _funds_controller: &Account<FundsController>,
+    _new_version: &AccountInfo,
+    _amount: u64
+) -> Result<FundsMigrated> {
     if _amount == 0 {
         return Err(ErrorCode::ZeroInput.into());
     }
 
-    let controller_sol_balance = ctx.accounts.funds_controller
-        .to_account_info()
-        .lamports()
-        .checked_sub(_amount)
-        .unwrap();
-
-    let controller_rent_exempt = Rent::get()?.minimum_balance(FundsController::LEN);
+    // Only the SOLs not committed to rewards can leave, so the committed rewards stay claimable.
+    if _amount > _global_state.total_funded.safe_sub(_global_state.total_distributed_reward)? {
+        return Err(ErrorCode::RewardExceedsFunds.into());
+    }
 
-    if controller_sol_balance <= controller_rent_exempt {
+    if not_have_enough_sol(_funds_controller, _amount)? {
         return Err(ErrorCode::InsufficientFunds.into());
     }
 
-    ctx.accounts.transfer_sol_from_reward_vault_to_new_version(_amount)?;
+    transfer_sol_from_controller(_funds_controller, _new_version, _amount)?;
 
-    Ok(())
+    _global_state.total_funded = _global_state.total_funded.safe_sub(_amount)?;
+
+    Ok(FundsMigrated {
+        new_version: _new_version.key(),
+        amount: _amount,
+        total_funded: _global_state.total_funded,
+    })
 }
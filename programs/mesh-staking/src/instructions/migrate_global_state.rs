@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::Mint;
+
+use crate::errors::ErrorCode;
+use crate::events::GlobalStateMigrated;
+use crate::legacy::LegacyGlobalState;
+use crate::state::{ GlobalState, RewardSchedule, StakePool };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateGlobalState<'info> {
+    /// Only the governance of the legacy layout can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// CHECK: Global state PDA in the legacy layout, checked while it is read and then reallocated.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+    )]
+    pub global_state: UncheckedAccount<'info>,
+
+    /// PDA to store the future reward cycles, which the legacy layout didn't have.
+    #[account(
+        init,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+        payer = governance,
+        space = RewardSchedule::LEN
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+
+    /// Mint address of $MESH, as stored in the legacy global state.
+    pub mesh_mint: InterfaceAccount<'info, Mint>,
+
+    /// Mint address of $indexMESH, as stored in the legacy global state.
+    pub index_mesh_mint: InterfaceAccount<'info, Mint>,
+
+    /// PDA to store the stake pool state of $MESH.
+    #[account(
+        init,
+        seeds = [StakePool::SEEDS, mesh_mint.key().as_ref()],
+        bump,
+        payer = governance,
+        space = StakePool::LEN
+    )]
+    pub mesh_pool: Box<Account<'info, StakePool>>,
+
+    /// PDA to store the stake pool state of $indexMESH.
+    #[account(
+        init,
+        seeds = [StakePool::SEEDS, index_mesh_mint.key().as_ref()],
+        bump,
+        payer = governance,
+        space = StakePool::LEN
+    )]
+    pub index_mesh_pool: Box<Account<'info, StakePool>>,
+
+    /// The program used to create the PDAs and top up the rent of the reallocated global state.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateGlobalState<'info> {
+    /// Transfer the rent of the grown global state from governance.
+    fn transfer_rent_from_governance(&self, _amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), system_program::Transfer {
+            from: self.governance.to_account_info(),
+            to: self.global_state.to_account_info(),
+        });
+        system_program::transfer(cpi_ctx, _amount)
+    }
+}
+
+/// Convert the legacy global state, which held the $MESH and $indexMESH stake, into the current layout and
+/// the stake pools of both tokens. The legacy userInfo PDAs are then migrated one by one.
+pub fn migrate_global_state_handler(ctx: Context<MigrateGlobalState>) -> Result<()> {
+    let global_state_info = ctx.accounts.global_state.to_account_info();
+
+    if global_state_info.owner != ctx.program_id {
+        return Err(ErrorCode::InvalidLegacyAccount.into());
+    }
+
+    let legacy_global_state = LegacyGlobalState::try_from_data(&global_state_info.try_borrow_data()?)?;
+
+    if legacy_global_state.governance != ctx.accounts.governance.key() {
+        return Err(ErrorCode::InvalidGovernance.into());
+    }
+
+    if
+        legacy_global_state.mesh_mint != ctx.accounts.mesh_mint.key() ||
+        legacy_global_state.index_mesh_mint != ctx.accounts.index_mesh_mint.key()
+    {
+        return Err(ErrorCode::InvalidMint.into());
+    }
+
+    let rent = Rent::get()?.minimum_balance(GlobalState::LEN);
+    let lamports = global_state_info.lamports();
+
+    if rent > lamports {
+        ctx.accounts.transfer_rent_from_governance(rent - lamports)?;
+    }
+
+    global_state_info.realloc(GlobalState::LEN, false)?;
+
+    // The grown account is zeroed, so every field missing from the legacy layout starts from its default.
+    let mut global_state = {
+        let mut data = global_state_info.try_borrow_mut_data()?;
+        data.fill(0);
+        Box::new(GlobalState::try_deserialize_unchecked(&mut &data[..])?)
+    };
+
+    let legacy_pools = [ctx.accounts.mesh_pool.key(), ctx.accounts.index_mesh_pool.key()];
+
+    legacy_global_state.migrate(
+        &mut global_state,
+        &mut ctx.accounts.mesh_pool,
+        &mut ctx.accounts.index_mesh_pool,
+        legacy_pools
+    )?;
+
+    global_state.try_serialize(&mut &mut global_state_info.try_borrow_mut_data()?[..])?;
+
+    emit_cpi!(GlobalStateMigrated {
+        mesh_pool: legacy_pools[0],
+        index_mesh_pool: legacy_pools[1],
+        total_weighted_stake: global_state.total_weighted_stake,
+        layout_version: global_state.layout_version,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use super::base::*;
+use crate::errors::ErrorCode;
+use crate::events::UserInfoMigrated;
+use crate::legacy::{ merge_legacy_position, LegacyUserInfo };
+use crate::state::UserInfo;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateUserInfo<'info> {
+    pub base: Base<'info>,
+
+    /// CHECK: UserInfo PDA of the user in the legacy layout, checked while it is read.
+    #[account(
+        mut,
+        seeds = [base.user.key().as_ref(), UserInfo::SEEDS],
+        bump,
+    )]
+    pub legacy_user_info: UncheckedAccount<'info>,
+}
+
+/// Move the legacy stake of the user in the given stake pool into their userInfo of the pool. The pending
+/// SOL rewards of both positions are kept as owed, and the legacy PDA is closed once both of its stakes
+/// are migrated.
+pub fn migrate_user_info_handler(ctx: Context<MigrateUserInfo>) -> Result<()> {
+    // The reward math is not trusted in safe mode.
+    if ctx.accounts.base.global_state.safe_mode {
+        return Err(ErrorCode::SafeModeActive.into());
+    }
+
+    let legacy_user_info_account = ctx.accounts.legacy_user_info.to_account_info();
+
+    if legacy_user_info_account.owner != ctx.program_id {
+        return Err(ErrorCode::InvalidLegacyAccount.into());
+    }
+
+    let mut legacy_user_info = LegacyUserInfo::try_from_data(
+        &legacy_user_info_account.try_borrow_data()?
+    )?;
+
+    let base = &mut ctx.accounts.base;
+
+    let index = base.global_state.legacy_pools
+        .iter()
+        .position(|legacy_pool| *legacy_pool == base.stake_pool.key())
+        .ok_or(ErrorCode::InvalidLegacyAccount)?;
+
+    let (amount, acc_reward) = legacy_user_info.take_position(index);
+
+    if amount == 0 {
+        return Err(ErrorCode::ZeroInput.into());
+    }
+
+    base.user_info.init(base.user.key(), base.stake_pool.key());
+
+    base.update_reward_per_share()?;
+    // No SOL leaves the fundsController on migration, the rewards of the current position are owed as well.
+    base.harvest_user_rewards(true)?;
+
+    merge_legacy_position(amount, acc_reward, &mut base.stake_pool, &mut base.user_info)?;
+
+    base.update_weighted_stake()?;
+
+    if legacy_user_info.is_empty() {
+        let lamports = legacy_user_info_account.lamports();
+        **legacy_user_info_account.try_borrow_mut_lamports()? -= lamports;
+        **base.user.to_account_info().try_borrow_mut_lamports()? += lamports;
+    } else {
+        legacy_user_info.try_write_data(&mut legacy_user_info_account.try_borrow_mut_data()?)?;
+    }
+
+    emit_cpi!(UserInfoMigrated {
+        user: base.user.key(),
+        mint: base.stake_pool.mint,
+        amount,
+        user_staked: base.user_info.staked,
+        unclaimed_owed: base.user_info.unclaimed_owed,
+    });
+
+    Ok(())
+}
@@ -1,25 +1,111 @@
+pub mod accept_governance;
+pub mod add_reward_stream;
+pub mod add_stake_pool;
+pub mod balance_at;
 pub mod base;
+pub mod cancel_action;
+pub mod cancel_governance_proposal;
+pub mod cancel_reward_cycle;
+pub mod cancel_withdrawal;
 pub mod claim;
+pub mod claim_distribution;
+pub mod close_reward_campaign;
 pub mod close_user_info;
+pub mod collect_unstake_fees;
+pub mod create_distribution;
+pub mod edit_reward_cycle;
+pub mod emergency_withdraw;
+pub mod execute_action;
+pub mod fund_distribution;
+pub mod fund_rewards;
+pub mod fund_stream_rewards;
+pub mod grant_role;
 pub mod initialize;
 pub mod migrate_funds;
+pub mod migrate_global_state;
+pub mod migrate_user_info;
+pub mod open_reward_campaign;
+pub mod open_snapshot_epoch;
 pub mod pending_rewards;
+pub mod pending_withdrawals;
+pub mod propose_governance;
+pub mod queue_action;
+pub mod release_lock;
+pub mod revoke_role;
+pub mod schedule_reward_cycle;
+pub mod sponsor_reward_campaign;
 pub mod stake;
+pub mod sweep_distribution;
 pub mod unstake;
-pub mod update_governance;
+pub mod unstake_fee_schedule;
+pub mod update_campaign_config;
+pub mod update_lock_tiers;
+pub mod update_max_voter_weight_record;
+pub mod update_pause_flags;
 pub mod update_period_end;
 pub mod update_rewards;
+pub mod update_safe_mode;
+pub mod update_stream_rewards;
+pub mod update_timelock_delay;
+pub mod update_unbonding_cooldown;
+pub mod update_unstake_fee;
+pub mod update_voter_weight_record;
 pub mod update_weightage;
+pub mod withdraw;
+pub mod withdraw_stranded_reward;
 
+pub use accept_governance::*;
+pub use add_reward_stream::*;
+pub use add_stake_pool::*;
+pub use balance_at::*;
 pub use base::*;
+pub use cancel_action::*;
+pub use cancel_governance_proposal::*;
+pub use cancel_reward_cycle::*;
+pub use cancel_withdrawal::*;
 pub use claim::*;
+pub use claim_distribution::*;
+pub use close_reward_campaign::*;
 pub use close_user_info::*;
+pub use collect_unstake_fees::*;
+pub use create_distribution::*;
+pub use edit_reward_cycle::*;
+pub use emergency_withdraw::*;
+pub use execute_action::*;
+pub use fund_distribution::*;
+pub use fund_rewards::*;
+pub use fund_stream_rewards::*;
+pub use grant_role::*;
 pub use initialize::*;
 pub use migrate_funds::*;
+pub use migrate_global_state::*;
+pub use migrate_user_info::*;
+pub use open_reward_campaign::*;
+pub use open_snapshot_epoch::*;
 pub use pending_rewards::*;
+pub use pending_withdrawals::*;
+pub use propose_governance::*;
+pub use queue_action::*;
+pub use release_lock::*;
+pub use revoke_role::*;
+pub use schedule_reward_cycle::*;
+pub use sponsor_reward_campaign::*;
 pub use stake::*;
+pub use sweep_distribution::*;
 pub use unstake::*;
-pub use update_governance::*;
+pub use unstake_fee_schedule::*;
+pub use update_campaign_config::*;
+pub use update_lock_tiers::*;
+pub use update_max_voter_weight_record::*;
+pub use update_pause_flags::*;
 pub use update_period_end::*;
 pub use update_rewards::*;
+pub use update_safe_mode::*;
+pub use update_stream_rewards::*;
+pub use update_timelock_delay::*;
+pub use update_unbonding_cooldown::*;
+pub use update_unstake_fee::*;
+pub use update_voter_weight_record::*;
 pub use update_weightage::*;
+pub use withdraw::*;
+pub use withdraw_stranded_reward::*;
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::RewardCampaignOpened;
+use crate::math::{ SafeCast, SafeMath };
+use crate::state::{
+    CampaignState,
+    GlobalState,
+    RewardCampaign,
+    RewardSchedule,
+    MAX_CAMPAIGN_START_DELAY,
+    PAUSE_REWARD_UPDATES,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenRewardCampaign<'info> {
+    /// Only reward manager can execute and pay for the instruction.
+    #[account(mut)]
+    pub reward_manager: Signer<'info>,
+
+    /// Global state PDA to store the campaign slot.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = reward_manager @ ErrorCode::InvalidRewardManager,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to start the scheduled reward cycles that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+
+    /// Reward campaign PDA to store the campaign.
+    #[account(
+        init,
+        payer = reward_manager,
+        space = RewardCampaign::LEN,
+        seeds = [RewardCampaign::SEEDS, global_state.next_campaign_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub reward_campaign: Account<'info, RewardCampaign>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn open_reward_campaign_handler(
+    ctx: Context<OpenRewardCampaign>,
+    _reward: u64,
+    _start_time: u64,
+    _end_time: u64
+) -> Result<()> {
+    let event = open_campaign(
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.reward_schedule,
+        &mut ctx.accounts.reward_campaign,
+        ctx.accounts.reward_manager.key(),
+        CampaignState {
+            reward: _reward,
+            start_time: _start_time,
+            end_time: _end_time,
+            ..CampaignState::default()
+        }
+    )?;
+
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Take a free campaign slot for the given campaign and commit its rewards, shared by the reward manager
+/// and sponsors. SOL rewards are committed against the funded SOLs, tokens against the funded tokens of their
+/// reward stream.
+pub fn open_campaign(
+    _global_state: &mut GlobalState,
+    _reward_schedule: &mut RewardSchedule,
+    _reward_campaign: &mut Account<RewardCampaign>,
+    _creator: Pubkey,
+    _campaign: CampaignState
+) -> Result<RewardCampaignOpened> {
+    if _global_state.is_paused(PAUSE_REWARD_UPDATES) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
+    if _campaign.reward == 0 {
+        return Err(ErrorCode::ZeroInput.into());
+    }
+
+    let current_time = _global_state.get_current_time()?;
+
+    if
+        _campaign.start_time < current_time ||
+        _campaign.start_time > current_time.safe_add(MAX_CAMPAIGN_START_DELAY)? ||
+        _campaign.end_time <= _campaign.start_time
+    {
+        return Err(ErrorCode::InvalidCampaignWindow.into());
+    }
+
+    let slot = _global_state.reward_campaigns
+        .iter()
+        .position(|reward_campaign| !reward_campaign.is_opened())
+        .ok_or(ErrorCode::RewardCampaignsFull)?;
+
+    _global_state.update_reward_per_share(_reward_schedule)?;
+
+    _global_state.reward_campaigns[slot] = CampaignState {
+        campaign: _reward_campaign.key(),
+        last_updated_time: current_time,
+        global_acc_reward: 0,
+        stranded_reward: 0,
+        reward_dust: 0,
+        .._campaign
+    };
+
+    if _campaign.is_sol() {
+        _global_state.total_distributed_reward = _global_state.total_distributed_reward.safe_add(
+            _campaign.reward
+        )?;
+
+        if !_global_state.is_reward_funded() {
+            return Err(ErrorCode::RewardExceedsFunds.into());
+        }
+    } else {
+        let reward_stream = _global_state.reward_streams
+            .iter_mut()
+            .find(|reward_stream| {
+                reward_stream.is_opened() && reward_stream.mint == _campaign.reward_mint
+            })
+            .ok_or(ErrorCode::InvalidCampaignMint)?;
+        reward_stream.total_distributed_reward = reward_stream.total_distributed_reward.safe_add(
+            _campaign.reward
+        )?;
+
+        if !reward_stream.is_reward_funded() {
+            return Err(ErrorCode::RewardExceedsFunds.into());
+        }
+    }
+
+    let id = _global_state.next_campaign_id;
+    _global_state.next_campaign_id = id.safe_add(1)?;
+
+    _reward_campaign.id = id;
+    _reward_campaign.creator = _creator;
+    _reward_campaign.slot = slot.safe_cast()?;
+    _reward_campaign.reward_mint = _campaign.reward_mint;
+    _reward_campaign.reward = _campaign.reward;
+    _reward_campaign.start_time = _campaign.start_time;
+    _reward_campaign.end_time = _campaign.end_time;
+
+    Ok(RewardCampaignOpened {
+        campaign: _reward_campaign.key(),
+        id,
+        slot: _reward_campaign.slot,
+        creator: _creator,
+        reward_mint: _campaign.reward_mint,
+        sponsored: _campaign.sponsored,
+        reward: _campaign.reward,
+        start_time: _campaign.start_time,
+        end_time: _campaign.end_time,
+        total_distributed_reward: _global_state.total_distributed_reward,
+    })
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::SnapshotEpochOpened;
+use crate::math::SafeMath;
+use crate::state::GlobalState;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenSnapshotEpoch<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to store the snapshot epoch.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn open_snapshot_epoch_handler(ctx: Context<OpenSnapshotEpoch>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+
+    // The checkpoints of the previous epoch are dropped by each user on their next balance change.
+    global_state.snapshot_epoch = global_state.snapshot_epoch.safe_add(1)?;
+    global_state.snapshot_start_time = global_state.get_current_time()?;
+
+    emit_cpi!(SnapshotEpochOpened {
+        snapshot_epoch: global_state.snapshot_epoch,
+        start_time: global_state.snapshot_start_time,
+    });
+
+    Ok(())
+}
@@ -1,58 +1,107 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{ GlobalState, UserInfo, SCALE_FACTOR };
+use crate::errors::ErrorCode;
+use crate::math::{ SafeCast, SafeMath };
+use crate::state::{ GlobalState, RewardSchedule, StakePool, UserInfo, MAX_REWARD_STREAMS };
 
 #[derive(Accounts)]
 #[instruction(_user:Pubkey)]
 pub struct PendingRewards<'info> {
     /// Global state PDA to read globally accumulated reward.
     #[account(seeds = [GlobalState::SEEDS], bump)]
-    pub global_state: Account<'info, GlobalState>,
+    pub global_state: Box<Account<'info, GlobalState>>,
 
-    /// User info PDA to read user accumulated reward.
-    #[account(seeds = [_user.key().as_ref(), b"user_info"], bump)]
-    pub user_info: Account<'info, UserInfo>,
+    /// Reward schedule PDA to read the scheduled reward cycles that are due.
+    #[account(seeds = [RewardSchedule::SEEDS], bump)]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PoolRewardStruct {
+    pub mint: Pubkey,
+    pub reward: u64,
+    /// Rewards owed by an earlier emergency unstake, not included in `reward`.
+    pub unclaimed_owed: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StreamRewardStruct {
+    pub mint: Pubkey,
+    pub reward: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RewardStruct {
-    pub mesh_reward: u64,
-    pub index_mesh_reward: u64,
+    pub total_reward: u64,
+    pub total_unclaimed_owed: u64,
+    pub pool_rewards: Vec<PoolRewardStruct>,
+    pub stream_rewards: Vec<StreamRewardStruct>,
 }
 
-pub fn pending_rewards_handler(
-    ctx: Context<PendingRewards>,
+/// Pairs of stake pool and userInfo PDAs are passed as remaining accounts, one pair for each pool
+/// the rewards are calculated for.
+pub fn pending_rewards_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PendingRewards<'info>>,
     _user: Pubkey
 ) -> Result<RewardStruct> {
-    let user_info = &ctx.accounts.user_info;
-    let global_state = &ctx.accounts.global_state;
+    let pool_accounts = ctx.remaining_accounts.chunks_exact(2);
 
-    if user_info.staked_mesh == 0 && user_info.staked_index_mesh == 0 {
-        return Ok(RewardStruct { mesh_reward: 0, index_mesh_reward: 0 });
+    if !pool_accounts.remainder().is_empty() {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
     }
 
-    let current_time: u64 = global_state.get_current_time()?;
-    let [global_acc_reward_mesh, global_acc_reward_index_mesh] =
-        global_state.calculate_reward_per_share(current_time)?;
-
-    let mesh_reward = global_acc_reward_mesh
-        .checked_sub(user_info.acc_reward_mesh)
-        .unwrap()
-        .checked_mul(GlobalState::to_u128(user_info.staked_mesh))
-        .unwrap()
-        .checked_div(SCALE_FACTOR)
-        .unwrap();
-
-    let index_mesh_reward = global_acc_reward_index_mesh
-        .checked_sub(user_info.acc_reward_index_mesh)
-        .unwrap()
-        .checked_mul(GlobalState::to_u128(user_info.staked_index_mesh))
-        .unwrap()
-        .checked_div(SCALE_FACTOR)
-        .unwrap();
-
-    Ok(RewardStruct {
-        mesh_reward: mesh_reward.try_into().unwrap(),
-        index_mesh_reward: index_mesh_reward.try_into().unwrap(),
-    })
+    // The states are updated in memory only, the accounts are never written back.
+    let mut global_state: GlobalState = (**ctx.accounts.global_state).clone();
+    let mut reward_schedule: RewardSchedule = (**ctx.accounts.reward_schedule).clone();
+    global_state.update_reward_per_share(&mut reward_schedule)?;
+
+    let mut total_reward: u64 = 0;
+    let mut total_unclaimed_owed: u64 = 0;
+    let mut pool_rewards = Vec::with_capacity(ctx.remaining_accounts.len() / 2);
+    let mut stream_totals = [0u64; MAX_REWARD_STREAMS];
+    let mut user_info_keys: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len() / 2);
+
+    for accounts in pool_accounts {
+        let mut stake_pool: StakePool = Account::<StakePool>::try_from(&accounts[0])?.into_inner();
+        let mut user_info: UserInfo = Account::<UserInfo>::try_from(&accounts[1])?.into_inner();
+
+        // A repeated pair would count its rewards twice, the same as in claim.
+        if
+            user_info.user != _user ||
+            user_info.pool != accounts[0].key() ||
+            user_info_keys.contains(accounts[1].key)
+        {
+            return Err(ErrorCode::InvalidRemainingAccounts.into());
+        }
+
+        user_info_keys.push(accounts[1].key());
+
+        stake_pool.update_reward_per_share(&global_state)?;
+        user_info.settle_stream_rewards(&stake_pool)?;
+
+        let reward: u64 = user_info
+            .calculate_pending_rewards(stake_pool.acc_reward)?
+            .safe_cast()?;
+
+        total_reward = total_reward.safe_add(reward)?;
+        total_unclaimed_owed = total_unclaimed_owed.safe_add(user_info.unclaimed_owed)?;
+        pool_rewards.push(PoolRewardStruct {
+            mint: stake_pool.mint,
+            reward,
+            unclaimed_owed: user_info.unclaimed_owed,
+        });
+
+        for (stream_total, reward) in stream_totals.iter_mut().zip(user_info.stream_pending_rewards) {
+            *stream_total = stream_total.safe_add(reward)?;
+        }
+    }
+
+    let stream_rewards = global_state.reward_streams
+        .iter()
+        .zip(stream_totals)
+        .filter(|(reward_stream, _)| reward_stream.is_opened())
+        .map(|(reward_stream, reward)| StreamRewardStruct { mint: reward_stream.mint, reward })
+        .collect();
+
+    Ok(RewardStruct { total_reward, total_unclaimed_owed, pool_rewards, stream_rewards })
 }
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{ GlobalState, WithdrawalTicket };
+
+#[derive(Accounts)]
+#[instruction(_user:Pubkey)]
+pub struct PendingWithdrawals<'info> {
+    /// Global state PDA to read the current time.
+    #[account(seeds = [GlobalState::SEEDS], bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct WithdrawalStruct {
+    pub id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub unlock_time: u64,
+    pub is_withdrawable: bool,
+}
+
+/// Withdrawal tickets of the user are passed as remaining accounts.
+pub fn pending_withdrawals_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PendingWithdrawals<'info>>,
+    _user: Pubkey
+) -> Result<Vec<WithdrawalStruct>> {
+    let current_time: u64 = ctx.accounts.global_state.get_current_time()?;
+
+    ctx.remaining_accounts
+        .iter()
+        .map(|account_info| {
+            let withdrawal_ticket = Account::<WithdrawalTicket>::try_from(account_info)?;
+
+            if withdrawal_ticket.user != _user {
+                return Err(ErrorCode::InvalidWithdrawalTicket.into());
+            }
+
+            Ok(WithdrawalStruct {
+                id: withdrawal_ticket.id,
+                mint: withdrawal_ticket.mint,
+                amount: withdrawal_ticket.amount,
+                unlock_time: withdrawal_ticket.unlock_time,
+                is_withdrawable: current_time >= withdrawal_ticket.unlock_time,
+            })
+        })
+        .collect()
+}
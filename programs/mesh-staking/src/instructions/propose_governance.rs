@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::GovernanceProposed;
+use crate::state::GlobalState;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeGovernance<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to store the pending governance.
+    #[account(
+      mut,
+      seeds = [GlobalState::SEEDS],
+      bump,
+      has_one = governance @ ErrorCode::InvalidGovernance,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn propose_governance_handler(
+    ctx: Context<ProposeGovernance>,
+    _new_governance: Pubkey
+) -> Result<()> {
+    // The governance only changes once the new key accepts it, a previous proposal is replaced.
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.pending_governance = _new_governance;
+
+    emit_cpi!(GovernanceProposed {
+        governance: global_state.governance,
+        pending_governance: _new_governance,
+    });
+
+    Ok(())
+}
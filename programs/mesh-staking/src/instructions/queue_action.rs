@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use super::update_lock_tiers::validate_lock_tiers;
+use crate::errors::ErrorCode;
+use crate::events::ActionQueued;
+use crate::math::SafeMath;
+use crate::state::{
+    GlobalState,
+    GovernanceAction,
+    TimelockAction,
+    MAX_UNBONDING_COOLDOWN,
+    MAX_UNSTAKE_FEE_BPS,
+    MIN_TIMELOCK_DELAY,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct QueueAction<'info> {
+    /// Only the holder of the role of the action can execute and pay for the instruction.
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    /// Global state PDA to assign the id of the queued action.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Timelock action PDA to store the queued parameter change.
+    #[account(
+        init,
+        payer = proposer,
+        space = TimelockAction::LEN,
+        seeds = [TimelockAction::SEEDS, global_state.next_action_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub timelock_action: Account<'info, TimelockAction>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn queue_action_handler(ctx: Context<QueueAction>, _action: GovernanceAction) -> Result<()> {
+    if ctx.accounts.global_state.get_role_holder(_action.get_role()) != ctx.accounts.proposer.key() {
+        return Err(ErrorCode::InvalidProposer.into());
+    }
+
+    if let GovernanceAction::UpdateTimelockDelay { timelock_delay } = _action {
+        if timelock_delay < MIN_TIMELOCK_DELAY {
+            return Err(ErrorCode::InvalidTimelockDelay.into());
+        }
+    }
+
+    if let GovernanceAction::UpdateUnbondingCooldown { unbonding_cooldown } = _action {
+        if unbonding_cooldown > MAX_UNBONDING_COOLDOWN {
+            return Err(ErrorCode::InvalidUnbondingCooldown.into());
+        }
+    }
+
+    if let GovernanceAction::UpdateUnstakeFee { unstake_fee } = _action {
+        if unstake_fee.fee_bps > MAX_UNSTAKE_FEE_BPS {
+            return Err(ErrorCode::InvalidUnstakeFee.into());
+        }
+    }
+
+    if let GovernanceAction::UpdateLockTiers { lock_tiers } = _action {
+        validate_lock_tiers(&lock_tiers)?;
+    }
+
+    let global_state = &mut ctx.accounts.global_state;
+    let eta = global_state
+        .get_current_time()?
+        .safe_add(global_state.timelock_delay)?;
+
+    let timelock_action = &mut ctx.accounts.timelock_action;
+    timelock_action.id = global_state.next_action_id;
+    timelock_action.proposer = ctx.accounts.proposer.key();
+    timelock_action.action = _action;
+    timelock_action.eta = eta;
+
+    global_state.next_action_id = global_state.next_action_id.safe_add(1)?;
+
+    emit_cpi!(ActionQueued {
+        id: ctx.accounts.timelock_action.id,
+        action: _action,
+        eta,
+    });
+
+    Ok(())
+}
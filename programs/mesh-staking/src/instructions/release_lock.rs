@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use super::base::*;
+use crate::errors::ErrorCode;
+use crate::events::LockReleased;
+use crate::math::SafeMath;
+use crate::state::{ GlobalState, LockTier, RewardSchedule, StakePool, UserInfo };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReleaseLock<'info> {
+    /// Anyone can release an expired lock, as the boosted stake is no longer earned.
+    pub caller: Signer<'info>,
+
+    /// UserInfo PDA whose expired lock is released.
+    #[account(
+        mut,
+        seeds = [user_info.user.as_ref(), stake_pool.key().as_ref(), UserInfo::SEEDS],
+        bump,
+    )]
+    pub user_info: Account<'info, UserInfo>,
+
+    /// Update the pool state of reward accumulation and weighted stake.
+    #[account(
+        mut,
+        seeds = [StakePool::SEEDS, stake_pool.mint.as_ref()],
+        bump,
+    )]
+    pub stake_pool: Account<'info, StakePool>,
+
+    /// Update the global state of reward accumulation.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to start the scheduled reward cycles that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+}
+
+/// Settle the rewards of the user up to now and reset the lock multiplier back to liquid. The SOL rewards
+/// are owed to the user instead of being paid, so they are paid on the next claim.
+pub fn release_lock_handler(ctx: Context<ReleaseLock>) -> Result<()> {
+    // The reward math is not trusted in safe mode.
+    if ctx.accounts.global_state.safe_mode {
+        return Err(ErrorCode::SafeModeActive.into());
+    }
+
+    let current_time = ctx.accounts.global_state.get_current_time()?;
+    let user_info = &ctx.accounts.user_info;
+
+    if user_info.is_locked(current_time) || user_info.lock_multiplier == LockTier::liquid().multiplier {
+        return Err(ErrorCode::NoExpiredLock.into());
+    }
+
+    let global_state = &mut ctx.accounts.global_state;
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let user_info = &mut ctx.accounts.user_info;
+
+    global_state.update_reward_per_share(&mut ctx.accounts.reward_schedule)?;
+    stake_pool.update_reward_per_share(global_state)?;
+    user_info.sync_snapshot_epoch(global_state);
+
+    let user_rewards = user_info.settle_rewards(stake_pool.acc_reward)?;
+    user_info.unclaimed_owed = user_info.unclaimed_owed.safe_add(user_rewards)?;
+    user_info.settle_stream_rewards(stake_pool)?;
+    user_info.settle_fee_rewards(stake_pool)?;
+
+    release_expired_lock(user_info, current_time)?;
+    update_weighted_stake(global_state, stake_pool, user_info)?;
+
+    emit_cpi!(LockReleased {
+        user: user_info.user,
+        mint: stake_pool.mint,
+        unclaimed_owed: user_info.unclaimed_owed,
+        user_weighted: user_info.weighted,
+        pool_total_weighted: stake_pool.total_weighted,
+        total_weighted_stake: global_state.total_weighted_stake,
+        global_acc_reward: global_state.global_acc_reward,
+        pool_acc_reward: stake_pool.acc_reward,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::RoleRevoked;
+use crate::state::{ GlobalState, Role };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to clear the role holder.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn revoke_role_handler(ctx: Context<RevokeRole>, _role: Role) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let account = global_state.get_role_holder(_role);
+
+    // A revoked role is held by the default pubkey, which can never sign.
+    match _role {
+        Role::Governance => {
+            return Err(ErrorCode::InvalidRole.into());
+        }
+        Role::RewardManager => {
+            global_state.reward_manager = Pubkey::default();
+        }
+        Role::Pauser => {
+            global_state.pauser = Pubkey::default();
+        }
+        Role::Treasurer => {
+            global_state.treasurer = Pubkey::default();
+        }
+    }
+
+    emit_cpi!(RoleRevoked { role: _role, account });
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::RewardCycleScheduled;
+use crate::math::SafeMath;
+use crate::state::{ GlobalState, RewardSchedule, ScheduledCycle, PAUSE_REWARD_UPDATES };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ScheduleRewardCycle<'info> {
+    /// Only reward manager can execute and pay for the instruction.
+    #[account(mut)]
+    pub reward_manager: Signer<'info>,
+
+    /// Global state PDA to commit the scheduled rewards.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = reward_manager @ ErrorCode::InvalidRewardManager,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to store the queued reward cycles.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+}
+
+pub fn schedule_reward_cycle_handler(
+    ctx: Context<ScheduleRewardCycle>,
+    _reward: u64,
+    _distribution_time: u64,
+    _start_time: u64
+) -> Result<()> {
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
+    }
+
+    let event = schedule_reward_cycle(
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.reward_schedule,
+        _reward,
+        _distribution_time,
+        _start_time
+    )?;
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Queue a future reward cycle, committing its rewards right away.
+/// Shared by the direct instruction and the timelock execution.
+pub fn schedule_reward_cycle(
+    _global_state: &mut GlobalState,
+    _reward_schedule: &mut RewardSchedule,
+    _reward: u64,
+    _distribution_time: u64,
+    _start_time: u64
+) -> Result<RewardCycleScheduled> {
+    if _global_state.is_paused(PAUSE_REWARD_UPDATES) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
+    if _reward == 0 || _distribution_time == 0 {
+        return Err(ErrorCode::ZeroInput.into());
+    }
+
+    // Start the cycles that are already due, so only future cycles are validated.
+    _global_state.update_reward_per_share(_reward_schedule)?;
+
+    let current_time = _global_state.get_current_time()?;
+
+    let mut scheduled_cycles = _reward_schedule.get_scheduled_cycles();
+
+    // Cycles are identified by their start time when edited or cancelled.
+    if scheduled_cycles.iter().any(|scheduled_cycle| scheduled_cycle.start_time == _start_time) {
+        return Err(ErrorCode::DuplicateScheduledCycle.into());
+    }
+
+    scheduled_cycles.push(ScheduledCycle {
+        reward: _reward,
+        distribution_time: _distribution_time,
+        start_time: _start_time,
+    });
+    _reward_schedule.set_scheduled_cycles(scheduled_cycles, _global_state.period_end_time, current_time)?;
+
+    _global_state.total_distributed_reward = _global_state.total_distributed_reward
+        .safe_add(_reward)?;
+
+    if !_global_state.is_reward_funded() {
+        return Err(ErrorCode::RewardExceedsFunds.into());
+    }
+
+    Ok(RewardCycleScheduled {
+        reward: _reward,
+        distribution_time: _distribution_time,
+        start_time: _start_time,
+        total_distributed_reward: _global_state.total_distributed_reward,
+    })
+}
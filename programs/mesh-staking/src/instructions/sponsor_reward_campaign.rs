@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{ self, Token, TokenAccount, Transfer };
+
+use super::open_reward_campaign::open_campaign;
+use crate::errors::ErrorCode;
+use crate::math::SafeMath;
+use crate::state::{
+    CampaignState,
+    GlobalState,
+    FundsController,
+    RewardCampaign,
+    RewardSchedule,
+    RewardStream,
+    MAX_SPONSORED_CAMPAIGNS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SponsorRewardCampaign<'info> {
+    /// Anyone can sponsor a campaign by depositing its rewards and paying for the instruction.
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// Global state PDA to store the campaign slot.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to start the scheduled reward cycles that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+
+    /// Reward campaign PDA to store the campaign.
+    #[account(
+        init,
+        payer = sponsor,
+        space = RewardCampaign::LEN,
+        seeds = [RewardCampaign::SEEDS, global_state.next_campaign_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub reward_campaign: Account<'info, RewardCampaign>,
+
+    /// PDA that receives the reward SOLs.
+    #[account(
+        mut,
+        seeds = [FundsController::SEEDS],
+        bump,
+    )]
+    pub funds_controller: Account<'info, FundsController>,
+
+    /// Reward stream vault PDA that receives the reward tokens. Omitted for SOL campaigns.
+    #[account(
+        mut,
+        seeds = [RewardStream::VAULT_SEEDS, reward_vault.mint.as_ref()],
+        bump,
+        token::authority = funds_controller,
+    )]
+    pub reward_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Sponsor token account that deposits the reward tokens. Omitted for SOL campaigns.
+    #[account(
+        mut,
+        token::authority = sponsor,
+    )]
+    pub sponsor_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The program used to transfer reward tokens to the vault. Omitted for SOL campaigns.
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// The program used to transfer SOLs to the fundsController and create the campaign PDA.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SponsorRewardCampaign<'info> {
+    /// Transfer SOLs from sponsor to fundsController.
+    fn transfer_sol_from_sponsor_to_controller(&self, _amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.system_program.to_account_info(),
+            system_program::Transfer {
+                from: self.sponsor.to_account_info(),
+                to: self.funds_controller.to_account_info(),
+            }
+        );
+        system_program::transfer(cpi_ctx, _amount)
+    }
+
+    /// Transfer reward tokens from sponsor token account to the reward stream vault.
+    fn transfer_tokens_from_sponsor_to_vault(
+        &self,
+        _sponsor_token_account: &Account<'info, TokenAccount>,
+        _reward_vault: &Account<'info, TokenAccount>,
+        _token_program: &Program<'info, Token>,
+        _amount: u64
+    ) -> Result<()> {
+        let cpi_ctx = CpiContext::new(_token_program.to_account_info(), Transfer {
+            from: _sponsor_token_account.to_account_info(),
+            to: _reward_vault.to_account_info(),
+            authority: self.sponsor.to_account_info(),
+        });
+        token::transfer(cpi_ctx, _amount)
+    }
+}
+
+/// SOL campaigns are sponsored without the token accounts, token campaigns pass the reward stream
+/// vault, the sponsor token account and the token program.
+pub fn sponsor_reward_campaign_handler(
+    ctx: Context<SponsorRewardCampaign>,
+    _reward: u64,
+    _start_time: u64,
+    _end_time: u64
+) -> Result<()> {
+    let campaign_config = ctx.accounts.global_state.campaign_config;
+
+    if _end_time <= _start_time {
+        return Err(ErrorCode::InvalidCampaignWindow.into());
+    }
+
+    let duration = _end_time.safe_sub(_start_time)?;
+
+    if duration < campaign_config.min_duration || duration > campaign_config.max_duration {
+        return Err(ErrorCode::InvalidCampaignWindow.into());
+    }
+
+    let sponsored_campaigns = ctx.accounts.global_state.reward_campaigns
+        .iter()
+        .filter(|reward_campaign| reward_campaign.is_opened() && reward_campaign.sponsored)
+        .count();
+
+    if sponsored_campaigns >= MAX_SPONSORED_CAMPAIGNS {
+        return Err(ErrorCode::SponsoredCampaignsFull.into());
+    }
+
+    let accounts = &ctx.accounts;
+
+    let reward_mint = match
+        (&accounts.reward_vault, &accounts.sponsor_token_account, &accounts.token_program)
+    {
+        (None, None, None) => {
+            if campaign_config.min_sol_reward == 0 {
+                return Err(ErrorCode::InvalidCampaignMint.into());
+            }
+
+            if _reward < campaign_config.min_sol_reward {
+                return Err(ErrorCode::CampaignRewardTooLow.into());
+            }
+
+            accounts.transfer_sol_from_sponsor_to_controller(_reward)?;
+
+            Pubkey::default()
+        }
+        (Some(reward_vault), Some(sponsor_token_account), Some(token_program)) => {
+            let campaign_mint = campaign_config
+                .get_mint(reward_vault.mint)
+                .ok_or(ErrorCode::InvalidCampaignMint)?;
+
+            if sponsor_token_account.mint != reward_vault.mint {
+                return Err(ErrorCode::InvalidMint.into());
+            }
+
+            if _reward < campaign_mint.min_reward {
+                return Err(ErrorCode::CampaignRewardTooLow.into());
+            }
+
+            accounts.transfer_tokens_from_sponsor_to_vault(
+                sponsor_token_account,
+                reward_vault,
+                token_program,
+                _reward
+            )?;
+
+            campaign_mint.mint
+        }
+        _ => {
+            return Err(ErrorCode::InvalidCampaignMint.into());
+        }
+    };
+
+    let global_state = &mut ctx.accounts.global_state;
+
+    // Deposited SOLs or tokens fund exactly the rewards they commit.
+    if reward_mint == Pubkey::default() {
+        global_state.total_funded = global_state.total_funded.safe_add(_reward)?;
+    } else {
+        let reward_stream = global_state.reward_streams
+            .iter_mut()
+            .find(|reward_stream| reward_stream.is_opened() && reward_stream.mint == reward_mint)
+            .ok_or(ErrorCode::InvalidCampaignMint)?;
+        reward_stream.total_funded = reward_stream.total_funded.safe_add(_reward)?;
+    }
+
+    let event = open_campaign(
+        global_state,
+        &mut ctx.accounts.reward_schedule,
+        &mut ctx.accounts.reward_campaign,
+        ctx.accounts.sponsor.key(),
+        CampaignState {
+            reward_mint,
+            sponsored: true,
+            reward: _reward,
+            start_time: _start_time,
+            end_time: _end_time,
+            ..CampaignState::default()
+        }
+    )?;
+
+    emit_cpi!(event);
+
+    Ok(())
+}
@@ -1,94 +1,136 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{ self, Mint, Token, TokenAccount, Transfer };
+use anchor_spl::token_interface::{ self, Mint, TokenAccount, TokenInterface, TransferChecked };
 
 use super::base::*;
 use crate::errors::ErrorCode;
+use crate::events::Staked;
+use crate::math::SafeMath;
+use crate::state::PAUSE_STAKE;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Stake<'info> {
     /// Base instruction for calculating & distributing user pending rewards.
     pub base: Base<'info>,
 
-    /// Mint address of $MESH or $indexMESH.
-    #[account(
-        constraint = (mint.key() == base.global_state.mesh_mint || mint.key() == base.global_state.index_mesh_mint) @ ErrorCode::InvalidMint
-    )]
-    pub mint: Account<'info, Mint>,
+    /// Mint address of the stake pool token.
+    #[account(address = base.stake_pool.mint @ ErrorCode::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     /// ATA of fundsConrtoller to hold mint.
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = base.funds_controller
+        associated_token::authority = base.funds_controller,
+        associated_token::token_program = token_program
     )]
-    pub mint_vault: Account<'info, TokenAccount>,
+    pub mint_vault: InterfaceAccount<'info, TokenAccount>,
 
     /// ATA of user that is holding mint.
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = base.user
+        associated_token::authority = base.user,
+        associated_token::token_program = token_program
     )]
-    pub user_mint_token_account: Account<'info, TokenAccount>,
+    pub user_mint_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// The program used to transfer token from user ATA to vault.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    /// The program used to transfer token from user ATA to vault, either Token or Token-2022.
+    #[account(address = mint.to_account_info().owner.key() @ ErrorCode::InvalidMint)]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 impl<'info> Stake<'info> {
-    /// Transfer $MESH or $indexMESH from user ATA to fundsController ATA.
-    fn transfer_tokens_from_user_to_vault(&self, _amount: u64) -> Result<()> {
-        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), Transfer {
+    /// Transfer stake pool tokens from user ATA to fundsController ATA.
+    /// Returns the amount actually received by the vault, which is lower than the requested
+    /// amount for mints with a transfer fee.
+    fn transfer_tokens_from_user_to_vault(&mut self, _amount: u64) -> Result<u64> {
+        let vault_balance_before = self.mint_vault.amount;
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), TransferChecked {
             from: self.user_mint_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
             to: self.mint_vault.to_account_info(),
             authority: self.base.user.to_account_info(),
         });
-        token::transfer(cpi_ctx, _amount)
+        token_interface::transfer_checked(cpi_ctx, _amount, self.mint.decimals)?;
+
+        self.mint_vault.reload()?;
+
+        self.mint_vault.amount.safe_sub(vault_balance_before)
     }
 }
 
-pub fn stake_handler(ctx: Context<Stake>, _amount: u64) -> Result<()> {
+pub fn stake_handler(ctx: Context<Stake>, _amount: u64, _lock_tier: u8) -> Result<()> {
+    if ctx.accounts.base.global_state.is_paused(PAUSE_STAKE) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
     if _amount == 0 {
         return Err(ErrorCode::ZeroInput.into());
     }
 
-    if ctx.accounts.base.global_state.reward == 0 {
-        return Err(ErrorCode::RewardIsNotSet.into());
+    let lock_tier = ctx.accounts.base.global_state
+        .get_lock_tier(_lock_tier)
+        .ok_or(ErrorCode::InvalidLockTier)?;
+
+    if ctx.accounts.base.not_have_enough_sol(0)? {
+        return Err(ErrorCode::InsufficientFunds.into());
     }
 
+    ctx.accounts.base.user_info.init(
+        ctx.accounts.base.user.key(),
+        ctx.accounts.base.stake_pool.key()
+    );
+
+    let current_time = ctx.accounts.base.global_state.get_current_time()?;
+
+    // Not allowed to lower the multiplier of a stake that is still locked.
     if
-        ctx.accounts.base.global_state.period_end_time <=
-        ctx.accounts.base.global_state.get_current_time()?
+        ctx.accounts.base.user_info.is_locked(current_time) &&
+        lock_tier.multiplier < ctx.accounts.base.user_info.lock_multiplier
     {
-        return Err(ErrorCode::RewardDistributionPeriodHasExpired.into());
-    }
-
-    if ctx.accounts.base.not_have_enough_sol(0)? {
-        return Err(ErrorCode::InsufficientFunds.into());
+        return Err(ErrorCode::LockTierDowngrade.into());
     }
 
-    ctx.accounts.base.user_info.init(ctx.accounts.base.user.key());
+    let received_amount = ctx.accounts.transfer_tokens_from_user_to_vault(_amount)?;
 
-    ctx.accounts.transfer_tokens_from_user_to_vault(_amount)?;
+    if received_amount == 0 {
+        return Err(ErrorCode::ZeroInput.into());
+    }
 
-    ctx.accounts.base.global_state.update_reward_per_share()?;
+    ctx.accounts.base.update_reward_per_share()?;
     ctx.accounts.base.harvest_user_rewards(false)?;
 
+    ctx.accounts.base.increase_stake(received_amount)?;
+
     let user_info = &mut ctx.accounts.base.user_info;
-    let global_state = &mut ctx.accounts.base.global_state;
-
-    if ctx.accounts.mint.key() == global_state.mesh_mint.key() {
-        user_info.staked_mesh = user_info.staked_mesh.checked_add(_amount).unwrap();
-        global_state.total_staked_mesh = global_state.total_staked_mesh
-            .checked_add(_amount)
-            .unwrap();
-    } else {
-        user_info.staked_index_mesh = user_info.staked_index_mesh.checked_add(_amount).unwrap();
-        global_state.total_staked_index_mesh = global_state.total_staked_index_mesh
-            .checked_add(_amount)
-            .unwrap();
-    }
+
+    // The unstake fee decays from the last stake of the user.
+    user_info.last_stake_time = current_time;
+
+    // The chosen tier applies to the whole user stake, and the lock can only be extended.
+    user_info.lock_multiplier = lock_tier.multiplier;
+    user_info.lock_end_time = user_info.lock_end_time.max(
+        current_time.safe_add(lock_tier.duration)?
+    );
+
+    ctx.accounts.base.update_weighted_stake()?;
+
+    let base = &ctx.accounts.base;
+
+    emit_cpi!(Staked {
+        user: base.user.key(),
+        mint: base.stake_pool.mint,
+        amount: received_amount,
+        lock_multiplier: base.user_info.lock_multiplier,
+        lock_end_time: base.user_info.lock_end_time,
+        user_staked: base.user_info.staked,
+        pool_total_staked: base.stake_pool.total_staked,
+        total_weighted_stake: base.global_state.total_weighted_stake,
+        global_acc_reward: base.global_state.global_acc_reward,
+        pool_acc_reward: base.stake_pool.acc_reward,
+    });
 
     Ok(())
 }
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{ self, CloseAccount, Token, TokenAccount, Transfer };
+
+use crate::errors::ErrorCode;
+use crate::events::DistributionSwept;
+use crate::state::{ Distribution, GlobalState };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepDistribution<'info> {
+    /// Only governance can execute the instruction, and receives the rent back.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to release the unclaimed SOLs.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Distribution PDA to be closed.
+    #[account(
+        mut,
+        close = governance,
+        seeds = [Distribution::SEEDS, distribution.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub distribution: Box<Account<'info, Distribution>>,
+
+    /// Distribution vault to be emptied and closed. Omitted for SOL distributions.
+    #[account(
+        mut,
+        token::mint = distribution.mint,
+        token::authority = distribution,
+    )]
+    pub distribution_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Governance token account that receives the unclaimed tokens. Omitted for SOL distributions.
+    #[account(
+        mut,
+        token::mint = distribution.mint,
+    )]
+    pub governance_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The program used to transfer the unclaimed tokens. Omitted for SOL distributions.
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+impl<'info> SweepDistribution<'info> {
+    /// Transfer the unclaimed tokens to governance token account and close the distribution vault.
+    fn sweep_tokens_to_governance(&self, _distribution_bump: u8) -> Result<u64> {
+        let (Some(distribution_vault), Some(governance_token_account), Some(token_program)) = (
+            &self.distribution_vault,
+            &self.governance_token_account,
+            &self.token_program,
+        ) else {
+            return Err(ErrorCode::InvalidDistribution.into());
+        };
+
+        let id = self.distribution.id.to_le_bytes();
+        let signer: &[&[&[u8]]] = &[&[Distribution::SEEDS, id.as_ref(), &[_distribution_bump]]];
+        let amount = distribution_vault.amount;
+
+        if amount > 0 {
+            let cpi_ctx = CpiContext {
+                program: token_program.to_account_info(),
+                accounts: Transfer {
+                    from: distribution_vault.to_account_info(),
+                    to: governance_token_account.to_account_info(),
+                    authority: self.distribution.to_account_info(),
+                },
+                remaining_accounts: Vec::new(),
+                signer_seeds: signer,
+            };
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        let cpi_ctx = CpiContext {
+            program: token_program.to_account_info(),
+            accounts: CloseAccount {
+                account: distribution_vault.to_account_info(),
+                destination: self.governance.to_account_info(),
+                authority: self.distribution.to_account_info(),
+            },
+            remaining_accounts: Vec::new(),
+            signer_seeds: signer,
+        };
+        token::close_account(cpi_ctx)?;
+
+        Ok(amount)
+    }
+}
+
+/// A funded distribution can only be swept once it has expired, an unfunded SOL distribution can be
+/// dropped at any time.
+pub fn sweep_distribution_handler(ctx: Context<SweepDistribution>) -> Result<()> {
+    let current_time = ctx.accounts.global_state.get_current_time()?;
+    let distribution = &ctx.accounts.distribution;
+
+    if distribution.is_funded && !distribution.is_expired(current_time) {
+        return Err(ErrorCode::DistributionNotExpired.into());
+    }
+
+    let amount = if distribution.mint != Pubkey::default() {
+        ctx.accounts.sweep_tokens_to_governance(ctx.bumps.distribution)?
+    } else if distribution.is_funded {
+        // The unclaimed SOLs never left the fundsController, so they are released for future rewards.
+        let unclaimed_amount = distribution.get_unclaimed_amount()?;
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.total_distributed_reward = global_state.total_distributed_reward.saturating_sub(
+            unclaimed_amount
+        );
+        unclaimed_amount
+    } else {
+        0
+    };
+
+    emit_cpi!(DistributionSwept {
+        distribution: ctx.accounts.distribution.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,63 +1,33 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{ self, Mint, Token, TokenAccount, Transfer };
 
 use super::base::*;
 use crate::errors::ErrorCode;
-use crate::state::FundsController;
+use crate::events::Unstaked;
+use crate::math::SafeMath;
+use crate::state::{ WithdrawalTicket, PAUSE_UNSTAKE };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Unstake<'info> {
     /// Base instruction for calculating & distributing user pending rewards.
     pub base: Base<'info>,
 
-    /// Mint address of $MESH or $indexMESH.
+    /// Ticket PDA to hold the unstaked amount until the unbonding cooldown has passed.
     #[account(
-        constraint = (mint.key() == base.global_state.mesh_mint || mint.key() ==  base.global_state.index_mesh_mint) @ ErrorCode::InvalidMint
+        init,
+        seeds = [
+            base.user_info.key().as_ref(),
+            WithdrawalTicket::SEEDS,
+            &base.user_info.next_ticket_id.to_le_bytes(),
+        ],
+        bump,
+        payer = base.user,
+        space = WithdrawalTicket::LEN
     )]
-    pub mint: Account<'info, Mint>,
+    pub withdrawal_ticket: Account<'info, WithdrawalTicket>,
 
-    /// ATA of fundsConrtoller that is holding mint.
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = base.funds_controller
-    )]
-    pub mint_vault: Account<'info, TokenAccount>,
-
-    /// ATA of user which will receive mint.
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = base.user
-    )]
-    pub user_mint_token_account: Account<'info, TokenAccount>,
-
-    /// The program used to transfer token from vault to user ATA.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
-}
-
-impl<'info> Unstake<'info> {
-    /// Transfer $MESH or $indexMESH from fundsController ATA to user ATA.
-    fn transfer_tokens_from_vault_to_user(
-        &self,
-        _amount: u64,
-        _funds_controller_bump: u8
-    ) -> Result<()> {
-        let signer: &[&[&[u8]]] = &[&[FundsController::SEEDS, &[_funds_controller_bump]]];
-
-        let cpi_ctx = CpiContext {
-            program: self.token_program.to_account_info(),
-            accounts: Transfer {
-                from: self.mint_vault.to_account_info(),
-                to: self.user_mint_token_account.to_account_info(),
-                authority: self.base.funds_controller.to_account_info(),
-            },
-            remaining_accounts: Vec::new(),
-            signer_seeds: signer,
-        };
-        token::transfer(cpi_ctx, _amount)
-    }
+    /// The program used to create the withdrawal ticket account.
+    pub system_program: Program<'info, System>,
 }
 
 pub fn unstake_handler(ctx: Context<Unstake>, _amount: u64, _is_emergency: bool) -> Result<()> {
@@ -67,39 +37,78 @@ pub fn unstake_handler(ctx: Context<Unstake>, _amount: u64, _is_emergency: bool)
 
     let base = &ctx.accounts.base;
 
-    if
-        ctx.accounts.mint.key() == base.global_state.mesh_mint.key() &&
-        _amount > base.user_info.staked_mesh
-    {
-        return Err(ErrorCode::AmountGreaterThanStakedAmount.into());
+    // The reward math is not trusted in safe mode, users exit through emergency withdraw instead.
+    if base.global_state.safe_mode {
+        return Err(ErrorCode::SafeModeActive.into());
     }
 
-    if
-        ctx.accounts.mint.key() == base.global_state.index_mesh_mint.key() &&
-        _amount > base.user_info.staked_index_mesh
-    {
+    // Emergency unstake always stays available to let users exit while the program is paused.
+    if !_is_emergency && base.global_state.is_paused(PAUSE_UNSTAKE) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
+    if _amount > base.user_info.staked {
         return Err(ErrorCode::AmountGreaterThanStakedAmount.into());
     }
 
-    ctx.accounts.transfer_tokens_from_vault_to_user(_amount, ctx.bumps.base.funds_controller)?;
+    let current_time = base.global_state.get_current_time()?;
+
+    // Locked stake can't be unstaked before the lock ends, not even through the emergency unstake.
+    if base.user_info.is_locked(current_time) {
+        return Err(ErrorCode::StakeIsLocked.into());
+    }
+
+    // Emergency unstake only exits the stake, so it is exempt from the unstake fee.
+    let unstake_fee = base.global_state.unstake_fee;
+    let fee = if _is_emergency {
+        0
+    } else {
+        unstake_fee.calculate_fee(_amount, base.user_info.last_stake_time, current_time)?
+    };
 
-    ctx.accounts.base.global_state.update_reward_per_share()?;
+    ctx.accounts.base.update_reward_per_share()?;
     ctx.accounts.base.harvest_user_rewards(_is_emergency)?;
 
-    let user_info = &mut ctx.accounts.base.user_info;
-    let global_state = &mut ctx.accounts.base.global_state;
+    // The unstaked amount stops accruing rewards right away and waits in the ticket for withdrawal.
+    ctx.accounts.base.decrease_stake(_amount)?;
 
-    if ctx.accounts.mint.key() == global_state.mesh_mint.key() {
-        user_info.staked_mesh = user_info.staked_mesh.checked_sub(_amount).unwrap();
-        global_state.total_staked_mesh = global_state.total_staked_mesh
-            .checked_sub(_amount)
-            .unwrap();
-    } else {
-        user_info.staked_index_mesh = user_info.staked_index_mesh.checked_sub(_amount).unwrap();
-        global_state.total_staked_index_mesh = global_state.total_staked_index_mesh
-            .checked_sub(_amount)
-            .unwrap();
+    if fee > 0 {
+        ctx.accounts.base.stake_pool.distribute_unstake_fee(fee, unstake_fee.redistribute)?;
     }
 
+    ctx.accounts.base.release_expired_lock()?;
+    ctx.accounts.base.update_weighted_stake()?;
+
+    let user_info = &mut ctx.accounts.base.user_info;
+    let withdrawal_ticket = &mut ctx.accounts.withdrawal_ticket;
+
+    withdrawal_ticket.user = user_info.user;
+    withdrawal_ticket.pool = user_info.pool;
+    withdrawal_ticket.mint = ctx.accounts.base.stake_pool.mint;
+    withdrawal_ticket.id = user_info.next_ticket_id;
+    withdrawal_ticket.amount = _amount.safe_sub(fee)?;
+    withdrawal_ticket.unlock_time = current_time
+        .safe_add(ctx.accounts.base.global_state.unbonding_cooldown)?;
+
+    user_info.next_ticket_id = user_info.next_ticket_id.safe_add(1)?;
+    user_info.open_tickets = user_info.open_tickets.safe_add(1)?;
+
+    let base = &ctx.accounts.base;
+
+    emit_cpi!(Unstaked {
+        user: base.user.key(),
+        mint: base.stake_pool.mint,
+        amount: withdrawal_ticket.amount,
+        fee,
+        ticket_id: withdrawal_ticket.id,
+        unlock_time: withdrawal_ticket.unlock_time,
+        is_emergency: _is_emergency,
+        user_staked: base.user_info.staked,
+        pool_total_staked: base.stake_pool.total_staked,
+        total_weighted_stake: base.global_state.total_weighted_stake,
+        global_acc_reward: base.global_state.global_acc_reward,
+        pool_acc_reward: base.stake_pool.acc_reward,
+    });
+
     Ok(())
 }
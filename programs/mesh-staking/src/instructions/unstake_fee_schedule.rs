@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::math::SafeMath;
+use crate::state::{ GlobalState, UserInfo };
+
+#[derive(Accounts)]
+pub struct UnstakeFeeSchedule<'info> {
+    /// Global state PDA to read the unstake fee.
+    #[account(seeds = [GlobalState::SEEDS], bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// UserInfo PDA to calculate the current fee of the user, if given.
+    pub user_info: Option<Account<'info, UserInfo>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UnstakeFeeStruct {
+    pub fee_bps: u64,
+    pub decay_duration: u64,
+    pub redistribute: bool,
+    pub treasury: Pubkey,
+    /// Fee in basis points the user would currently pay, equal to `fee_bps` without a userInfo PDA.
+    pub current_fee_bps: u64,
+    /// Unix timestamp after which the user pays no fee, zero if the fee does not decay.
+    pub fee_free_time: u64,
+}
+
+pub fn unstake_fee_schedule_handler(ctx: Context<UnstakeFeeSchedule>) -> Result<UnstakeFeeStruct> {
+    let unstake_fee = ctx.accounts.global_state.unstake_fee;
+    let current_time = ctx.accounts.global_state.get_current_time()?;
+
+    let mut current_fee_bps = unstake_fee.fee_bps;
+    let mut fee_free_time = 0;
+
+    if let Some(user_info) = &ctx.accounts.user_info {
+        current_fee_bps = unstake_fee.get_fee_bps(user_info.last_stake_time, current_time)?;
+
+        if unstake_fee.decay_duration != 0 {
+            fee_free_time = user_info.last_stake_time
+                .safe_add(unstake_fee.decay_duration)?;
+        }
+    }
+
+    Ok(UnstakeFeeStruct {
+        fee_bps: unstake_fee.fee_bps,
+        decay_duration: unstake_fee.decay_duration,
+        redistribute: unstake_fee.redistribute,
+        treasury: unstake_fee.treasury,
+        current_fee_bps,
+        fee_free_time,
+    })
+}
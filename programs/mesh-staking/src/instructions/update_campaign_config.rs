@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::CampaignConfigUpdated;
+use crate::state::{ CampaignConfig, GlobalState };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateCampaignConfig<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to store updated campaign config.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn update_campaign_config_handler(
+    ctx: Context<UpdateCampaignConfig>,
+    _campaign_config: CampaignConfig
+) -> Result<()> {
+    if _campaign_config.min_duration > _campaign_config.max_duration {
+        return Err(ErrorCode::InvalidCampaignConfig.into());
+    }
+
+    let global_state = &mut ctx.accounts.global_state;
+
+    // Campaign tokens are paid out through their reward stream, so only opened streams can be allowed.
+    for (index, campaign_mint) in _campaign_config.mints.iter().enumerate() {
+        if campaign_mint.mint == Pubkey::default() {
+            continue;
+        }
+
+        let is_duplicate = _campaign_config.mints[..index]
+            .iter()
+            .any(|other| other.mint == campaign_mint.mint);
+
+        let is_streamed = global_state.reward_streams
+            .iter()
+            .any(|reward_stream| reward_stream.is_opened() && reward_stream.mint == campaign_mint.mint);
+
+        if is_duplicate || !is_streamed {
+            return Err(ErrorCode::InvalidCampaignMint.into());
+        }
+    }
+
+    // Running campaigns keep the config they were opened with.
+    global_state.campaign_config = _campaign_config;
+
+    emit_cpi!(CampaignConfigUpdated { campaign_config: _campaign_config });
+
+    Ok(())
+}
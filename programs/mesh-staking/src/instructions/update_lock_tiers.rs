@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::LockTiersUpdated;
+use crate::state::{ GlobalState, LockTier, MAX_LOCK_TIERS, SACLE_FACTOR_BASE };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateLockTiers<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to store updated lock tiers.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn update_lock_tiers_handler(
+    ctx: Context<UpdateLockTiers>,
+    _lock_tiers: Vec<LockTier>
+) -> Result<()> {
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
+    }
+
+    let event = update_lock_tiers(&mut ctx.accounts.global_state, &_lock_tiers)?;
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Check that the lock tiers start with the liquid tier and that no enabled tier lowers the stake.
+pub fn validate_lock_tiers(_lock_tiers: &[LockTier]) -> Result<()> {
+    if _lock_tiers.len() > MAX_LOCK_TIERS {
+        return Err(ErrorCode::InvalidLockTier.into());
+    }
+
+    // Tier 0 is always the liquid tier, which unlocked stakes fall back to.
+    let liquid = LockTier::liquid();
+    let first_tier = _lock_tiers.first().ok_or(ErrorCode::InvalidLockTier)?;
+
+    if first_tier.duration != liquid.duration || first_tier.multiplier != liquid.multiplier {
+        return Err(ErrorCode::InvalidLockTier.into());
+    }
+
+    // Enabled tiers can't have a multiplier lower than 1x.
+    if
+        _lock_tiers
+            .iter()
+            .any(|tier| tier.is_enabled() && u128::from(tier.multiplier) < SACLE_FACTOR_BASE)
+    {
+        return Err(ErrorCode::InvalidLockTier.into());
+    }
+
+    Ok(())
+}
+
+/// Update the lock tiers that users can choose while staking.
+/// Shared by the direct instruction and the timelock execution.
+pub fn update_lock_tiers(
+    _global_state: &mut GlobalState,
+    _lock_tiers: &[LockTier]
+) -> Result<LockTiersUpdated> {
+    validate_lock_tiers(_lock_tiers)?;
+
+    // Existing stakes keep the multiplier they were locked with, so no reward update is needed.
+    _global_state.lock_tiers = [LockTier::default(); MAX_LOCK_TIERS];
+    _global_state.lock_tiers[.._lock_tiers.len()].copy_from_slice(_lock_tiers);
+
+    Ok(LockTiersUpdated { lock_tiers: _global_state.lock_tiers })
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::MaxVoterWeightRecordUpdated;
+use crate::math::{ SafeCast, SafeMath };
+use crate::state::{ GlobalState, MaxVoterWeightRecord, StakePool };
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(_realm: Pubkey, _governing_token_mint: Pubkey)]
+pub struct UpdateMaxVoterWeightRecord<'info> {
+    /// Anyone can update the max voter weight, as it always counts every stake pool.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Global state PDA to read the number of stake pools.
+    #[account(
+        seeds = [GlobalState::SEEDS],
+        bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Init the max voter weight record PDA of the realm, else just load mutably.
+    #[account(
+        init_if_needed,
+        seeds = [MaxVoterWeightRecord::SEEDS, _realm.as_ref(), _governing_token_mint.as_ref()],
+        bump,
+        payer = payer,
+        space = MaxVoterWeightRecord::LEN
+    )]
+    pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+
+    /// The program used to create the max voter weight record account.
+    pub system_program: Program<'info, System>,
+}
+
+/// Every stake pool PDA is passed as a remaining account. The record expires at the current slot,
+/// so the update has to be part of the same transaction as the governance instruction that reads it.
+pub fn update_max_voter_weight_record_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateMaxVoterWeightRecord<'info>>,
+    _realm: Pubkey,
+    _governing_token_mint: Pubkey
+) -> Result<()> {
+    let pool_count: usize = ctx.accounts.global_state.pool_count.safe_cast()?;
+
+    if ctx.remaining_accounts.len() != pool_count {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
+    }
+
+    let mut max_voter_weight: u128 = 0;
+    let mut counted_pools = Vec::with_capacity(pool_count);
+
+    for account in ctx.remaining_accounts {
+        let stake_pool = Account::<StakePool>::try_from(account)?;
+
+        // Leaving out a pool would lower the max voter weight, so each pool has to be passed once.
+        if counted_pools.contains(&stake_pool.key()) {
+            return Err(ErrorCode::InvalidRemainingAccounts.into());
+        }
+        counted_pools.push(stake_pool.key());
+
+        max_voter_weight = max_voter_weight.safe_add(
+            stake_pool.to_voting_power(stake_pool.total_staked)?
+        )?;
+    }
+
+    let current_slot = Clock::get()?.slot;
+
+    let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
+    max_voter_weight_record.realm = _realm;
+    max_voter_weight_record.governing_token_mint = _governing_token_mint;
+    max_voter_weight_record.max_voter_weight = max_voter_weight.safe_cast()?;
+    max_voter_weight_record.max_voter_weight_expiry = Some(current_slot);
+
+    emit_cpi!(MaxVoterWeightRecordUpdated {
+        realm: _realm,
+        governing_token_mint: _governing_token_mint,
+        max_voter_weight: max_voter_weight_record.max_voter_weight,
+        max_voter_weight_expiry: current_slot,
+    });
+
+    Ok(())
+}
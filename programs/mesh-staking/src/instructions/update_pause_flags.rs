@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::PauseFlagsUpdated;
+use crate::state::GlobalState;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePauseFlags<'info> {
+    /// Only pauser can execute and pay for the instruction.
+    #[account(mut)]
+    pub pauser: Signer<'info>,
+
+    /// Global state PDA to store updated pause flags.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = pauser @ ErrorCode::InvalidPauser,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn update_pause_flags_handler(ctx: Context<UpdatePauseFlags>, _pause_flags: u8) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let prev_pause_flags = global_state.pause_flags;
+    global_state.pause_flags = _pause_flags;
+
+    emit_cpi!(PauseFlagsUpdated { prev_pause_flags, pause_flags: _pause_flags });
+
+    Ok(())
+}
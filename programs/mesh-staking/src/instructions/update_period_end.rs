@@ -1,40 +1,105 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
-use crate::state::GlobalState;
+use crate::events::PeriodEndUpdated;
+use crate::math::{ reward_for_duration, SafeMath };
+use crate::state::{ GlobalState, RewardSchedule, PAUSE_REWARD_UPDATES };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdatePeriodEnd<'info> {
-    /// Only governance can execute and pay for the instruction.
+    /// Only reward manager can execute and pay for the instruction.
     #[account(mut)]
-    pub governance: Signer<'info>,
+    pub reward_manager: Signer<'info>,
 
     /// Global state PDA to store the updated period_end_time.
     #[account(
         mut,
         seeds = [GlobalState::SEEDS],
         bump,
-        has_one = governance @ ErrorCode::InvalidGovernance,
+        has_one = reward_manager @ ErrorCode::InvalidRewardManager,
       )]
-    pub global_state: Account<'info, GlobalState>,
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to start the scheduled reward cycles that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
 }
 
 pub fn update_period_end_handler(
     ctx: Context<UpdatePeriodEnd>,
     _distribution_time: u64
 ) -> Result<()> {
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
+    }
+
+    let event = update_period_end(
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.reward_schedule,
+        _distribution_time
+    )?;
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Move the end of the current reward cycle, keeping the same reward rate.
+/// Shared by the direct instruction and the timelock execution.
+pub fn update_period_end(
+    _global_state: &mut GlobalState,
+    _reward_schedule: &mut RewardSchedule,
+    _distribution_time: u64
+) -> Result<PeriodEndUpdated> {
+    if _global_state.is_paused(PAUSE_REWARD_UPDATES) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
     if _distribution_time == 0 {
         return Err(ErrorCode::ZeroInput.into());
     }
 
-    ctx.accounts.global_state.update_reward_per_share()?;
+    _global_state.update_reward_per_share(_reward_schedule)?;
 
-    let global_state = &mut ctx.accounts.global_state;
+    let current_time = _global_state.get_current_time()?;
 
-    global_state.period_end_time = global_state
-        .get_current_time()?
-        .checked_add(_distribution_time)
-        .unwrap();
+    // Nothing accrues while no cycle is running, so the idle time since the end of the last cycle is skipped.
+    _global_state.last_updated_time = current_time;
 
-    Ok(())
+    // The same reward rate continues up to the new period end, so the committed rewards are
+    // adjusted by the difference between the new and the remaining emission.
+    let remaining_reward = reward_for_duration(
+        _global_state.reward,
+        _global_state.period_end_time.saturating_sub(current_time),
+        _global_state.distribution_time
+    )?;
+
+    let new_reward = reward_for_duration(
+        _global_state.reward,
+        _distribution_time,
+        _global_state.distribution_time
+    )?;
+
+    _global_state.period_end_time = current_time.safe_add(_distribution_time)?;
+    _global_state.total_distributed_reward = _global_state.total_distributed_reward
+        .safe_sub(remaining_reward)?
+        .safe_add(new_reward)?;
+
+    if !_global_state.is_reward_funded() {
+        return Err(ErrorCode::RewardExceedsFunds.into());
+    }
+
+    if _reward_schedule.is_overlapping(_global_state.period_end_time) {
+        return Err(ErrorCode::OverlapsScheduledCycle.into());
+    }
+
+    Ok(PeriodEndUpdated {
+        period_end_time: _global_state.period_end_time,
+        total_distributed_reward: _global_state.total_distributed_reward,
+        global_acc_reward: _global_state.global_acc_reward,
+    })
 }
@@ -1,62 +1,124 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
-use crate::state::GlobalState;
+use crate::events::RewardsUpdated;
+use crate::math::{ reward_for_duration, time_elapsed, SafeMath };
+use crate::state::{ GlobalState, RewardSchedule, PAUSE_REWARD_UPDATES };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateRewards<'info> {
-    /// Only governance can execute and pay for the instruction.
+    /// Only reward manager can execute and pay for the instruction.
     #[account(mut)]
-    pub governance: Signer<'info>,
+    pub reward_manager: Signer<'info>,
 
     /// Global state PDA to store updated reward and distribution_time.
     #[account(
         mut,
         seeds = [GlobalState::SEEDS],
         bump,
-        has_one = governance @ ErrorCode::InvalidGovernance,
+        has_one = reward_manager @ ErrorCode::InvalidRewardManager,
       )]
-    pub global_state: Account<'info, GlobalState>,
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to start the scheduled reward cycles that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
 }
 
 pub fn update_rewards_handler(
     ctx: Context<UpdateRewards>,
     _reward: u64,
-    _distribution_time: u64
+    _distribution_time: u64,
+    _roll_over_stranded: bool
 ) -> Result<()> {
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
+    }
+
+    let event = update_rewards(
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.reward_schedule,
+        _reward,
+        _distribution_time,
+        _roll_over_stranded
+    )?;
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Start a new reward cycle, rolling over the remaining rewards of the current cycle and optionally
+/// the stranded rewards. Shared by the direct instruction and the timelock execution.
+pub fn update_rewards(
+    _global_state: &mut GlobalState,
+    _reward_schedule: &mut RewardSchedule,
+    _reward: u64,
+    _distribution_time: u64,
+    _roll_over_stranded: bool
+) -> Result<RewardsUpdated> {
+    if _global_state.is_paused(PAUSE_REWARD_UPDATES) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
     if _distribution_time == 0 {
         return Err(ErrorCode::ZeroInput.into());
     }
 
-    ctx.accounts.global_state.update_reward_per_share()?;
+    _global_state.update_reward_per_share(_reward_schedule)?;
 
-    let global_state = &mut ctx.accounts.global_state;
+    let current_time = _global_state.get_current_time()?;
 
-    let current_time = global_state.get_current_time()?;
+    // Nothing accrues while no cycle is running, so the idle time since the end of the last cycle is skipped.
+    _global_state.last_updated_time = current_time;
 
-    if current_time >= global_state.period_end_time {
-        if _reward == 0 {
+    // Stranded rewards are already part of the committed rewards, so they only raise the cycle reward.
+    let rolled_over_stranded = if _roll_over_stranded {
+        std::mem::take(&mut _global_state.stranded_reward)
+    } else {
+        0
+    };
+
+    if current_time >= _global_state.period_end_time {
+        if _reward == 0 && rolled_over_stranded == 0 {
             return Err(ErrorCode::ZeroInput.into());
         }
 
-        global_state.reward = _reward;
+        _global_state.reward = _reward.safe_add(rolled_over_stranded)?;
     } else {
-        let remaining_reward = global_state.period_end_time
-            .checked_sub(current_time)
-            .unwrap()
-            .checked_mul(global_state.reward)
-            .unwrap()
-            .checked_div(global_state.distribution_time)
-            .unwrap();
-
-        global_state.reward = _reward.checked_add(remaining_reward).unwrap();
+        let remaining_reward = reward_for_duration(
+            _global_state.reward,
+            time_elapsed(current_time, _global_state.period_end_time)?,
+            _global_state.distribution_time
+        )?;
+
+        _global_state.reward = _reward.safe_add(remaining_reward)?.safe_add(rolled_over_stranded)?;
     }
 
-    global_state.distribution_time = _distribution_time;
-    global_state.period_end_time = current_time.checked_add(_distribution_time).unwrap();
-    global_state.total_distributed_reward = global_state.total_distributed_reward
-        .checked_add(_reward)
-        .unwrap();
+    _global_state.distribution_time = _distribution_time;
+    _global_state.period_end_time = current_time.safe_add(_distribution_time)?;
+    _global_state.total_distributed_reward = _global_state.total_distributed_reward
+        .safe_add(_reward)?;
 
-    Ok(())
+    if !_global_state.is_reward_funded() {
+        return Err(ErrorCode::RewardExceedsFunds.into());
+    }
+
+    if _reward_schedule.is_overlapping(_global_state.period_end_time) {
+        return Err(ErrorCode::OverlapsScheduledCycle.into());
+    }
+
+    Ok(RewardsUpdated {
+        added_reward: _reward,
+        rolled_over_stranded,
+        reward: _global_state.reward,
+        distribution_time: _global_state.distribution_time,
+        period_end_time: _global_state.period_end_time,
+        total_distributed_reward: _global_state.total_distributed_reward,
+        global_acc_reward: _global_state.global_acc_reward,
+    })
 }
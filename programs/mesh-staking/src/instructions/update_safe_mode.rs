@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::SafeModeUpdated;
+use crate::state::GlobalState;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateSafeMode<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to store the safe mode.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn update_safe_mode_handler(ctx: Context<UpdateSafeMode>, _safe_mode: bool) -> Result<()> {
+    // Like pausing, safe mode is an emergency action and is never timelocked.
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.safe_mode = _safe_mode;
+
+    emit_cpi!(SafeModeUpdated { safe_mode: _safe_mode });
+
+    Ok(())
+}
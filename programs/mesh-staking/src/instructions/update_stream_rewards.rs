@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::StreamRewardsUpdated;
+use crate::math::{ reward_for_duration, time_elapsed, SafeMath };
+use crate::state::{ GlobalState, RewardSchedule, PAUSE_REWARD_UPDATES };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateStreamRewards<'info> {
+    /// Only reward manager can execute and pay for the instruction.
+    #[account(mut)]
+    pub reward_manager: Signer<'info>,
+
+    /// Global state PDA to store updated reward and distribution_time of the stream.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = reward_manager @ ErrorCode::InvalidRewardManager,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to start the scheduled reward cycles that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+}
+
+pub fn update_stream_rewards_handler(
+    ctx: Context<UpdateStreamRewards>,
+    _stream_index: u8,
+    _reward: u64,
+    _distribution_time: u64
+) -> Result<()> {
+    if ctx.accounts.global_state.is_paused(PAUSE_REWARD_UPDATES) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
+    if _distribution_time == 0 {
+        return Err(ErrorCode::ZeroInput.into());
+    }
+
+    ctx.accounts.global_state.update_reward_per_share(&mut ctx.accounts.reward_schedule)?;
+
+    let global_state = &mut ctx.accounts.global_state;
+
+    let current_time = global_state.get_current_time()?;
+
+    let reward_stream = global_state.reward_streams
+        .get_mut(_stream_index as usize)
+        .filter(|reward_stream| reward_stream.is_opened())
+        .ok_or(ErrorCode::InvalidRewardStream)?;
+
+    if current_time >= reward_stream.period_end_time {
+        if _reward == 0 {
+            return Err(ErrorCode::ZeroInput.into());
+        }
+
+        reward_stream.reward = _reward;
+    } else {
+        let remaining_reward = reward_for_duration(
+            reward_stream.reward,
+            time_elapsed(current_time, reward_stream.period_end_time)?,
+            reward_stream.distribution_time
+        )?;
+
+        reward_stream.reward = _reward.safe_add(remaining_reward)?;
+    }
+
+    reward_stream.distribution_time = _distribution_time;
+    reward_stream.period_end_time = current_time.safe_add(_distribution_time)?;
+    reward_stream.last_updated_time = current_time;
+    reward_stream.total_distributed_reward = reward_stream.total_distributed_reward
+        .safe_add(_reward)?;
+
+    if !reward_stream.is_reward_funded() {
+        return Err(ErrorCode::RewardExceedsFunds.into());
+    }
+
+    emit_cpi!(StreamRewardsUpdated {
+        mint: reward_stream.mint,
+        stream_index: _stream_index,
+        added_reward: _reward,
+        reward: reward_stream.reward,
+        distribution_time: reward_stream.distribution_time,
+        period_end_time: reward_stream.period_end_time,
+        total_distributed_reward: reward_stream.total_distributed_reward,
+        global_acc_reward: reward_stream.global_acc_reward,
+    });
+
+    Ok(())
+}
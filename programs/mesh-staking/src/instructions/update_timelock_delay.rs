@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::TimelockDelayUpdated;
+use crate::state::{ GlobalState, MIN_TIMELOCK_DELAY };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateTimelockDelay<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to store updated timelock delay.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn update_timelock_delay_handler(
+    ctx: Context<UpdateTimelockDelay>,
+    _timelock_delay: u64
+) -> Result<()> {
+    // Enabling the timelock is instant, any later change has to go through the timelock itself.
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
+    }
+
+    let event = update_timelock_delay(&mut ctx.accounts.global_state, _timelock_delay)?;
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Update the delay between queueing and executing governance actions.
+/// Shared by the direct instruction and the timelock execution.
+pub fn update_timelock_delay(
+    _global_state: &mut GlobalState,
+    _timelock_delay: u64
+) -> Result<TimelockDelayUpdated> {
+    if _timelock_delay < MIN_TIMELOCK_DELAY {
+        return Err(ErrorCode::InvalidTimelockDelay.into());
+    }
+
+    let prev_timelock_delay = _global_state.timelock_delay;
+    _global_state.timelock_delay = _timelock_delay;
+
+    Ok(TimelockDelayUpdated { prev_timelock_delay, timelock_delay: _timelock_delay })
+}
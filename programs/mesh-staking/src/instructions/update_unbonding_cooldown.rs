@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::UnbondingCooldownUpdated;
+use crate::state::{ GlobalState, MAX_UNBONDING_COOLDOWN };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateUnbondingCooldown<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to store updated unbonding cooldown.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn update_unbonding_cooldown_handler(
+    ctx: Context<UpdateUnbondingCooldown>,
+    _unbonding_cooldown: u64
+) -> Result<()> {
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
+    }
+
+    let event = update_unbonding_cooldown(&mut ctx.accounts.global_state, _unbonding_cooldown)?;
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Update the duration that unstaked tokens have to wait before they can be withdrawn.
+/// Shared by the direct instruction and the timelock execution.
+pub fn update_unbonding_cooldown(
+    _global_state: &mut GlobalState,
+    _unbonding_cooldown: u64
+) -> Result<UnbondingCooldownUpdated> {
+    // The cooldown is added to the unstake time, so it is bounded to keep the unlock time from overflowing.
+    if _unbonding_cooldown > MAX_UNBONDING_COOLDOWN {
+        return Err(ErrorCode::InvalidUnbondingCooldown.into());
+    }
+
+    // Already opened tickets keep the unlock time they were created with.
+    _global_state.unbonding_cooldown = _unbonding_cooldown;
+
+    Ok(UnbondingCooldownUpdated { unbonding_cooldown: _unbonding_cooldown })
+}
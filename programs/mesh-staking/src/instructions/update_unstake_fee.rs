@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::UnstakeFeeUpdated;
+use crate::state::{ GlobalState, UnstakeFee, MAX_UNSTAKE_FEE_BPS };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateUnstakeFee<'info> {
+    /// Only governance can execute and pay for the instruction.
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    /// Global state PDA to store updated unstake fee.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = governance @ ErrorCode::InvalidGovernance,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn update_unstake_fee_handler(
+    ctx: Context<UpdateUnstakeFee>,
+    _unstake_fee: UnstakeFee
+) -> Result<()> {
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
+    }
+
+    let event = update_unstake_fee(&mut ctx.accounts.global_state, _unstake_fee)?;
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Update the early-exit fee deducted from unstaked tokens.
+/// Shared by the direct instruction and the timelock execution.
+pub fn update_unstake_fee(
+    _global_state: &mut GlobalState,
+    _unstake_fee: UnstakeFee
+) -> Result<UnstakeFeeUpdated> {
+    if _unstake_fee.fee_bps > MAX_UNSTAKE_FEE_BPS {
+        return Err(ErrorCode::InvalidUnstakeFee.into());
+    }
+
+    // Already accrued fees are collected to the new treasury.
+    _global_state.unstake_fee = _unstake_fee;
+
+    Ok(UnstakeFeeUpdated { unstake_fee: _unstake_fee })
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::VoterWeightRecordUpdated;
+use crate::math::{ SafeCast, SafeMath };
+use crate::state::{ GlobalState, StakePool, UserInfo, VoterWeightRecord };
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(_realm: Pubkey, _governing_token_mint: Pubkey)]
+pub struct UpdateVoterWeightRecord<'info> {
+    /// User whose voter weight is written, pays for the record on the first update.
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Global state PDA to read the start of the snapshot epoch the voter weight is taken at.
+    #[account(seeds = [GlobalState::SEEDS], bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Init the voter weight record PDA of the user in the realm, else just load mutably.
+    #[account(
+        init_if_needed,
+        seeds = [
+            VoterWeightRecord::SEEDS,
+            _realm.as_ref(),
+            _governing_token_mint.as_ref(),
+            user.key().as_ref(),
+        ],
+        bump,
+        payer = user,
+        space = VoterWeightRecord::LEN
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// The program used to create the voter weight record account.
+    pub system_program: Program<'info, System>,
+}
+
+/// Pairs of stake pool and userInfo PDAs are passed as remaining accounts, one pair for each pool
+/// the stake is counted from. The stake is read at the start of the current snapshot epoch, which
+/// governance opens when a proposal starts. The record expires at the current slot, so the update has
+/// to be part of the same transaction as the governance instruction that reads it.
+pub fn update_voter_weight_record_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateVoterWeightRecord<'info>>,
+    _realm: Pubkey,
+    _governing_token_mint: Pubkey
+) -> Result<()> {
+    let pool_accounts = ctx.remaining_accounts.chunks_exact(2);
+
+    if !pool_accounts.remainder().is_empty() {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
+    }
+
+    let global_state = &ctx.accounts.global_state;
+    let mut voter_weight: u128 = 0;
+    let mut counted_pools = Vec::with_capacity(ctx.remaining_accounts.len() / 2);
+
+    for accounts in pool_accounts {
+        let stake_pool = Account::<StakePool>::try_from(&accounts[0])?;
+        let user_info = Account::<UserInfo>::try_from(&accounts[1])?;
+
+        // A pool counted twice would double the voter weight.
+        if
+            user_info.user != ctx.accounts.user.key() ||
+            user_info.pool != stake_pool.key() ||
+            counted_pools.contains(&stake_pool.key())
+        {
+            return Err(ErrorCode::InvalidRemainingAccounts.into());
+        }
+        counted_pools.push(stake_pool.key());
+
+        // Tokens unstaked after the snapshot epoch was opened keep their weight in this wallet only,
+        // so they can't vote again from another wallet.
+        let balance = user_info.balance_at(global_state, global_state.snapshot_start_time)?;
+
+        voter_weight = voter_weight.safe_add(stake_pool.to_voting_power(balance.staked)?)?;
+    }
+
+    let current_slot = Clock::get()?.slot;
+
+    let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+    voter_weight_record.realm = _realm;
+    voter_weight_record.governing_token_mint = _governing_token_mint;
+    voter_weight_record.governing_token_owner = ctx.accounts.user.key();
+    voter_weight_record.voter_weight = voter_weight.safe_cast()?;
+    voter_weight_record.voter_weight_expiry = Some(current_slot);
+    voter_weight_record.weight_action = None;
+    voter_weight_record.weight_action_target = None;
+
+    emit_cpi!(VoterWeightRecordUpdated {
+        user: ctx.accounts.user.key(),
+        realm: _realm,
+        governing_token_mint: _governing_token_mint,
+        voter_weight: voter_weight_record.voter_weight,
+        voter_weight_expiry: current_slot,
+    });
+
+    Ok(())
+}
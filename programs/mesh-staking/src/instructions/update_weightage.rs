@@ -1,29 +1,84 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
-use crate::state::GlobalState;
+use crate::events::WeightageUpdated;
+use crate::math::SafeMath;
+use crate::state::{ GlobalState, RewardSchedule, StakePool };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateWeightage<'info> {
     /// Only governance can execute and pay for the instruction.
     #[account(mut)]
     pub governance: Signer<'info>,
 
-    /// Global state PDA to store updated weightage.
+    /// Global state PDA to update the total weighted stake.
     #[account(
         mut,
         seeds = [GlobalState::SEEDS],
         bump,
         has_one = governance @ ErrorCode::InvalidGovernance,
       )]
-    pub global_state: Account<'info, GlobalState>,
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to start the scheduled reward cycles that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+
+    /// Stake pool PDA to store updated weightage.
+    #[account(
+        mut,
+        seeds = [StakePool::SEEDS, stake_pool.mint.as_ref()],
+        bump,
+    )]
+    pub stake_pool: Account<'info, StakePool>,
 }
 
 pub fn update_weightage_handler(ctx: Context<UpdateWeightage>, _weightage: u64) -> Result<()> {
-    ctx.accounts.global_state.update_reward_per_share()?;
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
+    }
 
-    let global_state = &mut ctx.accounts.global_state;
-    global_state.weightage = _weightage;
+    let event = update_weightage(
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.reward_schedule,
+        &mut ctx.accounts.stake_pool,
+        _weightage
+    )?;
+    emit_cpi!(event);
 
     Ok(())
 }
+
+/// Update the weightage of the pool and sync it with the global weighted total.
+/// Shared by the direct instruction and the timelock execution.
+pub fn update_weightage(
+    _global_state: &mut GlobalState,
+    _reward_schedule: &mut RewardSchedule,
+    _stake_pool: &mut StakePool,
+    _weightage: u64
+) -> Result<WeightageUpdated> {
+    _global_state.update_reward_per_share(_reward_schedule)?;
+    _stake_pool.update_reward_per_share(_global_state)?;
+
+    let prev_pool_weighted_stake = _stake_pool.get_weighted_stake()?;
+    let prev_weightage = _stake_pool.weightage;
+    _stake_pool.weightage = _weightage;
+
+    _global_state.total_weighted_stake = _global_state.total_weighted_stake
+        .safe_sub(prev_pool_weighted_stake)?
+        .safe_add(_stake_pool.get_weighted_stake()?)?;
+
+    Ok(WeightageUpdated {
+        mint: _stake_pool.mint,
+        prev_weightage,
+        weightage: _stake_pool.weightage,
+        total_weighted_stake: _global_state.total_weighted_stake,
+        global_acc_reward: _global_state.global_acc_reward,
+        pool_acc_reward: _stake_pool.acc_reward,
+    })
+}
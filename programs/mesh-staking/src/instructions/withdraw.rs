@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{ self, Mint, TokenAccount, TokenInterface, TransferChecked };
+
+use crate::errors::ErrorCode;
+use crate::events::Withdrawn;
+use crate::math::SafeMath;
+use crate::state::{ GlobalState, FundsController, UserInfo, WithdrawalTicket };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    /// User who owns the withdrawal ticket and receives the rent back.
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// UserInfo PDA to keep track of open withdrawal tickets.
+    #[account(
+        mut,
+        seeds = [user.key().as_ref(), withdrawal_ticket.pool.as_ref(), UserInfo::SEEDS],
+        bump,
+    )]
+    pub user_info: Account<'info, UserInfo>,
+
+    /// Ticket PDA that is redeemed and closed.
+    #[account(
+        mut,
+        has_one = user @ ErrorCode::InvalidWithdrawalTicket,
+        has_one = mint @ ErrorCode::InvalidWithdrawalTicket,
+        close = user
+    )]
+    pub withdrawal_ticket: Account<'info, WithdrawalTicket>,
+
+    /// Global state PDA.
+    #[account(seeds = [GlobalState::SEEDS], bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// PDA that holds the ownership of the vault.
+    #[account(seeds = [FundsController::SEEDS], bump)]
+    pub funds_controller: Account<'info, FundsController>,
+
+    /// Mint address of the unstaked token.
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// ATA of fundsConrtoller that is holding mint.
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = funds_controller,
+        associated_token::token_program = token_program
+    )]
+    pub mint_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// ATA of user which will receive mint.
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_mint_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The program used to transfer token from vault to user ATA, either Token or Token-2022.
+    #[account(address = mint.to_account_info().owner.key() @ ErrorCode::InvalidMint)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Withdraw<'info> {
+    /// Transfer unstaked tokens from fundsController ATA to user ATA.
+    fn transfer_tokens_from_vault_to_user(
+        &self,
+        _amount: u64,
+        _funds_controller_bump: u8
+    ) -> Result<()> {
+        let signer: &[&[&[u8]]] = &[&[FundsController::SEEDS, &[_funds_controller_bump]]];
+
+        let cpi_ctx = CpiContext {
+            program: self.token_program.to_account_info(),
+            accounts: TransferChecked {
+                from: self.mint_vault.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: self.user_mint_token_account.to_account_info(),
+                authority: self.funds_controller.to_account_info(),
+            },
+            remaining_accounts: Vec::new(),
+            signer_seeds: signer,
+        };
+        token_interface::transfer_checked(cpi_ctx, _amount, self.mint.decimals)
+    }
+}
+
+pub fn withdraw_handler(ctx: Context<Withdraw>) -> Result<()> {
+    let current_time = ctx.accounts.global_state.get_current_time()?;
+
+    if current_time < ctx.accounts.withdrawal_ticket.unlock_time {
+        return Err(ErrorCode::UnbondingCooldownNotPassed.into());
+    }
+
+    ctx.accounts.transfer_tokens_from_vault_to_user(
+        ctx.accounts.withdrawal_ticket.amount,
+        ctx.bumps.funds_controller
+    )?;
+
+    let user_info = &mut ctx.accounts.user_info;
+    user_info.open_tickets = user_info.open_tickets.safe_sub(1)?;
+
+    emit_cpi!(Withdrawn {
+        user: ctx.accounts.user.key(),
+        mint: ctx.accounts.mint.key(),
+        ticket_id: ctx.accounts.withdrawal_ticket.id,
+        amount: ctx.accounts.withdrawal_ticket.amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use super::base::*;
+use crate::errors::ErrorCode;
+use crate::events::StrandedRewardWithdrawn;
+use crate::state::{ GlobalState, FundsController, RewardSchedule, PAUSE_REWARD_UPDATES };
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawStrandedReward<'info> {
+    /// Only treasurer can execute and pay for the instruction.
+    #[account(mut)]
+    pub treasurer: Signer<'info>,
+
+    /// CHECK: Only the treasury set with the unstake fee can receive the stranded rewards.
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Global state PDA to read and reset the stranded rewards.
+    #[account(
+        mut,
+        seeds = [GlobalState::SEEDS],
+        bump,
+        has_one = treasurer @ ErrorCode::InvalidTreasurer,
+      )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// Reward schedule PDA to start the scheduled reward cycles that are due.
+    #[account(
+        mut,
+        seeds = [RewardSchedule::SEEDS],
+        bump,
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+
+    /// Transfer stranded reward SOLs to the treasury.
+    #[account(
+        mut,
+        seeds = [FundsController::SEEDS],
+        bump,
+    )]
+    pub funds_controller: Account<'info, FundsController>,
+}
+
+pub fn withdraw_stranded_reward_handler(ctx: Context<WithdrawStrandedReward>) -> Result<()> {
+    if ctx.accounts.global_state.is_timelocked() {
+        return Err(ErrorCode::TimelockRequired.into());
+    }
+
+    let event = withdraw_stranded_reward(
+        &mut ctx.accounts.global_state,
+        &mut ctx.accounts.reward_schedule,
+        &ctx.accounts.funds_controller,
+        &ctx.accounts.treasury
+    )?;
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Transfer the SOL rewards emitted while nothing was staked from fundsController to the treasury.
+/// Shared by the direct instruction and the timelock execution.
+pub fn withdraw_stranded_reward(
+    _global_state: &mut GlobalState,
+    _reward_schedule: &mut RewardSchedule,
+    _funds_controller: &Account<FundsController>,
+    _treasury: &AccountInfo
+) -> Result<StrandedRewardWithdrawn> {
+    // Only the treasury set with the unstake fee can receive the stranded rewards.
+    if _treasury.key() != _global_state.unstake_fee.treasury || _treasury.key() == Pubkey::default() {
+        return Err(ErrorCode::InvalidTreasury.into());
+    }
+
+    if _global_state.is_paused(PAUSE_REWARD_UPDATES) {
+        return Err(ErrorCode::InstructionPaused.into());
+    }
+
+    _global_state.update_reward_per_share(_reward_schedule)?;
+
+    let amount = _global_state.stranded_reward;
+
+    if amount == 0 {
+        return Err(ErrorCode::ZeroInput.into());
+    }
+
+    if not_have_enough_sol(_funds_controller, amount)? {
+        return Err(ErrorCode::InsufficientFunds.into());
+    }
+
+    transfer_sol_from_controller(_funds_controller, _treasury, amount)?;
+
+    // The stranded rewards were committed but never distributed, so they leave both totals.
+    _global_state.stranded_reward = 0;
+    _global_state.total_funded = _global_state.total_funded.saturating_sub(amount);
+    _global_state.total_distributed_reward = _global_state.total_distributed_reward.saturating_sub(
+        amount
+    );
+
+    Ok(StrandedRewardWithdrawn {
+        treasury: _treasury.key(),
+        amount,
+        total_funded: _global_state.total_funded,
+        total_distributed_reward: _global_state.total_distributed_reward,
+    })
+}
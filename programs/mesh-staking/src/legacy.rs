@@ -0,0 +1,310 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::errors::ErrorCode;
+use crate::math::{ mul_div, SafeCast, SafeMath };
+use crate::state::{
+    GlobalState,
+    LockTier,
+    StakePool,
+    UserInfo,
+    LAYOUT_VERSION,
+    MAX_REWARD_STREAMS,
+    SACLE_FACTOR_BASE,
+    SCALE_FACTOR,
+};
+
+/// Global state in the legacy layout, in which the global state held the $MESH and $indexMESH stake.
+/// Stored under the discriminator of `GlobalState`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyGlobalState {
+    pub governance: Pubkey,
+    pub mesh_mint: Pubkey,
+    pub index_mesh_mint: Pubkey,
+    pub reward: u64,
+    pub distribution_time: u64,
+    pub global_acc_reward_mesh: u128,
+    pub global_acc_reward_index_mesh: u128,
+    pub total_staked_mesh: u64,
+    pub total_staked_index_mesh: u64,
+    /// Note: the weightage is in ($MESH/$indexMESH), scaled by 1e9.
+    pub weightage: u64,
+    pub last_updated_time: u64,
+    pub period_end_time: u64,
+    pub total_distributed_reward: u64,
+}
+
+impl LegacyGlobalState {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 16 + 16 + 8 + 8 + 8 + 8 + 8 + 8;
+
+    /// Read the global state from the data of an account in the legacy layout.
+    pub fn try_from_data(_data: &[u8]) -> Result<Self> {
+        read_legacy_data(_data, Self::LEN, GlobalState::DISCRIMINATOR)
+    }
+
+    /// Write the legacy state into the migrated global state and the stake pools of $MESH and $indexMESH.
+    /// The legacy accumulators were kept per unit of $MESH and $indexMESH, so they become the pool
+    /// accumulators, while the $MESH accumulator is already per unit of weighted stake.
+    pub fn migrate(
+        &self,
+        _global_state: &mut GlobalState,
+        _mesh_pool: &mut StakePool,
+        _index_mesh_pool: &mut StakePool,
+        _legacy_pools: [Pubkey; 2]
+    ) -> Result<()> {
+        _global_state.governance = self.governance;
+        _global_state.reward_manager = self.governance;
+        _global_state.pauser = self.governance;
+        _global_state.treasurer = self.governance;
+        _global_state.reward = self.reward;
+        _global_state.distribution_time = self.distribution_time;
+        _global_state.global_acc_reward = self.global_acc_reward_mesh;
+        _global_state.last_updated_time = self.last_updated_time;
+        _global_state.period_end_time = self.period_end_time;
+        _global_state.total_distributed_reward = self.total_distributed_reward;
+        // The legacy layout didn't track the funding, the distributed rewards are taken as funded.
+        _global_state.total_funded = self.total_distributed_reward;
+        _global_state.lock_tiers[0] = LockTier::liquid();
+        _global_state.pool_count = 2;
+        _global_state.legacy_pools = _legacy_pools;
+        _global_state.layout_version = LAYOUT_VERSION;
+
+        migrate_pool(
+            _mesh_pool,
+            self.mesh_mint,
+            SACLE_FACTOR_BASE.safe_cast()?,
+            self.total_staked_mesh,
+            self.global_acc_reward_mesh,
+            _global_state
+        );
+        migrate_pool(
+            _index_mesh_pool,
+            self.index_mesh_mint,
+            self.weightage,
+            self.total_staked_index_mesh,
+            self.global_acc_reward_index_mesh,
+            _global_state
+        );
+
+        _global_state.total_weighted_stake = _mesh_pool
+            .get_weighted_stake()?
+            .safe_add(_index_mesh_pool.get_weighted_stake()?)?;
+
+        Ok(())
+    }
+}
+
+/// UserInfo PDA in the legacy layout, which held both the $MESH and $indexMESH stake of the user under the
+/// `[user, "user_info"]` seeds. Stored under the discriminator of `UserInfo`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyUserInfo {
+    pub user: Pubkey,
+    pub acc_reward_mesh: u128,
+    pub acc_reward_index_mesh: u128,
+    pub staked_mesh: u64,
+    pub staked_index_mesh: u64,
+    pub total_claimed_reward: u64,
+}
+
+impl LegacyUserInfo {
+    pub const LEN: usize = 8 + 32 + 16 + 16 + 8 + 8 + 8;
+
+    /// Read the userInfo from the data of an account in the legacy layout.
+    pub fn try_from_data(_data: &[u8]) -> Result<Self> {
+        read_legacy_data(_data, Self::LEN, UserInfo::DISCRIMINATOR)
+    }
+
+    /// Write the userInfo back into the data of its legacy account.
+    pub fn try_write_data(&self, _data: &mut [u8]) -> Result<()> {
+        self.serialize(&mut &mut _data[8..])?;
+        Ok(())
+    }
+
+    /// Check if both legacy stakes have been migrated.
+    pub fn is_empty(&self) -> bool {
+        self.staked_mesh == 0 && self.staked_index_mesh == 0
+    }
+
+    /// Take the stake and the accumulated reward of the legacy position at the given index of the legacy pools,
+    /// leaving the position empty.
+    pub fn take_position(&mut self, _index: usize) -> (u64, u128) {
+        if _index == 0 {
+            (std::mem::take(&mut self.staked_mesh), self.acc_reward_mesh)
+        } else {
+            (std::mem::take(&mut self.staked_index_mesh), self.acc_reward_index_mesh)
+        }
+    }
+}
+
+/// Check the size and the discriminator of a legacy account and read its state.
+fn read_legacy_data<T: AnchorDeserialize>(_data: &[u8], _len: usize, _discriminator: [u8; 8]) -> Result<T> {
+    if _data.len() != _len || _data[..8] != _discriminator {
+        return Err(ErrorCode::InvalidLegacyAccount.into());
+    }
+
+    Ok(T::deserialize(&mut &_data[8..])?)
+}
+
+/// Start a stake pool from the legacy stake of its token, with the legacy accumulator per unit of the token.
+/// Legacy stakes had no lock, so the weighted stake equals the staked amount.
+fn migrate_pool(
+    _stake_pool: &mut StakePool,
+    _mint: Pubkey,
+    _weightage: u64,
+    _total_staked: u64,
+    _acc_reward: u128,
+    _global_state: &GlobalState
+) {
+    _stake_pool.mint = _mint;
+    _stake_pool.weightage = _weightage;
+    _stake_pool.total_staked = _total_staked;
+    _stake_pool.total_weighted = _total_staked;
+    _stake_pool.acc_reward = _acc_reward;
+    _stake_pool.global_acc_reward_checkpoint = _global_state.global_acc_reward;
+    _stake_pool.stream_acc_reward_checkpoints = _global_state.get_stream_acc_rewards();
+}
+
+/// Merge a legacy position into the userInfo of its stake pool. The pool and the userInfo must be updated
+/// and settled before, so only the rewards of the legacy position are settled here: the SOL rewards are
+/// owed, and since the migrated pool started the stream and fee accumulators at zero, the legacy position
+/// earned them in full.
+pub fn merge_legacy_position(
+    _staked: u64,
+    _acc_reward: u128,
+    _stake_pool: &mut StakePool,
+    _user_info: &mut UserInfo
+) -> Result<()> {
+    let staked = GlobalState::to_u128(_staked);
+
+    let reward: u64 = mul_div(_stake_pool.acc_reward.safe_sub(_acc_reward)?, staked, SCALE_FACTOR)?.safe_cast()?;
+    _user_info.unclaimed_owed = _user_info.unclaimed_owed.safe_add(reward)?;
+
+    for index in 0..MAX_REWARD_STREAMS {
+        let reward: u64 = mul_div(_stake_pool.stream_acc_rewards[index], staked, SCALE_FACTOR)?.safe_cast()?;
+        _user_info.stream_pending_rewards[index] = _user_info.stream_pending_rewards[index].safe_add(reward)?;
+    }
+
+    // Redistributed unstake fees are compounded into the stake, as in the fee settlement.
+    let fee_rewards: u64 = mul_div(_stake_pool.fee_acc_reward, staked, SCALE_FACTOR)?.safe_cast()?;
+    _stake_pool.total_staked = _stake_pool.total_staked.safe_add(fee_rewards)?;
+
+    // The legacy stake is already counted in the pool totals at 1x, the weighted stake update applies the
+    // lock multiplier of the position.
+    _user_info.staked = _user_info.staked.safe_add(_staked)?.safe_add(fee_rewards)?;
+    _user_info.weighted = _user_info.weighted.safe_add(_staked)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESH_ACC_REWARD: u128 = 7 * SCALE_FACTOR;
+    const INDEX_MESH_ACC_REWARD: u128 = 3 * SCALE_FACTOR;
+    const WEIGHTAGE: u64 = 500_000_000;
+
+    fn zeroed<T: AccountDeserialize>(_len: usize) -> T {
+        T::try_deserialize_unchecked(&mut &vec![0u8; _len][..]).unwrap()
+    }
+
+    /// Global state data as written by the program in the legacy layout.
+    fn legacy_global_state_data(mesh_mint: Pubkey, index_mesh_mint: Pubkey) -> Vec<u8> {
+        let mut data = GlobalState::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(mesh_mint.as_ref());
+        data.extend_from_slice(index_mesh_mint.as_ref());
+        data.extend_from_slice(&1_000_000_000u64.to_le_bytes()); // reward
+        data.extend_from_slice(&100u64.to_le_bytes()); // distribution_time
+        data.extend_from_slice(&MESH_ACC_REWARD.to_le_bytes());
+        data.extend_from_slice(&INDEX_MESH_ACC_REWARD.to_le_bytes());
+        data.extend_from_slice(&300u64.to_le_bytes()); // total_staked_mesh
+        data.extend_from_slice(&400u64.to_le_bytes()); // total_staked_index_mesh
+        data.extend_from_slice(&WEIGHTAGE.to_le_bytes());
+        data.extend_from_slice(&50u64.to_le_bytes()); // last_updated_time
+        data.extend_from_slice(&150u64.to_le_bytes()); // period_end_time
+        data.extend_from_slice(&2_000_000_000u64.to_le_bytes()); // total_distributed_reward
+        data
+    }
+
+    /// UserInfo data as written by the program in the legacy layout.
+    fn legacy_user_info_data(user: Pubkey) -> Vec<u8> {
+        let mut data = UserInfo::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(user.as_ref());
+        data.extend_from_slice(&(5 * SCALE_FACTOR).to_le_bytes()); // acc_reward_mesh
+        data.extend_from_slice(&(2 * SCALE_FACTOR).to_le_bytes()); // acc_reward_index_mesh
+        data.extend_from_slice(&100u64.to_le_bytes()); // staked_mesh
+        data.extend_from_slice(&200u64.to_le_bytes()); // staked_index_mesh
+        data.extend_from_slice(&0u64.to_le_bytes()); // total_claimed_reward
+        data
+    }
+
+    #[test]
+    fn upgrade_from_legacy_layout() {
+        let (mesh_mint, index_mesh_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let legacy_pools = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let data = legacy_global_state_data(mesh_mint, index_mesh_mint);
+        assert_eq!(data.len(), LegacyGlobalState::LEN);
+
+        let mut global_state: Box<GlobalState> = Box::new(zeroed(GlobalState::LEN));
+        let mut mesh_pool: StakePool = zeroed(StakePool::LEN);
+        let mut index_mesh_pool: StakePool = zeroed(StakePool::LEN);
+
+        LegacyGlobalState::try_from_data(&data)
+            .unwrap()
+            .migrate(&mut global_state, &mut mesh_pool, &mut index_mesh_pool, legacy_pools)
+            .unwrap();
+
+        assert_eq!(global_state.layout_version, LAYOUT_VERSION);
+        assert_eq!(global_state.legacy_pools, legacy_pools);
+        assert_eq!(global_state.global_acc_reward, MESH_ACC_REWARD);
+        assert_eq!(global_state.period_end_time, 150);
+        assert!(global_state.is_reward_funded());
+        // 300 $MESH plus 400 $indexMESH at 0.5 weightage.
+        assert_eq!(global_state.total_weighted_stake, 500);
+        assert_eq!(mesh_pool.mint, mesh_mint);
+        assert_eq!(index_mesh_pool.weightage, WEIGHTAGE);
+        assert_eq!(index_mesh_pool.total_weighted, 400);
+
+        // Both legacy positions keep the rewards pending at the upgrade.
+        let data = legacy_user_info_data(Pubkey::new_unique());
+        let mut legacy_user_info = LegacyUserInfo::try_from_data(&data).unwrap();
+
+        let mut user_mesh: UserInfo = zeroed(UserInfo::LEN);
+        let (staked, acc_reward) = legacy_user_info.take_position(0);
+        merge_legacy_position(staked, acc_reward, &mut mesh_pool, &mut user_mesh).unwrap();
+
+        assert!(!legacy_user_info.is_empty());
+
+        let mut user_index_mesh: UserInfo = zeroed(UserInfo::LEN);
+        let (staked, acc_reward) = legacy_user_info.take_position(1);
+        merge_legacy_position(staked, acc_reward, &mut index_mesh_pool, &mut user_index_mesh).unwrap();
+
+        assert!(legacy_user_info.is_empty());
+        assert_eq!((user_mesh.staked, user_mesh.weighted, user_mesh.unclaimed_owed), (100, 100, 200));
+        assert_eq!((user_index_mesh.staked, user_index_mesh.unclaimed_owed), (200, 200));
+
+        // Rewards accumulated after the upgrade follow the legacy weightage.
+        user_mesh.acc_reward = mesh_pool.acc_reward;
+        user_index_mesh.acc_reward = index_mesh_pool.acc_reward;
+        global_state.global_acc_reward += 2 * SCALE_FACTOR;
+        mesh_pool.update_reward_per_share(&global_state).unwrap();
+        index_mesh_pool.update_reward_per_share(&global_state).unwrap();
+
+        assert_eq!(user_mesh.calculate_pending_rewards(mesh_pool.acc_reward).unwrap(), 200);
+        assert_eq!(user_index_mesh.calculate_pending_rewards(index_mesh_pool.acc_reward).unwrap(), 200);
+    }
+
+    #[test]
+    fn reject_accounts_not_in_legacy_layout() {
+        let mut data = legacy_global_state_data(Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert!(LegacyUserInfo::try_from_data(&data).is_err());
+
+        // A migrated global state is longer than the legacy layout.
+        data.resize(GlobalState::LEN, 0);
+        let err: Error = ErrorCode::InvalidLegacyAccount.into();
+        assert_eq!(LegacyGlobalState::try_from_data(&data).err(), Some(err));
+    }
+}
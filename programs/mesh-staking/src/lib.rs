@@ -3,8 +3,12 @@ use anchor_lang::prelude::*;
 pub mod instructions;
 pub mod state;
 pub mod errors;
+pub mod events;
+pub mod legacy;
+pub mod math;
 
 use crate::instructions::*;
+use crate::state::{ CampaignConfig, GovernanceAction, LockTier, Role, StakeCheckpoint, UnstakeFee };
 
 declare_id!("HcgqAqH5MwpTACy2PXA91ecu98hr1Ewt7YAf5mHeT7zY");
 
@@ -12,15 +16,128 @@ declare_id!("HcgqAqH5MwpTACy2PXA91ecu98hr1Ewt7YAf5mHeT7zY");
 pub mod mesh_staking {
     use super::*;
 
-    /// Claims all pending rewards accumulated on $MESH & $indexMESH.
+    /// Accepts the proposed governance, making the signer the new governance.
+    /// Only pending governance instruction.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The accounts required by the instruction.
-    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+    pub fn accept_governance(ctx: Context<AcceptGovernance>) -> Result<()> {
+        accept_governance_handler(ctx)
+    }
+
+    /// Opens a new reward stream distributing the given SPL token alongside the SOL rewards,
+    /// creating the vault PDA of the stream that holds the reward tokens. Only governance instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn add_reward_stream(ctx: Context<AddRewardStream>) -> Result<()> {
+        add_reward_stream_handler(ctx)
+    }
+
+    /// Registers a new stakeable token by creating its stake pool PDA.
+    /// Only governance instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `weightage` - Weight of the token used for its reward calculation.
+    pub fn add_stake_pool(ctx: Context<AddStakePool>, weightage: u64) -> Result<()> {
+        add_stake_pool_handler(ctx, weightage)
+    }
+
+    /// Readonly instruction for the staked and weighted balances of a user in a stake pool at a past time,
+    /// which can't be older than the current snapshot epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `user` - The user for which the balances are fetched.
+    /// * `timestamp` - The Unix timestamp of the balances.
+    pub fn balance_at(ctx: Context<BalanceAt>, user: Pubkey, timestamp: u64) -> Result<StakeCheckpoint> {
+        balance_at_handler(ctx, user, timestamp)
+    }
+
+    /// Cancels a queued governance action before it is executed.
+    /// Only governance instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn cancel_action(ctx: Context<CancelAction>) -> Result<()> {
+        cancel_action_handler(ctx)
+    }
+
+    /// Cancels the proposed governance before it is accepted.
+    /// Only governance instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn cancel_governance_proposal(ctx: Context<CancelGovernanceProposal>) -> Result<()> {
+        cancel_governance_proposal_handler(ctx)
+    }
+
+    /// Cancels a queued SOL reward cycle and releases its committed rewards.
+    /// Only reward manager instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `start_time` - The start time identifying the queued cycle.
+    pub fn cancel_reward_cycle(ctx: Context<CancelRewardCycle>, start_time: u64) -> Result<()> {
+        cancel_reward_cycle_handler(ctx, start_time)
+    }
+
+    /// Cancels a withdrawal ticket and stakes its amount again, along with pending reward calculation and distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        cancel_withdrawal_handler(ctx)
+    }
+
+    /// Claims all pending rewards accumulated on the stake pools the user participates in.
+    /// Pairs of stake pool and userInfo PDAs are passed as remaining accounts, followed by pairs of
+    /// reward vault and user token account for each reward stream to be claimed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn claim<'info>(ctx: Context<'_, '_, 'info, 'info, Claim<'info>>) -> Result<()> {
         claim_handler(ctx)
     }
 
+    /// Claims the leaf of the user from a merkle distribution, paying SOL from the fundsController or tokens
+    /// from the distribution vault. Each leaf can only be claimed once, until the distribution expires.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `index` - The index of the leaf in the distribution.
+    /// * `amount` - The amount of SOL or tokens of the leaf.
+    /// * `proof` - The sibling nodes from the leaf up to the merkle root.
+    pub fn claim_distribution(
+        ctx: Context<ClaimDistribution>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>
+    ) -> Result<()> {
+        claim_distribution_handler(ctx, index, amount, proof)
+    }
+
+    /// Closes an ended reward campaign and frees its slot. The stranded rewards of a sponsored campaign are
+    /// refunded to its sponsor, otherwise they are added to the stranded rewards of the cycle.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn close_reward_campaign(ctx: Context<CloseRewardCampaign>) -> Result<()> {
+        close_reward_campaign_handler(ctx)
+    }
+
     /// Closes the UserInfo PDA in order to get the rent SOL back from the user.
     /// Not closing by default in order to maintain user claimed history for analytics.
     ///
@@ -31,82 +148,404 @@ pub mod mesh_staking {
         close_user_info_handler(ctx)
     }
 
-    /// Initializes the program by creating PDAs for global state & fund controller along with storing mint keys.
-    /// Only deployer instruction.
+    /// Publishes a merkle distribution of SOL or tokens, where each leaf is the keccak hash of the leaf index,
+    /// the user and the amount. Tokens are deposited by governance, SOLs have to be funded with `fund_distribution`.
+    /// Only governance instruction.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The accounts required by the instruction.
-    /// * `governance` - Pubkey responsible for executing governance-only instructions.
-    /// * `mesh_mint` - Pubkey of the $MESH SPL token.
-    /// * `index_mesh_mint` - Pubkey of the $indexMESH SPL token.
-    /// * `weightage` - Weight of $indexMESH used for its reward calculation.
-    pub fn initialize(
-        ctx: Context<Initialize>,
-        governance: Pubkey,
-        mesh_mint: Pubkey,
-        index_mesh_mint: Pubkey,
-        weightage: u64
+    /// * `merkle_root` - The merkle root of the leaves, with the pairs of nodes sorted before hashing.
+    /// * `num_leaves` - The number of leaves, not greater than `MAX_DISTRIBUTION_LEAVES`.
+    /// * `total_amount` - The amount of SOL or tokens distributed by all the leaves.
+    /// * `expiry_time` - The Unix timestamp after which the distribution can not be claimed anymore.
+    pub fn create_distribution(
+        ctx: Context<CreateDistribution>,
+        merkle_root: [u8; 32],
+        num_leaves: u64,
+        total_amount: u64,
+        expiry_time: u64
+    ) -> Result<()> {
+        create_distribution_handler(ctx, merkle_root, num_leaves, total_amount, expiry_time)
+    }
+
+    /// Funds a SOL merkle distribution by committing its amount from the funded SOLs.
+    /// Only governance instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn fund_distribution(ctx: Context<FundDistribution>) -> Result<()> {
+        fund_distribution_handler(ctx)
+    }
+
+    /// Transfers reward SOLs to the fundsController and records them as funded,
+    /// so the reward schedule can be committed against them.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `amount` - The amount of SOL to be funded.
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        fund_rewards_handler(ctx, amount)
+    }
+
+    /// Transfers reward tokens to the vault of a reward stream and records them as funded,
+    /// so the stream rewards can be committed against them.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `stream_index` - Index of the reward stream slot.
+    /// * `amount` - The amount of tokens to be funded.
+    pub fn fund_stream_rewards(
+        ctx: Context<FundStreamRewards>,
+        stream_index: u8,
+        amount: u64
     ) -> Result<()> {
-        initialize_handler(ctx, governance, mesh_mint, index_mesh_mint, weightage)
+        fund_stream_rewards_handler(ctx, stream_index, amount)
     }
 
-    /// Migrates reward SOLs to the new version of the staking program.
+    /// Transfers the unstake fees accrued by a stake pool from the vault to the treasury token account.
+    /// Anyone can execute the instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn collect_unstake_fees(ctx: Context<CollectUnstakeFees>) -> Result<()> {
+        collect_unstake_fees_handler(ctx)
+    }
+
+    /// Edits a queued SOL reward cycle. Only reward manager instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `start_time` - The start time identifying the queued cycle.
+    /// * `reward` - The new amount of SOL to be distributed in the cycle.
+    /// * `distribution_time` - The new duration of the cycle.
+    /// * `new_start_time` - The new start time of the cycle.
+    pub fn edit_reward_cycle(
+        ctx: Context<EditRewardCycle>,
+        start_time: u64,
+        reward: u64,
+        distribution_time: u64,
+        new_start_time: u64
+    ) -> Result<()> {
+        edit_reward_cycle_handler(ctx, start_time, reward, distribution_time, new_start_time)
+    }
+
+    /// Returns all staked tokens of the user from the vault without updating the reward state. The pending
+    /// SOL rewards, stream rewards and unstake fees up to the stored pool state are kept as owed to the user.
+    /// Only available in safe mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+        emergency_withdraw_handler(ctx)
+    }
+
+    /// Executes a queued governance action once its timelock delay has passed.
+    /// Anyone can execute the instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn execute_action(ctx: Context<ExecuteAction>) -> Result<()> {
+        execute_action_handler(ctx)
+    }
+
+    /// Grants a role to the given pubkey, replacing its previous holder.
     /// Only governance instruction.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The accounts required by the instruction.
+    /// * `role` - The role to be granted, the governance role can not be granted.
+    /// * `account` - Pubkey that will hold the role.
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, account: Pubkey) -> Result<()> {
+        grant_role_handler(ctx, role, account)
+    }
+
+    /// Initializes the program by creating PDAs for global state & fund controller.
+    /// Only deployer instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `governance` - Pubkey responsible for executing governance-only instructions.
+    pub fn initialize(ctx: Context<Initialize>, governance: Pubkey) -> Result<()> {
+        initialize_handler(ctx, governance)
+    }
+
+    /// Migrates reward SOLs not committed to rewards to the new version of the staking program.
+    /// Only treasurer instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
     /// * `amount` - The amount of SOL to be migrated.
     pub fn migrate_funds(ctx: Context<MigrateFunds>, amount: u64) -> Result<()> {
         migrate_funds_handler(ctx, amount)
     }
 
+    /// Converts the legacy global state, which held the $MESH and $indexMESH stake, into the current layout
+    /// and creates the reward schedule and the stake pools of both tokens.
+    /// Only governance instruction of the legacy layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn migrate_global_state(ctx: Context<MigrateGlobalState>) -> Result<()> {
+        migrate_global_state_handler(ctx)
+    }
+
+    /// Merges the legacy stake of the user in the given stake pool into their userInfo of the pool.
+    /// The legacy userInfo is closed once both of its stakes are migrated.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn migrate_user_info(ctx: Context<MigrateUserInfo>) -> Result<()> {
+        migrate_user_info_handler(ctx)
+    }
+
+    /// Opens a SOL reward campaign that runs alongside the reward cycle with its own rate and window.
+    /// Only reward manager instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `reward` - The amount of SOL to be distributed over the campaign window.
+    /// * `start_time` - The Unix timestamp when the campaign starts, at most 30 days from now.
+    /// * `end_time` - The Unix timestamp when the campaign ends.
+    pub fn open_reward_campaign(
+        ctx: Context<OpenRewardCampaign>,
+        reward: u64,
+        start_time: u64,
+        end_time: u64
+    ) -> Result<()> {
+        open_reward_campaign_handler(ctx, reward, start_time, end_time)
+    }
+
+    /// Opens a new snapshot epoch, from which the stake balance changes of the users are checkpointed.
+    /// Only governance instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn open_snapshot_epoch(ctx: Context<OpenSnapshotEpoch>) -> Result<()> {
+        open_snapshot_epoch_handler(ctx)
+    }
+
     /// Readonly instruction for calculating pending rewards of a specific user.
-    /// The PDAs are not mutable in this instruction.
+    /// The PDAs are not mutable in this instruction. Pairs of stake pool and userInfo PDAs are passed as remaining accounts.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The accounts required by the instruction.
     /// * `user` - The user for which pending rewards are fetched.
-    pub fn pending_rewards(ctx: Context<PendingRewards>, user: Pubkey) -> Result<RewardStruct> {
+    pub fn pending_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PendingRewards<'info>>,
+        user: Pubkey
+    ) -> Result<RewardStruct> {
         pending_rewards_handler(ctx, user)
     }
 
-    /// Stakes user $MESH or $indexMESH tokens, along with pending reward calculation and distribution.
+    /// Readonly instruction for listing withdrawal tickets of a specific user.
+    /// The tickets are passed as remaining accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `user` - The user for which withdrawal tickets are fetched.
+    pub fn pending_withdrawals<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PendingWithdrawals<'info>>,
+        user: Pubkey
+    ) -> Result<Vec<WithdrawalStruct>> {
+        pending_withdrawals_handler(ctx, user)
+    }
+
+    /// Proposes a new governance pubkey, which becomes the governance once it accepts.
+    /// Only governance instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `new_governance` - Pubkey of the new governance account.
+    pub fn propose_governance(ctx: Context<ProposeGovernance>, new_governance: Pubkey) -> Result<()> {
+        propose_governance_handler(ctx, new_governance)
+    }
+
+    /// Queues a governance parameter change that can be executed once the timelock delay has passed.
+    /// Only instruction of the role holder of the action.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `action` - The parameter change to be applied on execution.
+    pub fn queue_action(ctx: Context<QueueAction>, action: GovernanceAction) -> Result<()> {
+        queue_action_handler(ctx, action)
+    }
+
+    /// Releases the expired lock of a user back to the liquid multiplier, so the boosted stake stops diluting
+    /// the other stakers. The pending SOL rewards are owed to the user and paid on the next claim.
+    /// Anyone can call it once the lock has ended.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn release_lock(ctx: Context<ReleaseLock>) -> Result<()> {
+        release_lock_handler(ctx)
+    }
+
+    /// Revokes a role, leaving it without a holder until it is granted again.
+    /// Only governance instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `role` - The role to be revoked, the governance role can not be revoked.
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: Role) -> Result<()> {
+        revoke_role_handler(ctx, role)
+    }
+
+    /// Queues a future SOL reward cycle that starts automatically at its start time.
+    /// The start time can not be taken by another queued cycle. Only reward manager instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `reward` - The amount of SOL to be distributed in the cycle.
+    /// * `distribution_time` - The duration of the cycle.
+    /// * `start_time` - The Unix timestamp when the cycle starts.
+    pub fn schedule_reward_cycle(
+        ctx: Context<ScheduleRewardCycle>,
+        reward: u64,
+        distribution_time: u64,
+        start_time: u64
+    ) -> Result<()> {
+        schedule_reward_cycle_handler(ctx, reward, distribution_time, start_time)
+    }
+
+    /// Opens a reward campaign funded by the signer, who deposits SOL or an allowed reward stream token
+    /// within the bounds of the campaign config.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `reward` - The amount of SOL or tokens deposited and distributed over the campaign window.
+    /// * `start_time` - The Unix timestamp when the campaign starts, at most 30 days from now.
+    /// * `end_time` - The Unix timestamp when the campaign ends.
+    pub fn sponsor_reward_campaign(
+        ctx: Context<SponsorRewardCampaign>,
+        reward: u64,
+        start_time: u64,
+        end_time: u64
+    ) -> Result<()> {
+        sponsor_reward_campaign_handler(ctx, reward, start_time, end_time)
+    }
+
+    /// Stakes user tokens into a stake pool, along with pending reward calculation and distribution.
+    /// The chosen lock tier applies to the whole user stake and can only extend an existing lock.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The accounts required by the instruction.
     /// * `amount` - The amount of tokens the user wants to stake.
-    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
-        stake_handler(ctx, amount)
+    /// * `lock_tier` - Index of the lock tier whose duration and multiplier apply to the stake.
+    pub fn stake(ctx: Context<Stake>, amount: u64, lock_tier: u8) -> Result<()> {
+        stake_handler(ctx, amount, lock_tier)
     }
 
-    /// Unstakes user $MESH or $indexMESH tokens, along with pending reward calculation and distribution.
+    /// Closes an expired merkle distribution. Unclaimed SOLs are released for future rewards and unclaimed tokens
+    /// are sent to governance. Only governance instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn sweep_distribution(ctx: Context<SweepDistribution>) -> Result<()> {
+        sweep_distribution_handler(ctx)
+    }
+
+    /// Unstakes user tokens from a stake pool, along with pending reward calculation and distribution.
+    /// The unstaked tokens stop accruing rewards and are moved into a withdrawal ticket until the unbonding cooldown has passed.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The accounts required by the instruction.
     /// * `amount` - The amount of tokens the user wants to unstake.
-    /// * `is_emergency` - A boolean flag indicating whether to disable fees and only unstake in case of emergency.
+    /// * `is_emergency` - A boolean flag indicating whether to only unstake in case of emergency, moving the pending
+    ///   SOL rewards into the owed balance of the user instead of paying them. Emergency unstake is not paused,
+    ///   but still fails while the stake is locked.
     pub fn unstake(ctx: Context<Unstake>, amount: u64, is_emergency: bool) -> Result<()> {
         unstake_handler(ctx, amount, is_emergency)
     }
 
-    /// Updates the governance pubkey.
+    /// Readonly instruction for the unstake fee schedule, along with the current fee of a user if their userInfo PDA is given.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn unstake_fee_schedule(ctx: Context<UnstakeFeeSchedule>) -> Result<UnstakeFeeStruct> {
+        unstake_fee_schedule_handler(ctx)
+    }
+
+    /// Updates the bounds of the reward campaigns opened by sponsors, including the allowed reward tokens.
     /// Only governance instruction.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The accounts required by the instruction.
-    /// * `new_governance` - Pubkey of the new governance account.
-    pub fn update_governance(ctx: Context<UpdateGovernance>, new_governance: Pubkey) -> Result<()> {
-        update_governance_handler(ctx, new_governance)
+    /// * `campaign_config` - Minimum SOL reward, duration bounds, and allowed reward tokens with their minimum reward.
+    pub fn update_campaign_config(
+        ctx: Context<UpdateCampaignConfig>,
+        campaign_config: CampaignConfig
+    ) -> Result<()> {
+        update_campaign_config_handler(ctx, campaign_config)
+    }
+
+    /// Updates the lock tiers that users can choose while staking. Tier 0 must remain the liquid tier.
+    /// Only governance instruction, which has to be queued once the timelock is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `lock_tiers` - Lock durations and their reward multipliers, a zero multiplier disables the tier.
+    pub fn update_lock_tiers(ctx: Context<UpdateLockTiers>, lock_tiers: Vec<LockTier>) -> Result<()> {
+        update_lock_tiers_handler(ctx, lock_tiers)
+    }
+
+    /// Writes the max voter weight of an SPL Governance realm from the stake of every pool, scaled by the
+    /// weightage of each pool. Every stake pool PDA is passed as a remaining account.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `realm` - The realm using the program as its max voter weight add-in.
+    /// * `governing_token_mint` - The governing token mint of the realm.
+    pub fn update_max_voter_weight_record<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateMaxVoterWeightRecord<'info>>,
+        realm: Pubkey,
+        governing_token_mint: Pubkey
+    ) -> Result<()> {
+        update_max_voter_weight_record_handler(ctx, realm, governing_token_mint)
+    }
+
+    /// Pauses and unpauses the stake, unstake, claim and reward update instructions independently.
+    /// Emergency unstake and withdraw are never paused. Only pauser instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `pause_flags` - Bitmask of the paused instructions, see the `PAUSE_*` flags in `state.rs`.
+    pub fn update_pause_flags(ctx: Context<UpdatePauseFlags>, pause_flags: u8) -> Result<()> {
+        update_pause_flags_handler(ctx, pause_flags)
     }
 
     /// Updates the reward period end state. Useful if there's a need to distribute the same reward for the next cycle.
-    /// Only governance instruction.
+    /// Only reward manager instruction.
     ///
     /// # Arguments
     ///
@@ -117,29 +556,135 @@ pub mod mesh_staking {
     }
 
     /// Updates distribution rewards. Used to change rewards after the rewards cycle ending or within the same cycle.
-    /// Only governance instruction.
+    /// Only reward manager instruction.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The accounts required by the instruction.
     /// * `reward` - The amount of SOL to be distributed.
     /// * `distribution_time` - The duration up to which the reward will be distributed.
+    /// * `roll_over_stranded` - Whether to add the rewards stranded while nothing was staked to the cycle.
     pub fn update_rewards(
         ctx: Context<UpdateRewards>,
         reward: u64,
+        distribution_time: u64,
+        roll_over_stranded: bool
+    ) -> Result<()> {
+        update_rewards_handler(ctx, reward, distribution_time, roll_over_stranded)
+    }
+
+    /// Turns the safe mode on or off. In safe mode the reward state is frozen, every pausable instruction
+    /// and unstake are disabled, and users exit through emergency withdraw. Only governance instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `safe_mode` - Whether the safe mode is active.
+    pub fn update_safe_mode(ctx: Context<UpdateSafeMode>, safe_mode: bool) -> Result<()> {
+        update_safe_mode_handler(ctx, safe_mode)
+    }
+
+    /// Updates distribution rewards of a reward stream, the same way as the SOL rewards. The committed rewards
+    /// can't exceed the tokens funded into the stream vault. Only reward manager instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `stream_index` - Index of the reward stream slot.
+    /// * `reward` - The amount of reward tokens to be distributed.
+    /// * `distribution_time` - The duration up to which the reward will be distributed.
+    pub fn update_stream_rewards(
+        ctx: Context<UpdateStreamRewards>,
+        stream_index: u8,
+        reward: u64,
         distribution_time: u64
     ) -> Result<()> {
-        update_rewards_handler(ctx, reward, distribution_time)
+        update_stream_rewards_handler(ctx, stream_index, reward, distribution_time)
     }
 
-    /// Updates the weightage of $indexMESH for reward calculation.
+    /// Enables the timelock by setting the delay between queueing and executing governance actions.
+    /// Once enabled, the delay can only be changed through a queued action. Only governance instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `timelock_delay` - The new timelock delay in seconds, not lower than `MIN_TIMELOCK_DELAY`.
+    pub fn update_timelock_delay(
+        ctx: Context<UpdateTimelockDelay>,
+        timelock_delay: u64
+    ) -> Result<()> {
+        update_timelock_delay_handler(ctx, timelock_delay)
+    }
+
+    /// Updates the duration that unstaked tokens have to wait before they can be withdrawn.
+    /// Only governance instruction, which has to be queued once the timelock is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `unbonding_cooldown` - The new unbonding cooldown in seconds, not higher than `MAX_UNBONDING_COOLDOWN`.
+    pub fn update_unbonding_cooldown(
+        ctx: Context<UpdateUnbondingCooldown>,
+        unbonding_cooldown: u64
+    ) -> Result<()> {
+        update_unbonding_cooldown_handler(ctx, unbonding_cooldown)
+    }
+
+    /// Updates the early-exit fee deducted from unstaked tokens.
+    /// Only governance instruction, which has to be queued once the timelock is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `unstake_fee` - Fee in basis points, its decay duration, and whether it is redistributed or sent to the treasury.
+    pub fn update_unstake_fee(ctx: Context<UpdateUnstakeFee>, unstake_fee: UnstakeFee) -> Result<()> {
+        update_unstake_fee_handler(ctx, unstake_fee)
+    }
+
+    /// Writes the voter weight of the signer in an SPL Governance realm from their stake at the start of the
+    /// current snapshot epoch, scaled by the weightage of each pool. Pairs of stake pool and userInfo PDAs are
+    /// passed as remaining accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    /// * `realm` - The realm using the program as its voter weight add-in.
+    /// * `governing_token_mint` - The governing token mint of the realm.
+    pub fn update_voter_weight_record<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateVoterWeightRecord<'info>>,
+        realm: Pubkey,
+        governing_token_mint: Pubkey
+    ) -> Result<()> {
+        update_voter_weight_record_handler(ctx, realm, governing_token_mint)
+    }
+
+    /// Updates the weightage of a stake pool token for reward calculation.
     /// Only governance instruction.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The accounts required by the instruction.
-    /// * `weightage` - The new weightage of $indexMESH for reward calculation.
+    /// * `weightage` - The new weightage of the stake pool token for reward calculation.
     pub fn update_weightage(ctx: Context<UpdateWeightage>, weightage: u64) -> Result<()> {
         update_weightage_handler(ctx, weightage)
     }
+
+    /// Withdraws the tokens of a withdrawal ticket once the unbonding cooldown has passed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+        withdraw_handler(ctx)
+    }
+
+    /// Withdraws the SOL rewards emitted while nothing was staked to the treasury.
+    /// Only treasurer instruction, which has to be queued once the timelock is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The accounts required by the instruction.
+    pub fn withdraw_stranded_reward(ctx: Context<WithdrawStrandedReward>) -> Result<()> {
+        withdraw_stranded_reward_handler(ctx)
+    }
 }
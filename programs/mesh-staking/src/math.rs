@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Checked arithmetic that surfaces failures as typed errors instead of panicking.
+pub trait SafeMath: Sized {
+    fn safe_add(self, _rhs: Self) -> Result<Self>;
+    fn safe_sub(self, _rhs: Self) -> Result<Self>;
+    fn safe_mul(self, _rhs: Self) -> Result<Self>;
+    fn safe_div(self, _rhs: Self) -> Result<Self>;
+}
+
+macro_rules! impl_safe_math {
+    ($($t:ty),*) => {
+        $(
+            impl SafeMath for $t {
+                fn safe_add(self, _rhs: Self) -> Result<Self> {
+                    self.checked_add(_rhs).ok_or(ErrorCode::MathOverflow.into())
+                }
+
+                fn safe_sub(self, _rhs: Self) -> Result<Self> {
+                    self.checked_sub(_rhs).ok_or(ErrorCode::MathOverflow.into())
+                }
+
+                fn safe_mul(self, _rhs: Self) -> Result<Self> {
+                    self.checked_mul(_rhs).ok_or(ErrorCode::MathOverflow.into())
+                }
+
+                fn safe_div(self, _rhs: Self) -> Result<Self> {
+                    self.checked_div(_rhs).ok_or(ErrorCode::DivisionByZero.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_math!(u64, u128);
+
+/// Checked conversion between integer types.
+pub trait SafeCast: Sized {
+    fn safe_cast<T: TryFrom<Self>>(self) -> Result<T> {
+        T::try_from(self).map_err(|_| ErrorCode::ConversionFailed.into())
+    }
+}
+
+impl SafeCast for usize {}
+impl SafeCast for u64 {}
+impl SafeCast for u128 {}
+
+/// Get the seconds elapsed between two timestamps, failing if the end is earlier than the start.
+pub fn time_elapsed(_start_time: u64, _end_time: u64) -> Result<u64> {
+    _end_time.checked_sub(_start_time).ok_or(ErrorCode::NegativeTime.into())
+}
+
+/// Calculate `_a * _b / _denominator` with a full 256-bit intermediate product, rounding down.
+/// It only fails if the denominator is zero or the quotient itself does not fit in u128.
+pub fn mul_div(_a: u128, _b: u128, _denominator: u128) -> Result<u128> {
+    mul_div_rem(_a, _b, _denominator).map(|(quotient, _)| quotient)
+}
+
+/// Calculate `(_a * _b + _carry) / _denominator` along with its remainder, so the truncated fraction can be
+/// carried into the next calculation. The carry is the remainder of the previous calculation.
+pub fn mul_div_carry(_a: u128, _b: u128, _denominator: u128, _carry: u128) -> Result<(u128, u128)> {
+    let (quotient, remainder) = mul_div_rem(_a, _b, _denominator)?;
+    let remainder = remainder.safe_add(_carry)?;
+
+    Ok((quotient.safe_add(remainder / _denominator)?, remainder % _denominator))
+}
+
+/// Calculate the rewards of a cycle emitted over the given duration, `_reward * _duration / _distribution_time`
+/// with a u128 intermediate product, rounding down.
+pub fn reward_for_duration(_reward: u64, _duration: u64, _distribution_time: u64) -> Result<u64> {
+    mul_div(u128::from(_reward), u128::from(_duration), u128::from(_distribution_time))?.safe_cast()
+}
+
+/// Calculate the quotient and the remainder of `_a * _b / _denominator` with a full 256-bit intermediate product.
+fn mul_div_rem(_a: u128, _b: u128, _denominator: u128) -> Result<(u128, u128)> {
+    if _denominator == 0 {
+        return Err(ErrorCode::DivisionByZero.into());
+    }
+
+    if let Some(product) = _a.checked_mul(_b) {
+        return Ok((product / _denominator, product % _denominator));
+    }
+
+    let (high, low) = full_mul(_a, _b);
+
+    if high >= _denominator {
+        return Err(ErrorCode::MathOverflow.into());
+    }
+
+    // Restoring long division of the 256-bit product, one bit of the low half at a time.
+    let mut remainder = high;
+    let mut quotient: u128 = 0;
+
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> bit) & 1);
+        quotient <<= 1;
+
+        if carry == 1 || remainder >= _denominator {
+            remainder = remainder.wrapping_sub(_denominator);
+            quotient |= 1;
+        }
+    }
+
+    Ok((quotient, remainder))
+}
+
+/// Multiply two u128 values into the high and low halves of the 256-bit product.
+fn full_mul(_a: u128, _b: u128) -> (u128, u128) {
+    let mask = u128::from(u64::MAX);
+    let (a_high, a_low) = (_a >> 64, _a & mask);
+    let (b_high, b_low) = (_b >> 64, _b & mask);
+
+    let low_low = a_low * b_low;
+    let high_low = a_high * b_low;
+    let low_high = a_low * b_high;
+    let high_high = a_high * b_high;
+
+    let cross = (low_low >> 64) + (high_low & mask) + (low_high & mask);
+
+    let low = (cross << 64) | (low_low & mask);
+    let high = high_high + (high_low >> 64) + (low_high >> 64) + (cross >> 64);
+
+    (high, low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check `quotient * denominator + remainder == a * b` on the 256-bit values.
+    fn assert_mul_div_rem(_a: u128, _b: u128, _denominator: u128) {
+        let (quotient, remainder) = mul_div_rem(_a, _b, _denominator).unwrap();
+        assert!(remainder < _denominator);
+
+        let (high, low) = full_mul(quotient, _denominator);
+        let (low, overflow) = low.overflowing_add(remainder);
+        let high = high + u128::from(overflow);
+
+        assert_eq!((high, low), full_mul(_a, _b));
+    }
+
+    #[test]
+    fn fast_path_matches_full_width_path() {
+        assert_eq!(mul_div_rem(10, 20, 7).unwrap(), (28, 4));
+
+        // u128::MAX * 1 fits in u128, u128::MAX * 2 does not.
+        assert_eq!(mul_div_rem(u128::MAX, 1, 3).unwrap(), (u128::MAX / 3, 0));
+        assert_eq!(mul_div_rem(u128::MAX, 2, 6).unwrap(), (u128::MAX / 3, 0));
+        assert_eq!(mul_div_rem(u128::MAX - 1, 1, 7).unwrap(), ((u128::MAX - 1) / 7, (u128::MAX - 1) % 7));
+        assert_mul_div_rem(u128::MAX - 1, 2, 14);
+    }
+
+    #[test]
+    fn product_with_high_half_set() {
+        assert_eq!(full_mul(1 << 127, 4), (2, 0));
+        assert_eq!(mul_div(1 << 127, 4, 8).unwrap(), 1 << 126);
+        assert_eq!(mul_div(1 << 100, 1 << 60, 1 << 40).unwrap(), 1 << 120);
+        assert_mul_div_rem(u128::MAX / 3, 1 << 70, (1 << 90) + 7);
+    }
+
+    #[test]
+    fn high_half_one_below_denominator() {
+        // u128::MAX * u128::MAX has the high half u128::MAX - 1.
+        assert_eq!(full_mul(u128::MAX, u128::MAX), (u128::MAX - 1, 1));
+        assert_eq!(mul_div_rem(u128::MAX, u128::MAX, u128::MAX).unwrap(), (u128::MAX, 0));
+
+        let denominator = (1 << 64) + 1;
+        let (high, _) = full_mul(1 << 64, u128::MAX);
+        assert_eq!(high, denominator - 2);
+        assert_mul_div_rem(1 << 64, u128::MAX, denominator - 1);
+        assert_mul_div_rem((1 << 64) + 1, u128::MAX, denominator);
+    }
+
+    #[test]
+    fn quotient_not_fitting_in_u128_fails() {
+        let err: Error = ErrorCode::MathOverflow.into();
+
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX - 1).unwrap_err(), err);
+        assert_eq!(mul_div(u128::MAX, 2, 1).unwrap_err(), err);
+    }
+
+    #[test]
+    fn remainder_carry_at_bit_127() {
+        // Denominators above 2^127 shift a remainder with bit 127 set during the long division.
+        let denominator = (1 << 127) + 1;
+
+        assert_mul_div_rem(u128::MAX, 1 << 127, denominator);
+        assert_mul_div_rem(u128::MAX - 2, (1 << 127) - 5, denominator);
+        assert_mul_div_rem(u128::MAX - 5, u128::MAX - 20, u128::MAX - 9);
+        assert_eq!(mul_div_rem(u128::MAX, 1 << 127, u128::MAX).unwrap(), (1 << 127, 0));
+    }
+
+    #[test]
+    fn max_operands() {
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX).unwrap(), u128::MAX);
+        assert_eq!(mul_div(u128::MAX, 1, u128::MAX).unwrap(), 1);
+        assert_eq!(mul_div(u128::MAX, u128::MAX - 1, u128::MAX).unwrap(), u128::MAX - 1);
+        assert_eq!(mul_div(u128::MAX, 0, 1).unwrap(), 0);
+
+        let err: Error = ErrorCode::DivisionByZero.into();
+        assert_eq!(mul_div(u128::MAX, u128::MAX, 0).unwrap_err(), err);
+    }
+
+    #[test]
+    fn reward_for_duration_beyond_u64_product() {
+        // 10k SOL over 30 days, the product of the reward and the duration doesn't fit in u64.
+        let reward: u64 = 10_000 * 1_000_000_000;
+        let distribution_time: u64 = 30 * 24 * 60 * 60;
+
+        assert!(reward.checked_mul(distribution_time).is_none());
+        assert_eq!(reward_for_duration(reward, distribution_time, distribution_time).unwrap(), reward);
+        assert_eq!(reward_for_duration(reward, distribution_time / 2, distribution_time).unwrap(), reward / 2);
+        assert_eq!(reward_for_duration(reward, 1, distribution_time).unwrap(), reward / distribution_time);
+        assert_eq!(reward_for_duration(u64::MAX, distribution_time, distribution_time).unwrap(), u64::MAX);
+
+        let err: Error = ErrorCode::ConversionFailed.into();
+        assert_eq!(reward_for_duration(u64::MAX, 2, 1).unwrap_err(), err);
+    }
+
+    #[test]
+    fn carry_crossing_denominator() {
+        // 3 / 4 leaves 3, with a carry of 2 the remainder crosses the denominator once.
+        assert_eq!(mul_div_carry(1, 3, 4, 2).unwrap(), (1, 1));
+        assert_eq!(mul_div_carry(5, 1, 4, 3).unwrap(), (2, 0));
+        assert_eq!(mul_div_carry(5, 1, 4, 2).unwrap(), (1, 3));
+
+        // The same crossing on the full-width path.
+        let (quotient, remainder) = mul_div_rem(u128::MAX, 3, u128::MAX - 1).unwrap();
+        assert_eq!((quotient, remainder), (3, 3));
+
+        let carry = u128::MAX - 1 - remainder;
+        assert_eq!(
+            mul_div_carry(u128::MAX, 3, u128::MAX - 1, carry).unwrap(),
+            (quotient + 1, 0)
+        );
+    }
+}
@@ -1,47 +1,500 @@
 use std::primitive;
 
 use anchor_lang::prelude::*;
-use solana_program::{ pubkey, pubkey::Pubkey };
+use solana_program::{ keccak, pubkey, pubkey::Pubkey };
 
+use crate::errors::ErrorCode;
+use crate::math::{ mul_div, mul_div_carry, time_elapsed, SafeCast, SafeMath };
+
+/// Fixed-point base of the pool weightage, e.g. a weightage of 1e9 gives the pool the full global reward.
 pub const SACLE_FACTOR_BASE: u128 = 1_000_000_000;
+/// Fixed-point scale of the reward accumulators.
+///
+/// The accumulators stay u128 and every product goes through `mul_div`, which keeps the full 256-bit
+/// intermediate, so the math is bounded by the stored values only:
+/// - `reward` and the total weighted stake are u64 and `distribution_time` is at least 1 second.
+/// - The global accumulator grows by at most `reward * SCALE_FACTOR` per distribution period when one
+///   unit is staked, i.e. below 2^128 for any total distribution under ~3.4e20 lamports.
+/// - A pool accumulator is the global one scaled by `weightage / SACLE_FACTOR_BASE`, so a weightage above
+///   the base lowers that ceiling by the same multiple.
+/// - A user's pending reward is `accumulator delta * stake / SCALE_FACTOR`, which never exceeds what was
+///   distributed to the pool while they were staked.
 pub const SCALE_FACTOR: u128 = 1_000_000_000_000_000_000;
 /// Pubkey that will deploy and initialize the program.
 pub const DEPLOYER: Pubkey = pubkey!("8SstKb2ugTF6D6RdDAFgnzPE4gHytTirGHJ8t7phdLS2");
+/// Number of lock tiers that can be configured by governance. Tier 0 is the liquid (no lock) tier.
+pub const MAX_LOCK_TIERS: usize = 5;
+
+/// Lock duration option that a user can choose while staking.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct LockTier {
+    /// Duration in seconds for which the stake is locked.
+    pub duration: u64,
+    /// Multiplier applied to the staked amount for reward calculation.
+    /// Note: the multiplier is scaled by 1e9, e.g., 1.5 means 1.5x weighted share. Zero means the tier is disabled.
+    pub multiplier: u64,
+}
+
+impl LockTier {
+    pub const LEN: usize = 8 + 8;
+
+    /// Liquid tier with no lock duration and 1x multiplier.
+    pub fn liquid() -> Self {
+        LockTier { duration: 0, multiplier: SACLE_FACTOR_BASE as u64 }
+    }
+
+    /// Check if the tier can be used for staking.
+    pub fn is_enabled(&self) -> bool {
+        self.multiplier != 0
+    }
+}
+
+/// Basis points denominator of the unstake fee.
+pub const FEE_BPS_BASE: u64 = 10_000;
+/// Upper bound of the unstake fee that governance can configure.
+pub const MAX_UNSTAKE_FEE_BPS: u64 = 1_000;
+
+/// Early-exit fee deducted from unstaked tokens.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct UnstakeFee {
+    /// Fee in basis points charged on an unstake right after staking. Zero means no fee.
+    pub fee_bps: u64,
+    /// Duration in seconds over which the fee decays linearly to zero, counted from the last stake of the user.
+    /// Zero means the fee does not decay.
+    pub decay_duration: u64,
+    /// If set, the fee boosts the stake of the remaining stakers of the pool instead of accruing to the treasury.
+    pub redistribute: bool,
+    /// Owner of the token accounts that receive the fees accrued to the treasury.
+    pub treasury: Pubkey,
+}
+
+impl UnstakeFee {
+    pub const LEN: usize = 8 + 8 + 1 + 32;
+
+    /// Get the fee in basis points for a stake that was last increased at the given time.
+    pub fn get_fee_bps(&self, _last_stake_time: u64, _current_time: u64) -> Result<u64> {
+        if self.decay_duration == 0 {
+            return Ok(self.fee_bps);
+        }
+
+        let elapsed = _current_time.saturating_sub(_last_stake_time).min(self.decay_duration);
+
+        GlobalState::to_u128(self.fee_bps)
+            .safe_mul(GlobalState::to_u128(self.decay_duration.safe_sub(elapsed)?))?
+            .safe_div(GlobalState::to_u128(self.decay_duration))?
+            .safe_cast()
+    }
+
+    /// Calculate the fee deducted from the unstaked amount.
+    pub fn calculate_fee(
+        &self,
+        _amount: u64,
+        _last_stake_time: u64,
+        _current_time: u64
+    ) -> Result<u64> {
+        GlobalState::to_u128(_amount)
+            .safe_mul(GlobalState::to_u128(self.get_fee_bps(_last_stake_time, _current_time)?))?
+            .safe_div(GlobalState::to_u128(FEE_BPS_BASE))?
+            .safe_cast()
+    }
+}
+
+/// Pause flag of the stake and cancel withdrawal instructions.
+pub const PAUSE_STAKE: u8 = 1 << 0;
+/// Pause flag of the unstake instruction. Emergency unstake is never paused.
+pub const PAUSE_UNSTAKE: u8 = 1 << 1;
+/// Pause flag of the claim instruction.
+pub const PAUSE_CLAIM: u8 = 1 << 2;
+/// Pause flag of the instructions that update the reward cycles.
+pub const PAUSE_REWARD_UPDATES: u8 = 1 << 3;
+
+/// Maximum duration in seconds of the unbonding cooldown that governance can configure.
+pub const MAX_UNBONDING_COOLDOWN: u64 = 30 * 24 * 60 * 60;
+
+/// Minimum delay in seconds of the governance timelock, once it is enabled.
+pub const MIN_TIMELOCK_DELAY: u64 = 24 * 60 * 60;
+
+/// Number of SPL token reward streams that can be opened by governance.
+pub const MAX_REWARD_STREAMS: usize = 4;
+
+/// Reward stream distributing an SPL token alongside the SOL rewards.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RewardStream {
+    /// Pubkey of the reward SPL token. Default pubkey means the stream slot is not opened.
+    pub mint: Pubkey,
+    /// Amount of tokens that will be distributed in the current cycle.
+    pub reward: u64,
+    /// Timeframe of the current cycle in seconds.
+    pub distribution_time: u64,
+    /// Stream state for maintaining reward calculation per unit of weighted stake across all stake pools.
+    pub global_acc_reward: u128,
+    /// Unix timestamp when the last time reward was calculated.
+    pub last_updated_time: u64,
+    /// Unix timestamp when the current reward distribution cycle will end.
+    pub period_end_time: u64,
+    /// The sum of total tokens that have been distributed as a reward up till now.
+    pub total_distributed_reward: u64,
+    /// The sum of total tokens that have been funded into the stream vault up till now.
+    pub total_funded: u64,
+}
+
+impl RewardStream {
+    pub const LEN: usize = 32 + 8 + 8 + 16 + 8 + 8 + 8 + 8;
+    pub const VAULT_SEEDS: &'static [u8] = b"stream_vault";
+
+    /// Check if the stream slot is opened.
+    pub fn is_opened(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
+
+    /// Check if the committed rewards are covered by the funded tokens.
+    pub fn is_reward_funded(&self) -> bool {
+        self.total_distributed_reward <= self.total_funded
+    }
+
+    /// Get the PDA of the vault holding the reward tokens of the stream with the given mint.
+    pub fn find_vault(_mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[RewardStream::VAULT_SEEDS, _mint.as_ref()], &crate::ID).0
+    }
+
+    /// Get the valid timestamp up to which reward can be distributed.
+    pub fn get_last_reward_time(&self, _current_time: u64) -> u64 {
+        if _current_time < self.period_end_time {
+            return _current_time;
+        }
+
+        self.period_end_time
+    }
+
+    /// Calculate the newly accumulated reward per unit of weighted stake and sum it with previously accumulated reward.
+    pub fn calculate_reward_per_share(
+        &self,
+        _current_time: u64,
+        _total_weighted_stake: u64
+    ) -> Result<u128> {
+        if
+            _current_time <= self.last_updated_time ||
+            self.period_end_time <= self.last_updated_time ||
+            _total_weighted_stake == 0
+        {
+            return Ok(self.global_acc_reward);
+        }
+
+        let reward_time = time_elapsed(self.last_updated_time, self.get_last_reward_time(_current_time))?;
+
+        let reward = GlobalState::to_u128(reward_time).safe_mul(GlobalState::to_u128(self.reward))?;
+        let weighted_time = GlobalState::to_u128(self.distribution_time).safe_mul(
+            GlobalState::to_u128(_total_weighted_stake)
+        )?;
+
+        let acc_reward = mul_div(reward, SCALE_FACTOR, weighted_time)?;
+
+        self.global_acc_reward.safe_add(acc_reward)
+    }
+
+    /// Update the stream state of reward accumulated per unit of weighted stake.
+    pub fn update_reward_per_share(
+        &mut self,
+        _current_time: u64,
+        _total_weighted_stake: u64
+    ) -> Result<()> {
+        if _current_time <= self.last_updated_time {
+            return Ok(());
+        }
+
+        self.global_acc_reward = self.calculate_reward_per_share(
+            _current_time,
+            _total_weighted_stake
+        )?;
+        self.last_updated_time = self.get_last_reward_time(_current_time).max(
+            self.last_updated_time
+        );
+
+        Ok(())
+    }
+}
+
+/// Number of future SOL reward cycles that can be queued by the reward manager.
+pub const MAX_SCHEDULED_CYCLES: usize = 8;
+
+/// Future SOL reward cycle that starts automatically once its start time is reached.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
+pub struct ScheduledCycle {
+    /// Amount of SOL to be distributed in the cycle.
+    pub reward: u64,
+    /// Duration in seconds of the cycle. Zero means the schedule slot is empty.
+    pub distribution_time: u64,
+    /// Unix timestamp when the cycle starts.
+    pub start_time: u64,
+}
+
+impl ScheduledCycle {
+    pub const LEN: usize = 8 + 8 + 8;
+
+    /// Check if the schedule slot holds a queued cycle.
+    pub fn is_queued(&self) -> bool {
+        self.distribution_time != 0
+    }
+
+    /// Get the Unix timestamp when the cycle ends.
+    pub fn get_end_time(&self) -> Result<u64> {
+        self.start_time.safe_add(self.distribution_time)
+    }
+}
+
+/// Number of reward campaigns that can run at the same time.
+pub const MAX_REWARD_CAMPAIGNS: usize = 4;
+
+/// Number of reward campaign slots that sponsors can hold at the same time, so the remaining slots stay
+/// reserved for the reward manager.
+pub const MAX_SPONSORED_CAMPAIGNS: usize = 2;
+
+/// Maximum delay in seconds between opening a campaign and its start, so a campaign can't hold a slot forever.
+pub const MAX_CAMPAIGN_START_DELAY: u64 = 30 * 24 * 60 * 60;
+
+/// Reward campaign running alongside the reward cycle, with its own reward rate and window.
+/// The state is kept on the global state, so every reward update accumulates the running campaigns.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct CampaignState {
+    /// Pubkey of the reward campaign PDA. Default pubkey means the campaign slot is free.
+    pub campaign: Pubkey,
+    /// Pubkey of the reward SPL token, which is distributed through its reward stream. Default pubkey means SOL.
+    pub reward_mint: Pubkey,
+    /// If set, the campaign was deposited by a sponsor, who reclaims its stranded rewards once it ends.
+    pub sponsored: bool,
+    /// Amount of reward distributed over the campaign window.
+    pub reward: u64,
+    /// Unix timestamp when the campaign starts.
+    pub start_time: u64,
+    /// Unix timestamp when the campaign ends.
+    pub end_time: u64,
+    /// Unix timestamp when the last time reward was calculated.
+    pub last_updated_time: u64,
+    /// Campaign state for maintaining reward calculation per unit of weighted stake across all stake pools.
+    pub global_acc_reward: u128,
+    /// Rewards of the campaign emitted while nothing was staked.
+    pub stranded_reward: u64,
+    /// Emitted campaign rewards, scaled by 1e18, not yet distributed because of rounding.
+    pub reward_dust: u128,
+}
+
+impl CampaignState {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 8 + 8 + 8 + 16 + 8 + 16;
+
+    /// Check if the campaign slot is in use.
+    pub fn is_opened(&self) -> bool {
+        self.campaign != Pubkey::default()
+    }
+
+    /// Check if the campaign window has ended at the given time.
+    pub fn is_ended(&self, _current_time: u64) -> bool {
+        self.end_time <= _current_time
+    }
+
+    /// Get the count of seconds of the campaign window since the last update.
+    fn get_reward_time(&self, _current_time: u64) -> u64 {
+        _current_time.min(self.end_time).saturating_sub(self.last_updated_time.max(self.start_time))
+    }
+
+    /// Check if the campaign distributes SOL.
+    pub fn is_sol(&self) -> bool {
+        self.reward_mint == Pubkey::default()
+    }
+
+    /// Calculate the campaign rewards emitted since the last update, scaled by 1e18, along with the dust carried
+    /// from the previous updates.
+    fn calculate_emitted_reward(&self, _current_time: u64) -> Result<u128> {
+        let reward = GlobalState::to_u128(self.get_reward_time(_current_time)).safe_mul(
+            GlobalState::to_u128(self.reward)
+        )?;
+
+        mul_div(
+            reward,
+            SCALE_FACTOR,
+            GlobalState::to_u128(time_elapsed(self.start_time, self.end_time)?)
+        )?.safe_add(self.reward_dust)
+    }
+
+    /// Calculate the newly accumulated reward per unit of weighted stake and sum it with previously accumulated reward.
+    pub fn calculate_reward_per_share(
+        &self,
+        _current_time: u64,
+        _total_weighted_stake: u64
+    ) -> Result<u128> {
+        if _total_weighted_stake == 0 {
+            return Ok(self.global_acc_reward);
+        }
+
+        let acc_reward = self
+            .calculate_emitted_reward(_current_time)?
+            .safe_div(GlobalState::to_u128(_total_weighted_stake))?;
+
+        self.global_acc_reward.safe_add(acc_reward)
+    }
+
+    /// Calculate the campaign rewards emitted since the last update while nothing was staked.
+    pub fn calculate_stranded_reward(&self, _current_time: u64, _total_weighted_stake: u64) -> Result<u64> {
+        if _total_weighted_stake != 0 {
+            return Ok(0);
+        }
+
+        self.calculate_emitted_reward(_current_time)?.safe_div(SCALE_FACTOR)?.safe_cast()
+    }
+
+    /// Calculate the emitted campaign rewards, scaled by 1e18, that are left over after the accumulator or the
+    /// stranded rewards truncate them.
+    pub fn calculate_reward_dust(&self, _current_time: u64, _total_weighted_stake: u64) -> Result<u128> {
+        let emitted_reward = self.calculate_emitted_reward(_current_time)?;
+
+        if _total_weighted_stake == 0 {
+            return Ok(emitted_reward % SCALE_FACTOR);
+        }
+
+        Ok(emitted_reward % GlobalState::to_u128(_total_weighted_stake))
+    }
+
+    /// Update the campaign state of reward accumulated per unit of weighted stake, and return the newly
+    /// accumulated reward so it can be added to the SOL or reward stream accumulator.
+    pub fn update_reward_per_share(
+        &mut self,
+        _current_time: u64,
+        _total_weighted_stake: u64
+    ) -> Result<u128> {
+        if _current_time <= self.last_updated_time {
+            return Ok(0);
+        }
+
+        let global_acc_reward = self.calculate_reward_per_share(_current_time, _total_weighted_stake)?;
+        let acc_reward = global_acc_reward.safe_sub(self.global_acc_reward)?;
+
+        self.stranded_reward = self.stranded_reward.safe_add(
+            self.calculate_stranded_reward(_current_time, _total_weighted_stake)?
+        )?;
+        self.reward_dust = self.calculate_reward_dust(_current_time, _total_weighted_stake)?;
+        self.global_acc_reward = global_acc_reward;
+        self.last_updated_time = _current_time;
+
+        Ok(acc_reward)
+    }
+}
+
+/// Number of SPL tokens that sponsors can use for reward campaigns.
+pub const MAX_CAMPAIGN_MINTS: usize = 4;
+
+/// SPL token allowed in sponsored reward campaigns.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct CampaignMint {
+    /// Pubkey of the reward SPL token, which must have an opened reward stream. Default pubkey means the slot is empty.
+    pub mint: Pubkey,
+    /// Minimum amount of tokens of a sponsored campaign.
+    pub min_reward: u64,
+}
+
+/// Bounds of the reward campaigns that anyone can open by depositing the rewards.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct CampaignConfig {
+    /// Minimum amount of SOL of a sponsored campaign. Zero means SOL campaigns can not be sponsored.
+    pub min_sol_reward: u64,
+    /// Minimum duration in seconds of a sponsored campaign.
+    pub min_duration: u64,
+    /// Maximum duration in seconds of a sponsored campaign. Zero means campaigns can not be sponsored.
+    pub max_duration: u64,
+    /// SPL tokens allowed in sponsored campaigns.
+    pub mints: [CampaignMint; MAX_CAMPAIGN_MINTS],
+}
+
+impl CampaignConfig {
+    pub const LEN: usize = 8 + 8 + 8 + (32 + 8) * MAX_CAMPAIGN_MINTS;
+
+    /// Get the allowed SPL token with the given mint.
+    pub fn get_mint(&self, _mint: Pubkey) -> Option<CampaignMint> {
+        self.mints
+            .iter()
+            .copied()
+            .find(|campaign_mint| campaign_mint.mint != Pubkey::default() && campaign_mint.mint == _mint)
+    }
+}
+
+/// Version of the account layout written by the program. The legacy layout, which held the $MESH and
+/// $indexMESH stake in the global state, has no version field and is treated as version 0.
+pub const LAYOUT_VERSION: u8 = 1;
 
 /// PDA to store globally used state
 #[account]
 pub struct GlobalState {
     /// Pubkey responsible for executing governance-only instructions.
     pub governance: Pubkey,
-    /// Pubkey of $MESH SPL token
-    pub mesh_mint: Pubkey,
-    /// Pubkey of $indexMESH SPL token
-    pub index_mesh_mint: Pubkey,
     /// Amount of SOL that will be distributed in the current cycle.
     pub reward: u64,
     /// Timeframe of the current cycle in seconds.
     pub distribution_time: u64,
-    /// Global state for maintaining reward calculation per unit of $MESH.
-    pub global_acc_reward_mesh: u128,
-    /// Global state for maintaining reward calculation per unit of $indexMESH
-    pub global_acc_reward_index_mesh: u128,
-    /// Total amount of $MESH staked by users.
-    pub total_staked_mesh: u64,
-    /// Total amount of $indexMESH staked by users.
-    pub total_staked_index_mesh: u64,
-    /// Weight of $indexMESH used for its reward calculation.
-    /// Note: the weightage is in ($MESH/$indexMESH), e.g., 0.5 means 0.5 $MESH is equivalent to 1 $indexMESH.
-    pub weightage: u64,
+    /// Global state for maintaining reward calculation per unit of weighted stake across all stake pools.
+    pub global_acc_reward: u128,
+    /// Total stake of all stake pools, scaled by the lock multipliers and the weightage of each pool.
+    pub total_weighted_stake: u64,
     /// Unix timestamp when the last time reward was calculated.
     pub last_updated_time: u64,
     /// Unix timestamp when the current reward distribution cycle will end.
     pub period_end_time: u64,
     /// The sum of total SOLs that have been distributed as a reward up till now.
     pub total_distributed_reward: u64,
+    /// Lock durations and their reward multipliers that users can choose while staking.
+    pub lock_tiers: [LockTier; MAX_LOCK_TIERS],
+    /// Duration in seconds that unstaked tokens have to wait before they can be withdrawn.
+    pub unbonding_cooldown: u64,
+    /// Number of stake pools registered by governance.
+    pub pool_count: u64,
+    /// SPL token reward streams distributed alongside the SOL rewards.
+    pub reward_streams: [RewardStream; MAX_REWARD_STREAMS],
+    /// The sum of total SOLs that have been funded for rewards up till now.
+    pub total_funded: u64,
+    /// Bitmask of the paused instructions, see the `PAUSE_*` flags.
+    pub pause_flags: u8,
+    /// Pubkey proposed as the new governance, until it accepts. Default pubkey means no proposal.
+    pub pending_governance: Pubkey,
+    /// Delay in seconds before a queued governance action can be executed. Zero means the timelock is not enabled yet.
+    pub timelock_delay: u64,
+    /// Id of the next queued governance action.
+    pub next_action_id: u64,
+    /// Pubkey responsible for updating the reward cycles of SOL and the reward streams.
+    pub reward_manager: Pubkey,
+    /// Pubkey responsible for pausing and unpausing instructions.
+    pub pauser: Pubkey,
+    /// Pubkey responsible for migrating reward SOLs.
+    pub treasurer: Pubkey,
+    /// Early-exit fee charged on unstake.
+    pub unstake_fee: UnstakeFee,
+    /// If set, the reward state is frozen and users can only exit through emergency withdraw.
+    pub safe_mode: bool,
+    /// SOL rewards emitted while nothing was staked, which are rolled over or withdrawn to the treasury.
+    pub stranded_reward: u64,
+    /// Emitted SOL rewards not yet allocated to the accumulator because of rounding, scaled by 1e18.
+    pub reward_dust: u128,
+    /// Id of the next reward campaign.
+    pub next_campaign_id: u64,
+    /// Reward campaigns running alongside the reward cycle.
+    pub reward_campaigns: [CampaignState; MAX_REWARD_CAMPAIGNS],
+    /// Bounds of the campaigns opened by sponsors.
+    pub campaign_config: CampaignConfig,
+    /// Id of the next merkle distribution, used in its PDA seeds.
+    pub next_distribution_id: u64,
+    /// Id of the current snapshot epoch. Zero means no snapshot epoch has been opened yet.
+    pub snapshot_epoch: u64,
+    /// Unix timestamp when the current snapshot epoch was opened.
+    pub snapshot_start_time: u64,
+    /// Stake pools of $MESH and $indexMESH created from the legacy layout, which the legacy userInfo PDAs
+    /// are migrated into. Default pubkeys mean the program was initialized in the current layout.
+    pub legacy_pools: [Pubkey; 2],
+    /// Version of the account layout, see `LAYOUT_VERSION`.
+    pub layout_version: u8,
 }
 
 impl GlobalState {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 16 + 16 + 8 + 8 + 8 + 8 + 8 + 8;
+    pub const LEN: usize =
+        8 + 32 + 8 + 8 + 16 + 8 + 8 + 8 + 8 + LockTier::LEN * MAX_LOCK_TIERS + 8 + 8 +
+        RewardStream::LEN * MAX_REWARD_STREAMS + 8 + 1 + 32 + 8 + 8 + 32 + 32 + 32 +
+        UnstakeFee::LEN + 1 + 8 + 16 + 8 +
+        CampaignState::LEN * MAX_REWARD_CAMPAIGNS + CampaignConfig::LEN + 8 + 8 + 8 + 32 * 2 + 1;
     pub const SEEDS: &'static [u8] = b"global_state";
 
     /// Typecast u64 to u128 to avoid overflow on u64 variables.
@@ -51,7 +504,7 @@ impl GlobalState {
 
     /// Get the current Unix timestamp, typecasted from i64 to u64.
     pub fn get_current_time(&self) -> Result<u64> {
-        Ok(Clock::get()?.unix_timestamp.try_into().unwrap())
+        u64::try_from(Clock::get()?.unix_timestamp).map_err(|_| ErrorCode::NegativeTime.into())
     }
 
     /// Get the valid timestamp up to which reward can be distributed.
@@ -64,110 +517,928 @@ impl GlobalState {
     }
 
     /// Get the count of seconds in which the reward will be accumulated.
-    pub fn get_reward_time(&self, _current_time: u64) -> u64 {
-        self.get_last_reward_time(_current_time).checked_sub(self.last_updated_time).unwrap()
-    }
-
-    /// Calculate the newly accumulated reward on $MESH & $indexMESH and sum it with previously accumulated reward.
-    pub fn calculate_reward_per_share(&self, _current_time: u64) -> Result<[u128; 2]> {
-        let reward = GlobalState::to_u128(self.get_reward_time(_current_time))
-            .checked_mul(GlobalState::to_u128(self.reward))
-            .unwrap();
-
-        let weighted_index_mesh = GlobalState::to_u128(self.total_staked_index_mesh)
-            .checked_mul(GlobalState::to_u128(self.weightage))
-            .unwrap()
-            .checked_div(SACLE_FACTOR_BASE)
-            .unwrap();
-
-        let total_weighted_amount = weighted_index_mesh
-            .checked_add(GlobalState::to_u128(self.total_staked_mesh))
-            .unwrap();
-
-        let acc_reward = reward
-            .checked_mul(SCALE_FACTOR)
-            .unwrap()
-            .checked_div(GlobalState::to_u128(self.distribution_time))
-            .unwrap()
-            .checked_div(total_weighted_amount)
-            .unwrap();
-
-        let acc_reward_index_mesh = acc_reward
-            .checked_mul(GlobalState::to_u128(self.weightage))
-            .unwrap()
-            .checked_div(SACLE_FACTOR_BASE)
-            .unwrap();
-
-        Ok([
-            self.global_acc_reward_mesh.checked_add(acc_reward).unwrap(),
-            self.global_acc_reward_index_mesh.checked_add(acc_reward_index_mesh).unwrap(),
-        ])
-    }
-
-    /// Update the global state of reward accumulated per unit of $MESH & $indexMESH.
-    pub fn update_reward_per_share(&mut self) -> Result<()> {
+    pub fn get_reward_time(&self, _current_time: u64) -> Result<u64> {
+        time_elapsed(self.last_updated_time, self.get_last_reward_time(_current_time))
+    }
+
+    /// Calculate the SOL rewards emitted since the last update, scaled by 1e18, along with the dust carried
+    /// from the previous updates.
+    fn calculate_emitted_reward(&self, _current_time: u64) -> Result<u128> {
+        if _current_time <= self.last_updated_time || self.period_end_time <= self.last_updated_time {
+            return Ok(self.reward_dust);
+        }
+
+        let reward = GlobalState::to_u128(self.get_reward_time(_current_time)?).safe_mul(
+            GlobalState::to_u128(self.reward)
+        )?;
+
+        mul_div(reward, SCALE_FACTOR, GlobalState::to_u128(self.distribution_time))?.safe_add(
+            self.reward_dust
+        )
+    }
+
+    /// Calculate the newly accumulated reward per unit of weighted stake and sum it with previously accumulated reward.
+    pub fn calculate_reward_per_share(&self, _current_time: u64) -> Result<u128> {
+        if _current_time <= self.last_updated_time || self.total_weighted_stake == 0 {
+            return Ok(self.global_acc_reward);
+        }
+
+        let acc_reward = self
+            .calculate_emitted_reward(_current_time)?
+            .safe_div(GlobalState::to_u128(self.total_weighted_stake))?;
+
+        self.global_acc_reward.safe_add(acc_reward)
+    }
+
+    /// Calculate the SOL rewards emitted since the last update while nothing was staked.
+    pub fn calculate_stranded_reward(&self, _current_time: u64) -> Result<u64> {
+        if _current_time <= self.last_updated_time || self.total_weighted_stake != 0 {
+            return Ok(0);
+        }
+
+        self.calculate_emitted_reward(_current_time)?.safe_div(SCALE_FACTOR)?.safe_cast()
+    }
+
+    /// Calculate the emitted SOL rewards, scaled by 1e18, that are left over after the accumulator or the
+    /// stranded rewards truncate them. The dust is carried into the next update, so it is distributed eventually.
+    pub fn calculate_reward_dust(&self, _current_time: u64) -> Result<u128> {
+        if _current_time <= self.last_updated_time {
+            return Ok(self.reward_dust);
+        }
+
+        let emitted_reward = self.calculate_emitted_reward(_current_time)?;
+
+        if self.total_weighted_stake == 0 {
+            return Ok(emitted_reward % SCALE_FACTOR);
+        }
+
+        Ok(emitted_reward % GlobalState::to_u128(self.total_weighted_stake))
+    }
+
+    /// Update the global state of reward accumulated per unit of weighted stake, for SOL and all reward streams.
+    /// The scheduled SOL reward cycles that are due are started in order, after the previous cycle is accumulated.
+    /// The rewards of the running campaigns are summed into the SOL or reward stream accumulator.
+    pub fn update_reward_per_share(&mut self, _reward_schedule: &mut RewardSchedule) -> Result<()> {
         let current_time: u64 = self.get_current_time()?;
 
-        if current_time <= self.last_updated_time {
-            return Ok(());
+        for reward_stream in self.reward_streams.iter_mut() {
+            if reward_stream.is_opened() {
+                reward_stream.update_reward_per_share(current_time, self.total_weighted_stake)?;
+            }
+        }
+
+        for reward_campaign in self.reward_campaigns.iter_mut() {
+            if !reward_campaign.is_opened() {
+                continue;
+            }
+
+            let acc_reward = reward_campaign.update_reward_per_share(
+                current_time,
+                self.total_weighted_stake
+            )?;
+
+            if reward_campaign.is_sol() {
+                self.global_acc_reward = self.global_acc_reward.safe_add(acc_reward)?;
+            } else {
+                let reward_stream = self.reward_streams
+                    .iter_mut()
+                    .find(|reward_stream| reward_stream.mint == reward_campaign.reward_mint)
+                    .ok_or(ErrorCode::InvalidCampaignMint)?;
+                reward_stream.global_acc_reward = reward_stream.global_acc_reward.safe_add(acc_reward)?;
+            }
         }
 
-        if self.total_staked_mesh == 0 && self.total_staked_index_mesh == 0 {
-            self.last_updated_time = self.get_last_reward_time(current_time);
+        while let Some(scheduled_cycle) = _reward_schedule.get_due_cycle(current_time) {
+            self.accumulate_reward(scheduled_cycle.start_time)?;
+            self.start_scheduled_cycle(scheduled_cycle);
+            _reward_schedule.remove_first_cycle();
+        }
+
+        self.accumulate_reward(current_time)
+    }
+
+    /// Accumulate the SOL rewards of the current cycle up to the given time.
+    fn accumulate_reward(&mut self, _current_time: u64) -> Result<()> {
+        if _current_time <= self.last_updated_time {
             return Ok(());
         }
 
-        let [global_acc_reward_mesh, global_acc_reward_index_mesh] =
-            self.calculate_reward_per_share(current_time)?;
+        self.global_acc_reward = self.calculate_reward_per_share(_current_time)?;
+        self.stranded_reward = self.stranded_reward.safe_add(self.calculate_stranded_reward(_current_time)?)?;
+        self.reward_dust = self.calculate_reward_dust(_current_time)?;
+        self.last_updated_time = self.get_last_reward_time(_current_time);
+
+        Ok(())
+    }
+
+    /// Replace the ended cycle with the given scheduled cycle.
+    /// The scheduled rewards were already committed when the cycle was queued.
+    fn start_scheduled_cycle(&mut self, _scheduled_cycle: ScheduledCycle) {
+        self.reward = _scheduled_cycle.reward;
+        self.distribution_time = _scheduled_cycle.distribution_time;
+        self.period_end_time = _scheduled_cycle.start_time.saturating_add(_scheduled_cycle.distribution_time);
+        self.last_updated_time = _scheduled_cycle.start_time;
+    }
+
+    /// Check if the parameter changes have to go through the timelock.
+    pub fn is_timelocked(&self) -> bool {
+        self.timelock_delay != 0
+    }
+
+    /// Get the pubkey holding the given role. Default pubkey means the role is revoked.
+    pub fn get_role_holder(&self, _role: Role) -> Pubkey {
+        match _role {
+            Role::Governance => self.governance,
+            Role::RewardManager => self.reward_manager,
+            Role::Pauser => self.pauser,
+            Role::Treasurer => self.treasurer,
+        }
+    }
+
+    /// Check if any of the given pause flags is set. Every pausable instruction is paused in safe mode.
+    pub fn is_paused(&self, _pause_flags: u8) -> bool {
+        self.safe_mode || self.pause_flags & _pause_flags != 0
+    }
+
+    /// Check if the committed rewards are covered by the funded SOLs.
+    pub fn is_reward_funded(&self) -> bool {
+        self.total_distributed_reward <= self.total_funded
+    }
+
+    /// Get the reward accumulated per unit of weighted stake of every reward stream slot.
+    pub fn get_stream_acc_rewards(&self) -> [u128; MAX_REWARD_STREAMS] {
+        let mut stream_acc_rewards = [0; MAX_REWARD_STREAMS];
+
+        for (acc_reward, reward_stream) in stream_acc_rewards.iter_mut().zip(self.reward_streams.iter()) {
+            *acc_reward = reward_stream.global_acc_reward;
+        }
+
+        stream_acc_rewards
+    }
+
+    /// Get the lock tier at the given index, if it exists and is enabled.
+    pub fn get_lock_tier(&self, _index: u8) -> Option<LockTier> {
+        self.lock_tiers
+            .get(_index as usize)
+            .copied()
+            .filter(|tier| tier.is_enabled())
+    }
+}
+
+/// PDA to store the state of a stakeable SPL token.
+#[account]
+pub struct StakePool {
+    /// Pubkey of the staked SPL token.
+    pub mint: Pubkey,
+    /// Weight of the token used for its reward calculation.
+    /// Note: the weightage is scaled by 1e9, e.g., 0.5 means 1 token is equivalent to 0.5 unit of weighted stake.
+    pub weightage: u64,
+    /// Total amount of the token staked by users.
+    pub total_staked: u64,
+    /// Total amount of the token staked by users, scaled by their lock multipliers.
+    pub total_weighted: u64,
+    /// Pool state for maintaining reward calculation per unit of the token.
+    pub acc_reward: u128,
+    /// Value of the global accumulated reward when the pool was last updated.
+    pub global_acc_reward_checkpoint: u128,
+    /// Pool state for maintaining reward calculation per unit of the token, for each reward stream.
+    pub stream_acc_rewards: [u128; MAX_REWARD_STREAMS],
+    /// Value of the accumulated reward of each reward stream when the pool was last updated.
+    pub stream_acc_reward_checkpoints: [u128; MAX_REWARD_STREAMS],
+    /// Unstake fees per unit of the staked token that are redistributed to the stakers, scaled by 1e18.
+    pub fee_acc_reward: u128,
+    /// Unstake fees held in the vault until they are collected to the treasury.
+    pub accrued_fees: u64,
+    /// Fraction of the SOL accumulated reward truncated on the last update, carried into the next one.
+    /// Note: the remainder is scaled by 1e9, the same as the weightage.
+    pub acc_reward_remainder: u128,
+}
+
+impl StakePool {
+    pub const LEN: usize =
+        8 + 32 + 8 + 8 + 8 + 16 + 16 + 16 * MAX_REWARD_STREAMS + 16 * MAX_REWARD_STREAMS + 16 + 8 + 16;
+    pub const SEEDS: &'static [u8] = b"stake_pool";
+
+    /// Scale the staked amount by the pool weightage only, as used for the voter weight.
+    pub fn to_voting_power(&self, _amount: u64) -> Result<u128> {
+        GlobalState::to_u128(_amount)
+            .safe_mul(GlobalState::to_u128(self.weightage))?
+            .safe_div(SACLE_FACTOR_BASE)
+    }
+
+    /// Get the stake of the pool, scaled by the lock multipliers and the pool weightage.
+    pub fn get_weighted_stake(&self) -> Result<u64> {
+        GlobalState::to_u128(self.total_weighted)
+            .safe_mul(GlobalState::to_u128(self.weightage))?
+            .safe_div(SACLE_FACTOR_BASE)?
+            .safe_cast()
+    }
+
+    /// Calculate the reward accumulated per unit of the token, given the reward accumulated per unit of
+    /// weighted stake since the last update.
+    fn calculate_reward_per_share(
+        &self,
+        _acc_reward: u128,
+        _global_acc_reward: u128,
+        _global_acc_reward_checkpoint: u128
+    ) -> Result<u128> {
+        let acc_reward = mul_div(
+            _global_acc_reward.safe_sub(_global_acc_reward_checkpoint)?,
+            GlobalState::to_u128(self.weightage),
+            SACLE_FACTOR_BASE
+        )?;
+
+        _acc_reward.safe_add(acc_reward)
+    }
+
+    /// Update the pool state of reward accumulated per unit of the token, for SOL and all reward streams.
+    /// Must be called after the global state is updated and before the pool weightage or stake changes.
+    pub fn update_reward_per_share(&mut self, _global_state: &GlobalState) -> Result<()> {
+        let (acc_reward, acc_reward_remainder) = mul_div_carry(
+            _global_state.global_acc_reward.safe_sub(self.global_acc_reward_checkpoint)?,
+            GlobalState::to_u128(self.weightage),
+            SACLE_FACTOR_BASE,
+            self.acc_reward_remainder
+        )?;
+        self.acc_reward = self.acc_reward.safe_add(acc_reward)?;
+        self.acc_reward_remainder = acc_reward_remainder;
+        self.global_acc_reward_checkpoint = _global_state.global_acc_reward;
+
+        let stream_acc_rewards = _global_state.get_stream_acc_rewards();
+
+        let mut pool_stream_acc_rewards = self.stream_acc_rewards;
+
+        for (index, acc_reward) in pool_stream_acc_rewards.iter_mut().enumerate() {
+            *acc_reward = self.calculate_reward_per_share(
+                *acc_reward,
+                stream_acc_rewards[index],
+                self.stream_acc_reward_checkpoints[index]
+            )?;
+        }
+        self.stream_acc_rewards = pool_stream_acc_rewards;
+        self.stream_acc_reward_checkpoints = stream_acc_rewards;
+
+        Ok(())
+    }
 
-        self.global_acc_reward_mesh = global_acc_reward_mesh;
-        self.global_acc_reward_index_mesh = global_acc_reward_index_mesh;
-        self.last_updated_time = self.get_last_reward_time(current_time);
+    /// Take the unstake fee out of the pool. The fee is either spread over the remaining staked tokens
+    /// or accrued to the treasury, which is also the fallback when no tokens are left staked.
+    pub fn distribute_unstake_fee(&mut self, _fee: u64, _redistribute: bool) -> Result<()> {
+        if _redistribute && self.total_staked > 0 {
+            let fee_per_share = mul_div(
+                GlobalState::to_u128(_fee),
+                SCALE_FACTOR,
+                GlobalState::to_u128(self.total_staked)
+            )?;
+
+            self.fee_acc_reward = self.fee_acc_reward.safe_add(fee_per_share)?;
+        } else {
+            self.accrued_fees = self.accrued_fees.safe_add(_fee)?;
+        }
 
         Ok(())
     }
 }
 
-/// PDA to store user-specific state.
+/// Number of balance checkpoints stored per user and snapshot epoch.
+pub const MAX_STAKE_CHECKPOINTS: usize = 16;
+
+/// Balances of a user from the given time until the next checkpoint.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct StakeCheckpoint {
+    /// Unix timestamp of the balance change.
+    pub time: u64,
+    /// Amount of tokens staked by user.
+    pub staked: u64,
+    /// Amount of tokens staked by user, scaled by the lock multiplier.
+    pub weighted: u64,
+}
+
+impl StakeCheckpoint {
+    pub const LEN: usize = 8 + 8 + 8;
+}
+
+/// PDA to store user-specific state of a stake pool.
 #[account]
 pub struct UserInfo {
     /// Pubkey of user/
     pub user: Pubkey,
-    /// User state for maintaining reward calculation per unit of $MESH.
-    /* It helps to find out how much user share they have in the globally accumulated reward
+    /// Pubkey of the stake pool.
+    pub pool: Pubkey,
+    /// User state for maintaining reward calculation per unit of the staked token.
+    /* It helps to find out how much user share they have in the pool accumulated reward
        and how much of it is still unclaimed. */
-    pub acc_reward_mesh: u128,
-    /// User state for maintaining reward calculation per unit of $indexMESH.
-    pub acc_reward_index_mesh: u128,
-    /// Amount of $MESH staked by user.
-    pub staked_mesh: u64,
-    /// Amount of $indexMESH staked by user.
-    pub staked_index_mesh: u64,
+    pub acc_reward: u128,
+    /// Amount of tokens staked by user.
+    pub staked: u64,
+    /// Amount of tokens staked by user, scaled by the lock multiplier.
+    pub weighted: u64,
     /// The sum of total reward SOLs that have been claimed by the user up till now.
     pub total_claimed_reward: u64,
+    /// Multiplier of the lock tier chosen by the user, scaled by 1e9.
+    pub lock_multiplier: u64,
+    /// Unix timestamp until which the staked tokens can't be unstaked.
+    pub lock_end_time: u64,
+    /// Id that will be assigned to the next withdrawal ticket of the user.
+    pub next_ticket_id: u64,
+    /// Count of withdrawal tickets that are not yet withdrawn or cancelled.
+    pub open_tickets: u64,
+    /// User state for maintaining reward calculation per unit of the staked token, for each reward stream.
+    pub stream_acc_rewards: [u128; MAX_REWARD_STREAMS],
+    /// Reward stream tokens that are accumulated by the user but not yet claimed.
+    pub stream_pending_rewards: [u64; MAX_REWARD_STREAMS],
+    /// User state for maintaining redistributed unstake fees per unit of the staked token.
+    pub fee_acc_reward: u128,
+    /// Unix timestamp of the last stake of the user, from which the unstake fee decays.
+    pub last_stake_time: u64,
+    /// Reward SOLs accumulated by the user but not paid out by an emergency unstake or a lock release, paid on
+    /// the next claim.
+    pub unclaimed_owed: u64,
+    /// Redistributed unstake fees accumulated by the user but not compounded by an emergency withdrawal, compounded
+    /// into the stake on the next fee settlement.
+    pub unclaimed_fee_owed: u64,
+    /// Fraction of a lamport truncated from the SOL rewards of the user, carried into the next settlement.
+    /// Note: the remainder is scaled by 1e18.
+    pub reward_remainder: u128,
+    /// Snapshot epoch of the stored checkpoints.
+    pub snapshot_epoch: u64,
+    /// Count of the stored checkpoints.
+    pub num_checkpoints: u8,
+    /// Balances of the user since the snapshot epoch was opened, ordered by time.
+    pub checkpoints: [StakeCheckpoint; MAX_STAKE_CHECKPOINTS],
 }
 
 impl UserInfo {
-    pub const LEN: usize = 8 + 32 + 16 + 16 + 8 + 8 + 8;
+    pub const LEN: usize =
+        8 + 32 + 32 + 16 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 16 * MAX_REWARD_STREAMS + 8 * MAX_REWARD_STREAMS +
+        16 + 8 + 8 + 8 + 16 + 8 + 1 + StakeCheckpoint::LEN * MAX_STAKE_CHECKPOINTS;
+    pub const SEEDS: &'static [u8] = b"user_info";
 
     /// Check if user PDA is initialized or not.
     pub fn is_initialized(&self) -> bool {
         self.user != Pubkey::default()
     }
 
-    /// Initialize PDA by storing user Pubkey if not already initialized.
-    pub fn init(&mut self, user: Pubkey) -> () {
+    /// Initialize PDA by storing user and pool Pubkey if not already initialized.
+    pub fn init(&mut self, user: Pubkey, pool: Pubkey) {
         if !self.is_initialized() {
             self.user = user;
+            self.pool = pool;
+            self.lock_multiplier = LockTier::liquid().multiplier;
+        }
+    }
+
+    /// Check if the user stake is still locked at the given time. The multiplier of an ended lock stays
+    /// boosted until the user acts again or anyone releases it.
+    pub fn is_locked(&self, _current_time: u64) -> bool {
+        _current_time < self.lock_end_time
+    }
+
+    /// Start the checkpoints of a newly opened snapshot epoch with the balances held when it was opened.
+    /// Has to be called before the balances are changed, as no balance change was recorded since then.
+    pub fn sync_snapshot_epoch(&mut self, _global_state: &GlobalState) {
+        if _global_state.snapshot_epoch == 0 || self.snapshot_epoch == _global_state.snapshot_epoch {
+            return;
+        }
+
+        self.snapshot_epoch = _global_state.snapshot_epoch;
+        self.checkpoints = [StakeCheckpoint::default(); MAX_STAKE_CHECKPOINTS];
+        self.checkpoints[0] = StakeCheckpoint {
+            time: _global_state.snapshot_start_time,
+            staked: self.staked,
+            weighted: self.weighted,
+        };
+        self.num_checkpoints = 1;
+    }
+
+    /// Record the current balances in the checkpoints of the snapshot epoch.
+    /// Once the checkpoints are full, the two oldest checkpoints after the opening one are merged into one
+    /// with the lower balances, so the balances at any time are never overstated.
+    pub fn write_checkpoint(&mut self, _global_state: &GlobalState) -> Result<()> {
+        if _global_state.snapshot_epoch == 0 {
+            return Ok(());
+        }
+
+        self.sync_snapshot_epoch(_global_state);
+
+        let checkpoint = StakeCheckpoint {
+            time: _global_state.get_current_time()?,
+            staked: self.staked,
+            weighted: self.weighted,
+        };
+
+        let mut num_checkpoints: usize = self.num_checkpoints.into();
+        let last = num_checkpoints - 1;
+
+        if self.checkpoints[last].time == checkpoint.time {
+            self.checkpoints[last] = checkpoint;
+            return Ok(());
+        }
+
+        if num_checkpoints == MAX_STAKE_CHECKPOINTS {
+            let (first, second) = (self.checkpoints[1], self.checkpoints[2]);
+
+            self.checkpoints[1] = StakeCheckpoint {
+                time: first.time,
+                staked: first.staked.min(second.staked),
+                weighted: first.weighted.min(second.weighted),
+            };
+            self.checkpoints.copy_within(3.., 2);
+            num_checkpoints -= 1;
+        }
+
+        self.checkpoints[num_checkpoints] = checkpoint;
+        self.num_checkpoints = (num_checkpoints + 1).safe_cast()?;
+
+        Ok(())
+    }
+
+    /// Get the balances of the user at the given time, which can't be older than the current snapshot epoch.
+    /// Within merged checkpoints, the lower balances of the merged range are returned.
+    pub fn balance_at(&self, _global_state: &GlobalState, _timestamp: u64) -> Result<StakeCheckpoint> {
+        if _global_state.snapshot_epoch == 0 || _timestamp < _global_state.snapshot_start_time {
+            return Err(ErrorCode::SnapshotUnavailable.into());
+        }
+
+        // The balances have not changed since the snapshot epoch was opened.
+        if self.snapshot_epoch != _global_state.snapshot_epoch {
+            return Ok(StakeCheckpoint { time: _timestamp, staked: self.staked, weighted: self.weighted });
+        }
+
+        let num_checkpoints: usize = self.num_checkpoints.into();
+
+        self.checkpoints[..num_checkpoints]
+            .iter()
+            .rev()
+            .find(|checkpoint| checkpoint.time <= _timestamp)
+            .copied()
+            .ok_or(ErrorCode::SnapshotUnavailable.into())
+    }
+
+    /// Scale the staked amount by the user lock multiplier.
+    pub fn to_weighted(&self, _amount: u64) -> Result<u64> {
+        GlobalState::to_u128(_amount)
+            .safe_mul(GlobalState::to_u128(self.lock_multiplier))?
+            .safe_div(SACLE_FACTOR_BASE)?
+            .safe_cast()
+    }
+
+    /// Calculate pending rewards of the user between the user and the pool accumulated reward.
+    fn calculate_rewards(&self, _acc_reward: u128, _pool_acc_reward: u128) -> Result<u128> {
+        mul_div(_pool_acc_reward.safe_sub(_acc_reward)?, GlobalState::to_u128(self.weighted), SCALE_FACTOR)
+    }
+
+    /// Calculate pending SOL rewards of the user up to the given pool accumulated reward, along with the
+    /// fraction of a lamport left over.
+    fn calculate_rewards_with_remainder(&self, _pool_acc_reward: u128) -> Result<(u128, u128)> {
+        mul_div_carry(
+            _pool_acc_reward.safe_sub(self.acc_reward)?,
+            GlobalState::to_u128(self.weighted),
+            SCALE_FACTOR,
+            self.reward_remainder
+        )
+    }
+
+    /// Calculate pending SOL rewards of the user up to the given pool accumulated reward.
+    pub fn calculate_pending_rewards(&self, _pool_acc_reward: u128) -> Result<u128> {
+        self.calculate_rewards_with_remainder(_pool_acc_reward).map(|(reward, _)| reward)
+    }
+
+    /// Move the pending SOL rewards of the user out of the pool accumulated reward and return them.
+    /// Must be called after the pool is updated and before the user stake changes.
+    pub fn settle_rewards(&mut self, _pool_acc_reward: u128) -> Result<u64> {
+        let (reward, reward_remainder) = self.calculate_rewards_with_remainder(_pool_acc_reward)?;
+
+        self.acc_reward = _pool_acc_reward;
+        self.reward_remainder = reward_remainder;
+
+        reward.safe_cast()
+    }
+
+    /// Move the newly accumulated reward stream tokens of the user into the pending rewards.
+    /// Must be called after the pool is updated and before the user stake changes.
+    pub fn settle_stream_rewards(&mut self, _stake_pool: &StakePool) -> Result<()> {
+        for index in 0..MAX_REWARD_STREAMS {
+            let reward: u64 = self
+                .calculate_rewards(self.stream_acc_rewards[index], _stake_pool.stream_acc_rewards[index])?
+                .safe_cast()?;
+
+            self.stream_pending_rewards[index] = self.stream_pending_rewards[index]
+                .safe_add(reward)?;
+        }
+        self.stream_acc_rewards = _stake_pool.stream_acc_rewards;
+
+        Ok(())
+    }
+
+    /// Compound the redistributed unstake fees of the user into their stake, as the fee tokens never left the vault.
+    /// The fees owed by an emergency withdrawal are compounded along with them.
+    /// Must be called before the user stake changes, and followed by a weighted stake update.
+    pub fn settle_fee_rewards(&mut self, _stake_pool: &mut StakePool) -> Result<u64> {
+        let fee_rewards: u64 = self
+            .calculate_rewards_on_staked(self.fee_acc_reward, _stake_pool.fee_acc_reward)?
+            .safe_cast::<u64>()?
+            .safe_add(self.unclaimed_fee_owed)?;
+        self.fee_acc_reward = _stake_pool.fee_acc_reward;
+        self.unclaimed_fee_owed = 0;
+
+        self.staked = self.staked.safe_add(fee_rewards)?;
+        _stake_pool.total_staked = _stake_pool.total_staked.safe_add(fee_rewards)?;
+
+        Ok(fee_rewards)
+    }
+
+    /// Move the reward stream tokens and the redistributed unstake fees of the user out of the stored pool
+    /// accumulators into their pending and owed balances. A failing settlement owes nothing, so the emergency
+    /// withdrawal can't be blocked by broken reward math.
+    pub fn settle_emergency_rewards(&mut self, _stake_pool: &StakePool) {
+        for index in 0..MAX_REWARD_STREAMS {
+            let reward = self
+                .calculate_rewards(self.stream_acc_rewards[index], _stake_pool.stream_acc_rewards[index])
+                .and_then(|reward| reward.safe_cast())
+                .unwrap_or(0);
+
+            self.stream_pending_rewards[index] = self.stream_pending_rewards[index].saturating_add(reward);
+        }
+        self.stream_acc_rewards = _stake_pool.stream_acc_rewards;
+
+        let fee_rewards = self
+            .calculate_rewards_on_staked(self.fee_acc_reward, _stake_pool.fee_acc_reward)
+            .and_then(|fee_rewards| fee_rewards.safe_cast())
+            .unwrap_or(0);
+
+        self.unclaimed_fee_owed = self.unclaimed_fee_owed.saturating_add(fee_rewards);
+        self.fee_acc_reward = _stake_pool.fee_acc_reward;
+    }
+
+    /// Calculate the rewards of the user on their staked tokens, regardless of the lock multiplier.
+    fn calculate_rewards_on_staked(&self, _acc_reward: u128, _pool_acc_reward: u128) -> Result<u128> {
+        mul_div(_pool_acc_reward.safe_sub(_acc_reward)?, GlobalState::to_u128(self.staked), SCALE_FACTOR)
+    }
+}
+
+/// PDA to hold unstaked tokens of a user until the unbonding cooldown has passed.
+#[account]
+pub struct WithdrawalTicket {
+    /// Pubkey of user who owns the ticket.
+    pub user: Pubkey,
+    /// Pubkey of the stake pool the tokens were unstaked from.
+    pub pool: Pubkey,
+    /// Pubkey of the unstaked SPL token.
+    pub mint: Pubkey,
+    /// Sequence number of the ticket among the user tickets.
+    pub id: u64,
+    /// Amount of tokens that can be withdrawn.
+    pub amount: u64,
+    /// Unix timestamp after which the tokens can be withdrawn.
+    pub unlock_time: u64,
+}
+
+impl WithdrawalTicket {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8;
+    pub const SEEDS: &'static [u8] = b"withdrawal_ticket";
+}
+
+/// Privileged roles stored on the global state.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Changed only through the propose/accept handover, can not be granted or revoked.
+    Governance,
+    RewardManager,
+    Pauser,
+    Treasurer,
+}
+
+/// Governance parameter change that can be queued in the timelock.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum GovernanceAction {
+    UpdateWeightage {
+        stake_pool: Pubkey,
+        weightage: u64,
+    },
+    UpdateRewards {
+        reward: u64,
+        distribution_time: u64,
+        roll_over_stranded: bool,
+    },
+    UpdatePeriodEnd {
+        distribution_time: u64,
+    },
+    MigrateFunds {
+        new_version: Pubkey,
+        amount: u64,
+    },
+    UpdateTimelockDelay {
+        timelock_delay: u64,
+    },
+    ScheduleRewardCycle {
+        reward: u64,
+        distribution_time: u64,
+        start_time: u64,
+    },
+    EditRewardCycle {
+        start_time: u64,
+        reward: u64,
+        distribution_time: u64,
+        new_start_time: u64,
+    },
+    CancelRewardCycle {
+        start_time: u64,
+    },
+    FundDistribution {
+        distribution: Pubkey,
+    },
+    UpdateUnbondingCooldown {
+        unbonding_cooldown: u64,
+    },
+    UpdateUnstakeFee {
+        unstake_fee: UnstakeFee,
+    },
+    UpdateLockTiers {
+        lock_tiers: [LockTier; MAX_LOCK_TIERS],
+    },
+    WithdrawStrandedReward,
+}
+
+impl GovernanceAction {
+    /// Size of the largest variant along with the variant index.
+    pub const LEN: usize = 1 + LockTier::LEN * MAX_LOCK_TIERS;
+
+    /// Get the role that is allowed to queue the action.
+    pub fn get_role(&self) -> Role {
+        match self {
+            GovernanceAction::UpdateRewards { .. } |
+            GovernanceAction::UpdatePeriodEnd { .. } |
+            GovernanceAction::ScheduleRewardCycle { .. } |
+            GovernanceAction::EditRewardCycle { .. } |
+            GovernanceAction::CancelRewardCycle { .. } => Role::RewardManager,
+            GovernanceAction::MigrateFunds { .. } |
+            GovernanceAction::WithdrawStrandedReward => Role::Treasurer,
+            GovernanceAction::UpdateWeightage { .. } |
+            GovernanceAction::UpdateTimelockDelay { .. } |
+            GovernanceAction::FundDistribution { .. } |
+            GovernanceAction::UpdateUnbondingCooldown { .. } |
+            GovernanceAction::UpdateUnstakeFee { .. } |
+            GovernanceAction::UpdateLockTiers { .. } => Role::Governance,
+        }
+    }
+}
+
+/// PDA to store a queued governance action until it is executed or cancelled.
+#[account]
+pub struct TimelockAction {
+    /// Id of the action, used in the PDA seeds.
+    pub id: u64,
+    /// Role holder that queued the action and receives the rent back.
+    pub proposer: Pubkey,
+    /// Parameter change that will be applied on execution.
+    pub action: GovernanceAction,
+    /// Unix timestamp after which the action can be executed.
+    pub eta: u64,
+}
+
+impl TimelockAction {
+    pub const LEN: usize = 8 + 8 + 32 + GovernanceAction::LEN + 8;
+    pub const SEEDS: &'static [u8] = b"timelock_action";
+}
+
+/// PDA to store the future SOL reward cycles, started by the global reward update once the current cycle ends.
+#[account]
+pub struct RewardSchedule {
+    /// Future SOL reward cycles ordered by start time, empty slots at the end.
+    pub cycles: [ScheduledCycle; MAX_SCHEDULED_CYCLES],
+}
+
+impl RewardSchedule {
+    pub const LEN: usize = 8 + ScheduledCycle::LEN * MAX_SCHEDULED_CYCLES;
+    pub const SEEDS: &'static [u8] = b"reward_schedule";
+
+    /// Get the first scheduled cycle if it has started by the given time.
+    pub fn get_due_cycle(&self, _current_time: u64) -> Option<ScheduledCycle> {
+        self.cycles
+            .first()
+            .copied()
+            .filter(|scheduled_cycle| {
+                scheduled_cycle.is_queued() && scheduled_cycle.start_time <= _current_time
+            })
+    }
+
+    /// Remove the first scheduled cycle once it is started.
+    pub fn remove_first_cycle(&mut self) {
+        self.cycles.rotate_left(1);
+        self.cycles[MAX_SCHEDULED_CYCLES - 1] = ScheduledCycle::default();
+    }
+
+    /// Get the queued reward cycles, ordered by start time.
+    pub fn get_scheduled_cycles(&self) -> Vec<ScheduledCycle> {
+        self.cycles
+            .iter()
+            .copied()
+            .filter(|scheduled_cycle| scheduled_cycle.is_queued())
+            .collect()
+    }
+
+    /// Replace the queued reward cycles. The cycles are sorted by start time and must neither start before
+    /// the given time nor overlap each other or the current cycle ending at the given period end.
+    pub fn set_scheduled_cycles(
+        &mut self,
+        mut _scheduled_cycles: Vec<ScheduledCycle>,
+        _period_end_time: u64,
+        _current_time: u64
+    ) -> Result<()> {
+        if _scheduled_cycles.len() > MAX_SCHEDULED_CYCLES {
+            return Err(ErrorCode::RewardScheduleFull.into());
+        }
+
+        _scheduled_cycles.sort_by_key(|scheduled_cycle| scheduled_cycle.start_time);
+
+        let mut previous_end_time = _period_end_time.max(_current_time);
+
+        for scheduled_cycle in _scheduled_cycles.iter() {
+            if scheduled_cycle.start_time < previous_end_time {
+                return Err(ErrorCode::InvalidScheduledCycle.into());
+            }
+
+            previous_end_time = scheduled_cycle.get_end_time()?;
         }
+
+        self.cycles = [ScheduledCycle::default(); MAX_SCHEDULED_CYCLES];
+        self.cycles[.._scheduled_cycles.len()].copy_from_slice(&_scheduled_cycles);
+
+        Ok(())
+    }
+
+    /// Check if the current cycle, ending at the given period end, ends after the first scheduled cycle starts.
+    pub fn is_overlapping(&self, _period_end_time: u64) -> bool {
+        self.get_scheduled_cycles()
+            .first()
+            .is_some_and(|scheduled_cycle| scheduled_cycle.start_time < _period_end_time)
+    }
+}
+
+/// PDA to store a reward campaign while it holds a campaign slot on the global state.
+#[account]
+pub struct RewardCampaign {
+    /// Id of the campaign, used in the PDA seeds.
+    pub id: u64,
+    /// Pubkey that opened the campaign and receives the rent back, along with the stranded rewards
+    /// of a sponsored campaign.
+    pub creator: Pubkey,
+    /// Index of the campaign slot on the global state.
+    pub slot: u8,
+    /// Pubkey of the reward SPL token. Default pubkey means SOL.
+    pub reward_mint: Pubkey,
+    /// Amount of reward distributed over the campaign window.
+    pub reward: u64,
+    /// Unix timestamp when the campaign starts.
+    pub start_time: u64,
+    /// Unix timestamp when the campaign ends.
+    pub end_time: u64,
+}
+
+impl RewardCampaign {
+    pub const LEN: usize = 8 + 8 + 32 + 1 + 32 + 8 + 8 + 8;
+    pub const SEEDS: &'static [u8] = b"reward_campaign";
+}
+
+/// Maximum number of leaves of a merkle distribution, bounded by the size of its claimed bitmap.
+pub const MAX_DISTRIBUTION_LEAVES: u64 = 65_536;
+
+/// PDA to store a merkle distribution of SOL or SPL tokens, where each leaf is the keccak hash of
+/// `(index, user, amount)` with the little-endian index and amount.
+#[account]
+pub struct Distribution {
+    /// Id of the distribution, used in the PDA seeds.
+    pub id: u64,
+    /// Pubkey of the distributed SPL token, held by the distribution vault. Default pubkey means SOL.
+    pub mint: Pubkey,
+    /// Merkle root of the distribution leaves, with the pairs of nodes sorted before hashing.
+    pub merkle_root: [u8; 32],
+    /// Number of leaves, each leaf index has a bit in the claimed bitmap.
+    pub num_leaves: u64,
+    /// Amount distributed by all the leaves.
+    pub total_amount: u64,
+    /// Amount claimed up till now.
+    pub claimed_amount: u64,
+    /// Unix timestamp after which the distribution can not be claimed anymore and can be swept.
+    pub expiry_time: u64,
+    /// If set, the distributed amount is held or committed and can be claimed.
+    pub is_funded: bool,
+    /// Bitmap of the claimed leaf indexes.
+    pub claimed_bitmap: Vec<u8>,
+}
+
+impl Distribution {
+    pub const LEN: usize = 8 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 4;
+    pub const SEEDS: &'static [u8] = b"distribution";
+
+    /// Get the account space for the given number of leaves.
+    pub fn space(_num_leaves: u64) -> usize {
+        Distribution::LEN + (_num_leaves.div_ceil(8) as usize)
+    }
+
+    /// Check if the distribution can not be claimed anymore.
+    pub fn is_expired(&self, _current_time: u64) -> bool {
+        _current_time > self.expiry_time
+    }
+
+    /// Check if the leaf index has been claimed.
+    pub fn is_claimed(&self, _index: u64) -> Result<bool> {
+        let byte: usize = (_index / 8).safe_cast()?;
+        Ok(self.claimed_bitmap[byte] & (1 << (_index % 8)) != 0)
+    }
+
+    /// Mark the leaf index as claimed.
+    pub fn set_claimed(&mut self, _index: u64) -> Result<()> {
+        let byte: usize = (_index / 8).safe_cast()?;
+        self.claimed_bitmap[byte] |= 1 << (_index % 8);
+        Ok(())
     }
+
+    /// Verify the merkle proof of the leaf of the given user and amount against the merkle root.
+    pub fn verify_proof(&self, _index: u64, _user: Pubkey, _amount: u64, _proof: &[[u8; 32]]) -> bool {
+        let leaf = keccak::hashv(&[&_index.to_le_bytes(), _user.as_ref(), &_amount.to_le_bytes()]).0;
+
+        let root = _proof.iter().fold(leaf, |node, sibling| {
+            if node <= *sibling {
+                keccak::hashv(&[&node, sibling]).0
+            } else {
+                keccak::hashv(&[sibling, &node]).0
+            }
+        });
+
+        root == self.merkle_root
+    }
+
+    /// Get the amount that has not been claimed yet.
+    pub fn get_unclaimed_amount(&self) -> Result<u64> {
+        self.total_amount.safe_sub(self.claimed_amount)
+    }
+}
+
+/// Action the voter weight is used for, as defined by the SPL Governance voter weight add-in interface.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum VoterWeightAction {
+    CastVote,
+    CommentProposal,
+    CreateGovernance,
+    CreateProposal,
+    SignOffProposal,
+}
+
+/// PDA to store the voter weight of a user in an SPL Governance realm, laid out as the `VoterWeightRecord`
+/// of the voter weight add-in interface, whose discriminator is the one of the account name.
+#[account]
+pub struct VoterWeightRecord {
+    /// Pubkey of the realm the record belongs to.
+    pub realm: Pubkey,
+    /// Pubkey of the governing token mint of the realm.
+    pub governing_token_mint: Pubkey,
+    /// Pubkey of the user the voter weight belongs to.
+    pub governing_token_owner: Pubkey,
+    /// Stake of the user in the given stake pools, scaled by the weightage of each pool.
+    pub voter_weight: u64,
+    /// Slot after which the voter weight is stale. It expires at the slot of the update.
+    pub voter_weight_expiry: Option<u64>,
+    /// Action the voter weight is restricted to. None means any action.
+    pub weight_action: Option<VoterWeightAction>,
+    /// Target of the action the voter weight is restricted to. None means any target.
+    pub weight_action_target: Option<Pubkey>,
+    /// Reserved space of the interface.
+    pub reserved: [u8; 8],
+}
+
+impl VoterWeightRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + (1 + 8) + (1 + 1) + (1 + 32) + 8;
+    pub const SEEDS: &'static [u8] = b"voter-weight-record";
+}
+
+/// PDA to store the max voter weight of an SPL Governance realm, laid out as the `MaxVoterWeightRecord`
+/// of the voter weight add-in interface, whose discriminator is the one of the account name.
+#[account]
+pub struct MaxVoterWeightRecord {
+    /// Pubkey of the realm the record belongs to.
+    pub realm: Pubkey,
+    /// Pubkey of the governing token mint of the realm.
+    pub governing_token_mint: Pubkey,
+    /// Stake of all the stake pools, scaled by the weightage of each pool.
+    pub max_voter_weight: u64,
+    /// Slot after which the max voter weight is stale. It expires at the slot of the update.
+    pub max_voter_weight_expiry: Option<u64>,
+    /// Reserved space of the interface.
+    pub reserved: [u8; 8],
+}
+
+impl MaxVoterWeightRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + (1 + 8) + 8;
+    pub const SEEDS: &'static [u8] = b"max-voter-weight-record";
 }
 
-/// PDA to hold the ownership of reward SOLs, staked $MESH, and $indexMESH.
+/// PDA to hold the ownership of reward SOLs and staked tokens of all stake pools.
 #[account]
 pub struct FundsController {}
 
 impl FundsController {
-    pub const LEN: usize = 8 + 0;
+    pub const LEN: usize = 8;
     pub const SEEDS: &'static [u8] = b"funds_controller";
 }
@@ -0,0 +1,116 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import { assert } from 'chai';
+
+import { governanceKeypair } from './hooks';
+import { assertKeysEqual } from './genericTests';
+import { findEventAuthority } from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('governance handover', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const newGovernanceKeypair = anchor.web3.Keypair.generate();
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  async function proposeGovernance(
+    governance: anchor.web3.Keypair,
+    newGovernance: anchor.web3.PublicKey
+  ) {
+    await program.methods
+      .proposeGovernance(newGovernance)
+      .accounts({
+        governance: governance.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governance]) //signing txn by governance
+      .rpc();
+  }
+
+  async function acceptGovernance(pendingGovernance: anchor.web3.Keypair) {
+    await program.methods
+      .acceptGovernance()
+      .accounts({
+        pendingGovernance: pendingGovernance.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([pendingGovernance]) //signing txn by pending governance
+      .rpc();
+  }
+
+  it('Not allow others to propose governance', async () => {
+    try {
+      await program.methods
+        .proposeGovernance(newGovernanceKeypair.publicKey)
+        .accounts({
+          governance: program.provider.publicKey, //not the actual governance
+          globalState,
+          eventAuthority,
+          program: program.programId,
+        })
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Caller not governance';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Allow governance to propose and cancel', async () => {
+    await proposeGovernance(governanceKeypair, newGovernanceKeypair.publicKey);
+
+    let gs = await program.account.globalState.fetch(globalState);
+
+    assertKeysEqual(gs.governance, governanceKeypair.publicKey);
+    assertKeysEqual(gs.pendingGovernance, newGovernanceKeypair.publicKey);
+
+    await program.methods
+      .cancelGovernanceProposal()
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    gs = await program.account.globalState.fetch(globalState);
+
+    assertKeysEqual(gs.pendingGovernance, anchor.web3.PublicKey.default);
+  });
+
+  it('Not allow others to accept governance', async () => {
+    await proposeGovernance(governanceKeypair, newGovernanceKeypair.publicKey);
+
+    try {
+      await acceptGovernance(governanceKeypair); //not the pending governance
+    } catch (error) {
+      const errMsg = 'Caller not pending governance';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Allow pending governance to accept', async () => {
+    await acceptGovernance(newGovernanceKeypair);
+
+    const gs = await program.account.globalState.fetch(globalState);
+
+    assertKeysEqual(gs.governance, newGovernanceKeypair.publicKey);
+    assertKeysEqual(gs.pendingGovernance, anchor.web3.PublicKey.default);
+  });
+
+  after(async () => {
+    //give governance back to actual address
+    await proposeGovernance(newGovernanceKeypair, governanceKeypair.publicKey);
+    await acceptGovernance(governanceKeypair);
+  });
+});
@@ -1,6 +1,22 @@
 import './initialize';
-import './updateGovernance';
+import './governance';
 import './updateWeightage';
 import './updateRewards';
 import './stakeUnstakeClaim';
+import './lockTiers';
+import './withdrawalTickets';
+import './rewardStreams';
+import './token2022';
+import './pause';
 import './migrateFunds';
+import './roles';
+import './unstakeFee';
+import './strandedReward';
+import './rewardSchedule';
+import './rewardCampaigns';
+import './sponsoredCampaigns';
+import './merkleDistributor';
+import './stakeSnapshots';
+import './voterWeight';
+import './safeMode';
+import './timelock';
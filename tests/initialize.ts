@@ -1,19 +1,22 @@
 import * as anchor from '@coral-xyz/anchor';
-import { Program } from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
 import {
   TOKEN_PROGRAM_ID,
   getAssociatedTokenAddressSync,
   createAssociatedTokenAccountInstruction,
   getAccount,
 } from '@solana/spl-token';
+import { assert } from 'chai';
 
 import { meshMint, indexMeshMint, governanceKeypair } from './hooks';
 import { assertKeysEqual, assertBNEqual } from './genericTests';
 import { parseUnits } from '../utils/formatting';
+import { findEventAuthority, findStakePool } from '../utils/web3';
 import { MeshStaking } from '../target/types/mesh_staking';
 
 describe('initialize', () => {
   const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
 
   const connection = anchor.getProvider().connection;
 
@@ -22,6 +25,11 @@ describe('initialize', () => {
     program.programId
   );
 
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
   const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from('funds_controller')],
     program.programId
@@ -55,15 +63,20 @@ describe('initialize', () => {
     indexMeshMint
   );
 
-  const weightage = parseUnits(0.5);
+  const meshPool = findStakePool(program.programId, meshMint);
+  const indexMeshPool = findStakePool(program.programId, indexMeshMint);
+
+  const meshWeightage = parseUnits(1);
+  const indexMeshWeightage = parseUnits(0.5);
 
   it('initialize staking program', async () => {
     await program.methods
-      .initialize(governanceKeypair.publicKey, meshMint, indexMeshMint, weightage)
+      .initialize(governanceKeypair.publicKey)
       .preInstructions([createMeshATAix, createindexMeshATAix])
       .accounts({
         payer: program.provider.publicKey,
         globalState,
+        rewardSchedule,
         fundsController,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -72,9 +85,6 @@ describe('initialize', () => {
     const gs = await program.account.globalState.fetch(globalState);
 
     assertKeysEqual(gs.governance, governanceKeypair.publicKey);
-    assertKeysEqual(gs.meshMint, meshMint);
-    assertKeysEqual(gs.indexMeshMint, indexMeshMint);
-    assertBNEqual(gs.weightage, weightage);
 
     const [meshATA, indexMeshATA] = await Promise.all([
       getAccount(connection, meshVault),
@@ -87,4 +97,69 @@ describe('initialize', () => {
     assertKeysEqual(indexMeshATA.mint, indexMeshMint);
     assertKeysEqual(indexMeshATA.owner, fundsController);
   });
+
+  it('Not allow others to add stake pool', async () => {
+    try {
+      await program.methods
+        .addStakePool(meshWeightage)
+        .accounts({
+          governance: program.provider.publicKey, //not the actual governance
+          globalState,
+          rewardSchedule,
+          mint: meshMint,
+          stakePool: meshPool,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          eventAuthority,
+          program: program.programId,
+        })
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Caller not governance';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('add mesh & indexMesh stake pools', async () => {
+    await program.methods
+      .addStakePool(meshWeightage)
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        rewardSchedule,
+        mint: meshMint,
+        stakePool: meshPool,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    await program.methods
+      .addStakePool(indexMeshWeightage)
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        rewardSchedule,
+        mint: indexMeshMint,
+        stakePool: indexMeshPool,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const [gs, mp, imp] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.stakePool.fetch(meshPool),
+      program.account.stakePool.fetch(indexMeshPool),
+    ]);
+
+    assertBNEqual(gs.poolCount, 2);
+    assertKeysEqual(mp.mint, meshMint);
+    assertBNEqual(mp.weightage, meshWeightage);
+    assertKeysEqual(imp.mint, indexMeshMint);
+    assertBNEqual(imp.weightage, indexMeshWeightage);
+  });
 });
@@ -0,0 +1,283 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import { getAssociatedTokenAddressSync, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { assert } from 'chai';
+
+import { governanceKeypair, indexMeshMint, userTwo } from './hooks';
+import { assertBNEqual } from './genericTests';
+import { parseUnits } from '../utils/formatting';
+import {
+  findEventAuthority,
+  findStakePool,
+  findUserInfo,
+  getConfirmedTransaction,
+  getNextWithdrawalTicket,
+} from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('lock tiers', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const connection = anchor.getProvider().connection;
+
+  const day = 24 * 60 * 60;
+
+  const lockTiers = [
+    { duration: new anchor.BN(0), multiplier: parseUnits(1) },
+    { duration: new anchor.BN(30 * day), multiplier: parseUnits(1.1) },
+    { duration: new anchor.BN(90 * day), multiplier: parseUnits(1.25) },
+    { duration: new anchor.BN(180 * day), multiplier: parseUnits(1.5) },
+    { duration: new anchor.BN(365 * day), multiplier: parseUnits(2) },
+  ];
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  const indexMeshPool = findStakePool(program.programId, indexMeshMint);
+  const userTwoInfo = findUserInfo(program.programId, userTwo.publicKey, indexMeshMint);
+
+  const indexMeshVault = getAssociatedTokenAddressSync(
+    indexMeshMint,
+    fundsController,
+    true,
+    TOKEN_PROGRAM_ID
+  );
+
+  const userTwoIndexMeshATA = getAssociatedTokenAddressSync(
+    indexMeshMint,
+    userTwo.publicKey,
+    false,
+    TOKEN_PROGRAM_ID
+  );
+
+  it('Not allow others to update lock tiers', async () => {
+    try {
+      await program.methods
+        .updateLockTiers(lockTiers)
+        .accounts({
+          governance: program.provider.publicKey, //not the actual governance
+          globalState,
+          eventAuthority,
+          program: program.programId,
+        })
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      const errMsg = 'Caller not governance';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Not allow first tier to be locked', async () => {
+    try {
+      await program.methods
+        .updateLockTiers(lockTiers.slice(1))
+        .accounts({
+          governance: governanceKeypair.publicKey,
+          globalState,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      const errMsg = 'Invalid lock tier';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Set lock tiers', async () => {
+    await program.methods
+      .updateLockTiers(lockTiers)
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const gs = await program.account.globalState.fetch(globalState);
+
+    lockTiers.forEach((tier, index) => {
+      assertBNEqual(gs.lockTiers[index].duration, tier.duration);
+      assertBNEqual(gs.lockTiers[index].multiplier, tier.multiplier);
+    });
+  });
+
+  it('stake 10 indexMesh locked for 30 days by userTwo', async () => {
+    const [gsPrev, poolPrev] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.stakePool.fetch(indexMeshPool),
+    ]);
+
+    const stakeAmount = parseUnits(10);
+
+    const txId = await program.methods
+      .stake(stakeAmount, 1)
+      .accounts({
+        base: {
+          fundsController,
+          globalState,
+          rewardSchedule,
+          user: userTwo.publicKey,
+          userInfo: userTwoInfo,
+          stakePool: indexMeshPool,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        mint: indexMeshMint,
+        mintVault: indexMeshVault,
+        userMintTokenAccount: userTwoIndexMeshATA,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userTwo])
+      .rpc();
+
+    const [gs, ui] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.userInfo.fetch(userTwoInfo),
+    ]);
+    const pool = await program.account.stakePool.fetch(indexMeshPool);
+    const tx = await getConfirmedTransaction(connection, txId);
+
+    const weightedAmount = stakeAmount.mul(lockTiers[1].multiplier).div(parseUnits(1));
+
+    assertBNEqual(ui.staked, stakeAmount);
+    assertBNEqual(ui.weighted, weightedAmount);
+    assertBNEqual(ui.lockMultiplier, lockTiers[1].multiplier);
+    assertBNEqual(ui.lockEndTime, tx.blockTime + lockTiers[1].duration.toNumber());
+    assertBNEqual(pool.totalWeighted, poolPrev.totalWeighted.add(weightedAmount));
+    assertBNEqual(
+      gs.totalWeightedStake,
+      gsPrev.totalWeightedStake.add(weightedAmount.mul(pool.weightage).div(parseUnits(1)))
+    );
+  });
+
+  it('Not allow unstake before lock ends', async () => {
+    const withdrawalTicket = await getNextWithdrawalTicket(program, userTwoInfo);
+
+    try {
+      await program.methods
+        .unstake(parseUnits(10), false)
+        .accounts({
+          base: {
+            fundsController,
+            globalState,
+            rewardSchedule,
+            user: userTwo.publicKey,
+            userInfo: userTwoInfo,
+            stakePool: indexMeshPool,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          },
+          withdrawalTicket,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([userTwo])
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      const errMsg = 'Stake is locked';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Not allow emergency unstake before lock ends', async () => {
+    const withdrawalTicket = await getNextWithdrawalTicket(program, userTwoInfo);
+
+    try {
+      await program.methods
+        .unstake(parseUnits(10), true)
+        .accounts({
+          base: {
+            fundsController,
+            globalState,
+            rewardSchedule,
+            user: userTwo.publicKey,
+            userInfo: userTwoInfo,
+            stakePool: indexMeshPool,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          },
+          withdrawalTicket,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([userTwo])
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      const errMsg = 'Stake is locked';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Not allow downgrading an active lock', async () => {
+    try {
+      await program.methods
+        .stake(parseUnits(1), 0)
+        .accounts({
+          base: {
+            fundsController,
+            globalState,
+            rewardSchedule,
+            user: userTwo.publicKey,
+            userInfo: userTwoInfo,
+            stakePool: indexMeshPool,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          },
+          mint: indexMeshMint,
+          mintVault: indexMeshVault,
+          userMintTokenAccount: userTwoIndexMeshATA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([userTwo])
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      const errMsg = 'Lock tier lower than current lock';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Not allow releasing a lock before it ends', async () => {
+    try {
+      await program.methods
+        .releaseLock()
+        .accounts({
+          caller: program.provider.publicKey,
+          userInfo: userTwoInfo,
+          stakePool: indexMeshPool,
+          globalState,
+          rewardSchedule,
+          eventAuthority,
+          program: program.programId,
+        })
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      const errMsg = 'No expired lock to release';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+});
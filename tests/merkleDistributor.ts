@@ -0,0 +1,264 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from '@solana/spl-token';
+import { assert } from 'chai';
+
+import { governanceKeypair, userOne, userTwo } from './hooks';
+import { assertBNEqual } from './genericTests';
+import { delay } from '../utils';
+import { buildMerkleTree, DistributionLeaf } from '../utils/merkle';
+import { findEventAuthority } from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('merkle distributor', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+  const connection = anchor.getProvider().connection;
+
+  const leaves: DistributionLeaf[] = [
+    { user: userOne.publicKey, amount: new anchor.BN(1_000_000) },
+    { user: userTwo.publicKey, amount: new anchor.BN(2_000_000) },
+  ];
+  const totalAmount = new anchor.BN(3_000_000);
+  const tree = buildMerkleTree(leaves);
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  let solDistribution: anchor.web3.PublicKey;
+
+  function findDistribution(id: anchor.BN) {
+    const [distribution] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('distribution'), id.toArrayLike(Buffer, 'le', 8)],
+      program.programId
+    );
+
+    return distribution;
+  }
+
+  async function getCurrentTime() {
+    return connection.getBlockTime(await connection.getSlot());
+  }
+
+  async function claimDistribution(
+    distribution: anchor.web3.PublicKey,
+    index: number,
+    amount: anchor.BN,
+    tokenAccounts?: { distributionVault: anchor.web3.PublicKey; userTokenAccount: anchor.web3.PublicKey }
+  ) {
+    await program.methods
+      .claimDistribution(new anchor.BN(index), amount, tree.getProof(index))
+      .accounts({
+        user: userOne.publicKey,
+        globalState,
+        distribution,
+        fundsController,
+        distributionVault: tokenAccounts ? tokenAccounts.distributionVault : null,
+        userTokenAccount: tokenAccounts ? tokenAccounts.userTokenAccount : null,
+        tokenProgram: tokenAccounts ? TOKEN_PROGRAM_ID : null,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userOne])
+      .rpc();
+  }
+
+  async function sweepDistribution(distribution: anchor.web3.PublicKey) {
+    await program.methods
+      .sweepDistribution()
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        distribution,
+        distributionVault: null,
+        governanceTokenAccount: null,
+        tokenProgram: null,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+  }
+
+  it('Publish and fund a SOL distribution', async () => {
+    const gsBefore = await program.account.globalState.fetch(globalState);
+    solDistribution = findDistribution(gsBefore.nextDistributionId);
+    const expiryTime = new anchor.BN((await getCurrentTime()) + 10);
+
+    await program.methods
+      .fundRewards(totalAmount)
+      .accounts({
+        funder: governanceKeypair.publicKey,
+        globalState,
+        fundsController,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    await program.methods
+      .createDistribution(tree.root, new anchor.BN(leaves.length), totalAmount, expiryTime)
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        distribution: solDistribution,
+        rewardMint: null,
+        distributionVault: null,
+        governanceTokenAccount: null,
+        tokenProgram: null,
+        associatedTokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    try {
+      await claimDistribution(solDistribution, 0, leaves[0].amount);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'DistributionNotFunded');
+    }
+
+    await program.methods
+      .fundDistribution()
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        distribution: solDistribution,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const [gs, distribution] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.distribution.fetch(solDistribution),
+    ]);
+
+    assert.isTrue(distribution.isFunded);
+    assertBNEqual(gs.nextDistributionId, gsBefore.nextDistributionId.addn(1));
+    assertBNEqual(gs.totalDistributedReward, gsBefore.totalDistributedReward.add(totalAmount));
+  });
+
+  it('Not allow a claim with an invalid proof', async () => {
+    try {
+      await claimDistribution(solDistribution, 0, leaves[1].amount);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidMerkleProof');
+    }
+  });
+
+  it('Claim a leaf only once', async () => {
+    const balanceBefore = await connection.getBalance(userOne.publicKey);
+
+    await claimDistribution(solDistribution, 0, leaves[0].amount);
+
+    const [balanceAfter, distribution] = await Promise.all([
+      connection.getBalance(userOne.publicKey),
+      program.account.distribution.fetch(solDistribution),
+    ]);
+
+    assert.equal(balanceAfter - balanceBefore, leaves[0].amount.toNumber());
+    assertBNEqual(distribution.claimedAmount, leaves[0].amount);
+
+    try {
+      await claimDistribution(solDistribution, 0, leaves[0].amount);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'DistributionAlreadyClaimed');
+    }
+  });
+
+  it('Sweep the unclaimed SOLs once the distribution expires', async () => {
+    try {
+      await sweepDistribution(solDistribution);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'DistributionNotExpired');
+    }
+
+    const gsBefore = await program.account.globalState.fetch(globalState);
+
+    await delay(12 * 1000);
+    await sweepDistribution(solDistribution);
+
+    const [gs, distributionAccount] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      connection.getAccountInfo(solDistribution),
+    ]);
+
+    // the unclaimed SOLs are released for future rewards
+    assertBNEqual(gs.totalDistributedReward, gsBefore.totalDistributedReward.sub(leaves[1].amount));
+    assert.isNull(distributionAccount);
+  });
+
+  it('Publish a token distribution and claim it from the distribution vault', async () => {
+    const gs = await program.account.globalState.fetch(globalState);
+    const distribution = findDistribution(gs.nextDistributionId);
+    const expiryTime = new anchor.BN((await getCurrentTime()) + 60);
+
+    const rewardMint = await createMint(connection, governanceKeypair, governanceKeypair.publicKey, null, 9);
+    const distributionVault = getAssociatedTokenAddressSync(rewardMint, distribution, true, TOKEN_PROGRAM_ID);
+    const [governanceTokenAccount, userTokenAccount] = await Promise.all([
+      createAssociatedTokenAccount(connection, governanceKeypair, rewardMint, governanceKeypair.publicKey),
+      createAssociatedTokenAccount(connection, governanceKeypair, rewardMint, userOne.publicKey),
+    ]);
+    await mintTo(
+      connection,
+      governanceKeypair,
+      rewardMint,
+      governanceTokenAccount,
+      governanceKeypair,
+      BigInt(totalAmount.toString())
+    );
+
+    await program.methods
+      .createDistribution(tree.root, new anchor.BN(leaves.length), totalAmount, expiryTime)
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        distribution,
+        rewardMint,
+        distributionVault,
+        governanceTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    await claimDistribution(distribution, 0, leaves[0].amount, { distributionVault, userTokenAccount });
+
+    const [vault, userAccount] = await Promise.all([
+      getAccount(connection, distributionVault),
+      getAccount(connection, userTokenAccount),
+    ]);
+
+    assert.equal(userAccount.amount, BigInt(leaves[0].amount.toString()));
+    assert.equal(vault.amount, BigInt(leaves[1].amount.toString()));
+  });
+});
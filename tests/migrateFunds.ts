@@ -3,11 +3,14 @@ import { Program, AnchorError } from '@coral-xyz/anchor';
 import { assert } from 'chai';
 
 import { governanceKeypair } from './hooks';
+import { assertBNEqual } from './genericTests';
 import { parseUnits } from '../utils/formatting';
+import { findEventAuthority } from '../utils/web3';
 import { MeshStaking } from '../target/types/mesh_staking';
 
 describe('migrateFunds', () => {
   const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
   const connection = anchor.getProvider().connection;
 
   const newVersion = anchor.web3.Keypair.generate();
@@ -22,62 +25,92 @@ describe('migrateFunds', () => {
     program.programId
   );
 
-  it('migrate funds more than rent exempt', async () => {
+  it('Not allow migrating the whole balance', async () => {
     const fcPrevSOlBalance = await connection.getBalance(fundsController);
 
     try {
       await program.methods
         .migrateFunds(new anchor.BN(fcPrevSOlBalance))
         .accounts({
-          governance: governanceKeypair.publicKey,
+          treasurer: governanceKeypair.publicKey,
           newVersion: newVersion.publicKey,
           globalState,
           fundsController,
+          eventAuthority,
+          program: program.programId,
         })
         .signers([governanceKeypair]) //signing txn by governance
         .rpc();
     } catch (error) {
-      const errMsg = 'Insufficient funds';
+      // the balance always holds the rent and the SOLs committed to rewards
+      const errMsg = 'Reward exceeds funded balance';
       assert.equal((error as AnchorError).error.errorMessage, errMsg);
     }
   });
 
-  it('migrate some SOL', async () => {
-    const fcPrevSOlBalance = await connection.getBalance(fundsController);
+  it('Not allow migrating the SOLs committed to rewards', async () => {
+    const gs = await program.account.globalState.fetch(globalState);
+    const uncommitted = gs.totalFunded.sub(gs.totalDistributedReward);
 
+    try {
+      await program.methods
+        .migrateFunds(uncommitted.addn(1))
+        .accounts({
+          treasurer: governanceKeypair.publicKey,
+          newVersion: newVersion.publicKey,
+          globalState,
+          fundsController,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair]) //signing txn by governance
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'RewardExceedsFunds');
+    }
+  });
+
+  it('migrate the funded SOL that is not committed', async () => {
     const amount = parseUnits(5);
+
     await program.methods
-      .migrateFunds(amount)
+      .fundRewards(amount)
       .accounts({
-        governance: governanceKeypair.publicKey,
-        newVersion: newVersion.publicKey,
+        funder: governanceKeypair.publicKey,
         globalState,
         fundsController,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
       })
-      .signers([governanceKeypair]) //signing txn by governance
+      .signers([governanceKeypair])
       .rpc();
 
-    const fcNewSOlBalance = await connection.getBalance(fundsController);
-    assert.equal(fcPrevSOlBalance, new anchor.BN(fcNewSOlBalance).add(amount).toNumber());
-  });
-
-  it('migrate all possible SOL', async () => {
-    const fcAccount = await connection.getAccountInfo(fundsController);
-    const rent = await connection.getMinimumBalanceForRentExemption(fcAccount.data.length);
-    const fcPrevSOlBalance = await connection.getBalance(fundsController);
+    const [gsPrev, fcPrevSOlBalance] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      connection.getBalance(fundsController),
+    ]);
 
     await program.methods
-      .migrateFunds(new anchor.BN(fcPrevSOlBalance - (rent + 1)))
+      .migrateFunds(amount)
       .accounts({
-        governance: governanceKeypair.publicKey,
+        treasurer: governanceKeypair.publicKey,
         newVersion: newVersion.publicKey,
         globalState,
         fundsController,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([governanceKeypair]) //signing txn by governance
       .rpc();
 
-    const fcNewSOlBalance = await connection.getBalance(fundsController);
-    assert.equal(fcNewSOlBalance, rent + 1);
+    const [gs, fcNewSOlBalance] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      connection.getBalance(fundsController),
+    ]);
+
+    assert.equal(fcPrevSOlBalance, new anchor.BN(fcNewSOlBalance).add(amount).toNumber());
+    assertBNEqual(gs.totalFunded, gsPrev.totalFunded.sub(amount));
   });
 });
@@ -0,0 +1,160 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import { TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { assert } from 'chai';
+
+import { governanceKeypair, meshMint, userOne } from './hooks';
+import { assertBNEqual } from './genericTests';
+import { parseUnits } from '../utils/formatting';
+import {
+  findEventAuthority,
+  findStakePool,
+  findUserInfo,
+  getNextWithdrawalTicket,
+} from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+const PAUSE_UNSTAKE = 1 << 1;
+const PAUSE_CLAIM = 1 << 2;
+
+describe('pause', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  const meshPool = findStakePool(program.programId, meshMint);
+  const userOneInfo = findUserInfo(program.programId, userOne.publicKey, meshMint);
+
+  async function unstake(isEmergency: boolean) {
+    const withdrawalTicket = await getNextWithdrawalTicket(program, userOneInfo);
+
+    return program.methods
+      .unstake(parseUnits(1), isEmergency)
+      .accounts({
+        base: {
+          fundsController,
+          globalState,
+          rewardSchedule,
+          user: userOne.publicKey,
+          userInfo: userOneInfo,
+          stakePool: meshPool,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        withdrawalTicket,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userOne])
+      .rpc();
+  }
+
+  it('Not allow others to pause', async () => {
+    try {
+      await program.methods
+        .updatePauseFlags(PAUSE_CLAIM)
+        .accounts({
+          pauser: program.provider.publicKey, //not the actual pauser
+          globalState,
+          eventAuthority,
+          program: program.programId,
+        })
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Caller not pauser';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Pause unstake and claim', async () => {
+    await program.methods
+      .updatePauseFlags(PAUSE_UNSTAKE | PAUSE_CLAIM)
+      .accounts({
+        pauser: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const gs = await program.account.globalState.fetch(globalState);
+
+    assert.equal(gs.pauseFlags, PAUSE_UNSTAKE | PAUSE_CLAIM);
+  });
+
+  it('Not allow claim while paused', async () => {
+    try {
+      await program.methods
+        .claim()
+        .accounts({
+          user: userOne.publicKey,
+          globalState,
+          rewardSchedule,
+          fundsController,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          eventAuthority,
+          program: program.programId,
+        })
+        .remainingAccounts([
+          { pubkey: meshPool, isSigner: false, isWritable: true },
+          { pubkey: userOneInfo, isSigner: false, isWritable: true },
+        ])
+        .signers([userOne])
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Instruction is paused';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Not allow unstake while paused', async () => {
+    try {
+      await unstake(false);
+    } catch (error) {
+      const errMsg = 'Instruction is paused';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('allow emergency unstake while paused', async () => {
+    const uiPrev = await program.account.userInfo.fetch(userOneInfo);
+
+    await unstake(true);
+
+    const ui = await program.account.userInfo.fetch(userOneInfo);
+
+    assertBNEqual(ui.staked, uiPrev.staked.sub(parseUnits(1)));
+  });
+
+  it('Unpause all instructions', async () => {
+    await program.methods
+      .updatePauseFlags(0)
+      .accounts({
+        pauser: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const gs = await program.account.globalState.fetch(globalState);
+
+    assert.equal(gs.pauseFlags, 0);
+  });
+});
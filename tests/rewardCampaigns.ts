@@ -0,0 +1,148 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import { assert } from 'chai';
+
+import { governanceKeypair } from './hooks';
+import { assertBNEqual, assertKeyDefault, assertKeysEqual } from './genericTests';
+import { delay } from '../utils';
+import { parseUnits } from '../utils/formatting';
+import { findEventAuthority } from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('reward campaigns', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+  const connection = anchor.getProvider().connection;
+
+  const reward = new anchor.BN(1_000_000);
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  function findRewardCampaign(id: anchor.BN) {
+    const [rewardCampaign] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('reward_campaign'), id.toArrayLike(Buffer, 'le', 8)],
+      program.programId
+    );
+
+    return rewardCampaign;
+  }
+
+  async function getCurrentTime() {
+    return connection.getBlockTime(await connection.getSlot());
+  }
+
+  async function openRewardCampaign(startTime: anchor.BN, endTime: anchor.BN) {
+    const gs = await program.account.globalState.fetch(globalState);
+    const rewardCampaign = findRewardCampaign(gs.nextCampaignId);
+
+    await program.methods
+      .openRewardCampaign(reward, startTime, endTime)
+      .accounts({
+        rewardManager: governanceKeypair.publicKey,
+        globalState,
+        rewardSchedule,
+        rewardCampaign,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    return rewardCampaign;
+  }
+
+  async function closeRewardCampaign(rewardCampaign: anchor.web3.PublicKey) {
+    await program.methods
+      .closeRewardCampaign()
+      .accounts({
+        closer: governanceKeypair.publicKey,
+        creator: governanceKeypair.publicKey,
+        globalState,
+        rewardSchedule,
+        rewardCampaign,
+        fundsController,
+        rewardVault: null,
+        creatorTokenAccount: null,
+        tokenProgram: null,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+  }
+
+  it('Not allow a campaign that starts after the maximum delay', async () => {
+    const startTime = new anchor.BN((await getCurrentTime()) + 31 * 24 * 60 * 60);
+
+    try {
+      await openRewardCampaign(startTime, startTime.addn(100));
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidCampaignWindow');
+    }
+  });
+
+  it('Not allow a campaign that ends before it starts', async () => {
+    const startTime = new anchor.BN((await getCurrentTime()) + 10);
+
+    try {
+      await openRewardCampaign(startTime, startTime);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidCampaignWindow');
+    }
+  });
+
+  it('Open a campaign alongside the reward cycle and close it once it ends', async () => {
+    const gsBefore = await program.account.globalState.fetch(globalState);
+    const startTime = new anchor.BN((await getCurrentTime()) + 1);
+    const endTime = startTime.addn(5);
+
+    const rewardCampaign = await openRewardCampaign(startTime, endTime);
+
+    const [gs, campaign] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.rewardCampaign.fetch(rewardCampaign),
+    ]);
+    const slot = gs.rewardCampaigns[campaign.slot];
+
+    assertKeysEqual(slot.campaign, rewardCampaign);
+    assertBNEqual(slot.reward, reward);
+    assertBNEqual(slot.endTime, endTime);
+    assertBNEqual(gs.nextCampaignId, gsBefore.nextCampaignId.addn(1));
+    assertBNEqual(gs.totalDistributedReward, gsBefore.totalDistributedReward.add(reward));
+
+    try {
+      await closeRewardCampaign(rewardCampaign);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'RewardCampaignNotEnded');
+    }
+
+    await delay(8 * 1000);
+    await closeRewardCampaign(rewardCampaign);
+
+    const [gsAfter, campaignAccount] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      connection.getAccountInfo(rewardCampaign),
+    ]);
+
+    // the campaign rewards were accumulated with the SOL rewards while it was running
+    assert.isTrue(gsAfter.globalAccReward.gt(gs.globalAccReward));
+    assertKeyDefault(gsAfter.rewardCampaigns[campaign.slot].campaign);
+    assert.isNull(campaignAccount);
+  });
+});
@@ -0,0 +1,188 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import { assert } from 'chai';
+
+import { governanceKeypair } from './hooks';
+import { assertBNEqual } from './genericTests';
+import { delay } from '../utils';
+import { parseUnits } from '../utils/formatting';
+import { findEventAuthority } from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('reward schedule', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+  const connection = anchor.getProvider().connection;
+
+  const reward = new anchor.BN(1_000_000);
+  const distributionTime = new anchor.BN(100);
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  const rewardManagerAccounts = {
+    rewardManager: governanceKeypair.publicKey,
+    globalState,
+    rewardSchedule,
+    eventAuthority,
+    program: program.programId,
+  };
+
+  async function scheduleRewardCycle(startTime: anchor.BN, cycleReward: anchor.BN = reward) {
+    await program.methods
+      .scheduleRewardCycle(cycleReward, distributionTime, startTime)
+      .accounts(rewardManagerAccounts)
+      .signers([governanceKeypair])
+      .rpc();
+  }
+
+  before(async () => {
+    // End the current cycle shortly, so the scheduled cycles can start during the tests.
+    await program.methods
+      .updatePeriodEnd(new anchor.BN(5))
+      .accounts(rewardManagerAccounts)
+      .signers([governanceKeypair])
+      .rpc();
+
+    await program.methods
+      .fundRewards(parseUnits(1))
+      .accounts({
+        funder: governanceKeypair.publicKey,
+        globalState,
+        fundsController,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+  });
+
+  it('Not allow a cycle that overlaps the current cycle', async () => {
+    const gs = await program.account.globalState.fetch(globalState);
+
+    try {
+      await scheduleRewardCycle(gs.periodEndTime.subn(1));
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidScheduledCycle');
+    }
+  });
+
+  it('Schedule, edit and cancel a cycle', async () => {
+    const gsBefore = await program.account.globalState.fetch(globalState);
+    const startTime = gsBefore.periodEndTime.addn(1_000);
+
+    await scheduleRewardCycle(startTime);
+
+    let gs = await program.account.globalState.fetch(globalState);
+    let schedule = await program.account.rewardSchedule.fetch(rewardSchedule);
+    assertBNEqual(schedule.cycles[0].startTime, startTime);
+    assertBNEqual(gs.totalDistributedReward, gsBefore.totalDistributedReward.add(reward));
+
+    try {
+      await scheduleRewardCycle(startTime);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'DuplicateScheduledCycle');
+    }
+
+    try {
+      await scheduleRewardCycle(startTime.add(distributionTime).subn(1));
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidScheduledCycle');
+    }
+
+    const newStartTime = startTime.addn(1_000);
+
+    await program.methods
+      .editRewardCycle(startTime, reward.muln(2), distributionTime, newStartTime)
+      .accounts(rewardManagerAccounts)
+      .signers([governanceKeypair])
+      .rpc();
+
+    gs = await program.account.globalState.fetch(globalState);
+    schedule = await program.account.rewardSchedule.fetch(rewardSchedule);
+    assertBNEqual(schedule.cycles[0].startTime, newStartTime);
+    assertBNEqual(schedule.cycles[0].reward, reward.muln(2));
+    assertBNEqual(gs.totalDistributedReward, gsBefore.totalDistributedReward.add(reward.muln(2)));
+
+    await program.methods
+      .cancelRewardCycle(newStartTime)
+      .accounts(rewardManagerAccounts)
+      .signers([governanceKeypair])
+      .rpc();
+
+    gs = await program.account.globalState.fetch(globalState);
+    schedule = await program.account.rewardSchedule.fetch(rewardSchedule);
+    assertBNEqual(schedule.cycles[0].distributionTime, 0);
+    assertBNEqual(gs.totalDistributedReward, gsBefore.totalDistributedReward);
+  });
+
+  it('Not accrue rewards while no cycle is running', async () => {
+    const gs = await program.account.globalState.fetch(globalState);
+    const farStartTime = gs.periodEndTime.addn(100_000);
+
+    // Each schedule update accumulates the rewards up to the current time.
+    async function updateRewardPerShare() {
+      await scheduleRewardCycle(farStartTime);
+      await program.methods
+        .cancelRewardCycle(farStartTime)
+        .accounts(rewardManagerAccounts)
+        .signers([governanceKeypair])
+        .rpc();
+
+      return program.account.globalState.fetch(globalState);
+    }
+
+    await delay(6 * 1000);
+    const gsIdle = await updateRewardPerShare();
+
+    await delay(2 * 1000);
+    const gsAfter = await updateRewardPerShare();
+
+    assertBNEqual(gsAfter.globalAccReward, gsIdle.globalAccReward);
+    assertBNEqual(gsAfter.lastUpdatedTime, gs.periodEndTime);
+  });
+
+  it('Start the scheduled cycle once the current cycle ends', async () => {
+    const gsBefore = await program.account.globalState.fetch(globalState);
+    const currentTime = await connection.getBlockTime(await connection.getSlot());
+    const startTime = anchor.BN.max(gsBefore.periodEndTime, new anchor.BN(currentTime)).addn(3);
+
+    await scheduleRewardCycle(startTime);
+    await delay((startTime.toNumber() - currentTime + 3) * 1000);
+
+    // Any reward update starts the due cycle, here queueing the next one.
+    const nextStartTime = startTime.add(distributionTime).addn(1_000);
+    await scheduleRewardCycle(nextStartTime);
+
+    const [gs, schedule] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.rewardSchedule.fetch(rewardSchedule),
+    ]);
+    assertBNEqual(gs.reward, reward);
+    assertBNEqual(gs.distributionTime, distributionTime);
+    assertBNEqual(gs.periodEndTime, startTime.add(distributionTime));
+    assertBNEqual(schedule.cycles[0].startTime, nextStartTime);
+
+    await program.methods
+      .cancelRewardCycle(nextStartTime)
+      .accounts(rewardManagerAccounts)
+      .signers([governanceKeypair])
+      .rpc();
+  });
+});
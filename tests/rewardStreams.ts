@@ -0,0 +1,252 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import {
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from '@solana/spl-token';
+import { assert } from 'chai';
+
+import { governanceKeypair, meshMint, userOne } from './hooks';
+import { assertBNEqual, assertKeysEqual } from './genericTests';
+import { delay } from '../utils';
+import { formatUnits, parseUnits } from '../utils/formatting';
+import { findEventAuthority, findStakePool, findUserInfo, userPedningRewards } from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('reward streams', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const connection = anchor.getProvider().connection;
+
+  const streamReward = parseUnits(1000);
+  const distributionTime = new anchor.BN(60);
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  const meshPool = findStakePool(program.programId, meshMint);
+  const userOneInfo = findUserInfo(program.programId, userOne.publicKey, meshMint);
+
+  let rewardMint: anchor.web3.PublicKey;
+  let rewardVault: anchor.web3.PublicKey;
+  let userOneRewardATA: anchor.web3.PublicKey;
+  let governanceRewardATA: anchor.web3.PublicKey;
+
+  before(async () => {
+    rewardMint = await createMint(connection, governanceKeypair, governanceKeypair.publicKey, null, 9);
+    [rewardVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('stream_vault'), rewardMint.toBuffer()],
+      program.programId
+    );
+    userOneRewardATA = await createAssociatedTokenAccount(
+      connection,
+      governanceKeypair,
+      rewardMint,
+      userOne.publicKey
+    );
+    governanceRewardATA = await createAssociatedTokenAccount(
+      connection,
+      governanceKeypair,
+      rewardMint,
+      governanceKeypair.publicKey
+    );
+  });
+
+  it('Not allow others to open a reward stream', async () => {
+    try {
+      await program.methods
+        .addRewardStream()
+        .accounts({
+          governance: program.provider.publicKey, //not the actual governance
+          globalState,
+          fundsController,
+          rewardMint,
+          rewardVault,
+          eventAuthority,
+          program: program.programId,
+        })
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Caller not governance';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Open a reward stream', async () => {
+    await program.methods
+      .addRewardStream()
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        fundsController,
+        rewardMint,
+        rewardVault,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const gs = await program.account.globalState.fetch(globalState);
+
+    assertKeysEqual(gs.rewardStreams[0].mint, rewardMint);
+    assertBNEqual(gs.rewardStreams[0].reward, 0);
+  });
+
+  it('Not allow to open the same reward stream twice', async () => {
+    try {
+      await program.methods
+        .addRewardStream()
+        .accounts({
+          governance: governanceKeypair.publicKey,
+          globalState,
+          fundsController,
+          rewardMint,
+          rewardVault,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Reward stream already opened';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Not allow to update rewards of a stream that is not opened', async () => {
+    try {
+      await program.methods
+        .updateStreamRewards(1, streamReward, distributionTime)
+        .accounts({
+          rewardManager: governanceKeypair.publicKey,
+          globalState,
+          rewardSchedule,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Invalid reward stream';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Not allow stream rewards higher than the funded tokens', async () => {
+    try {
+      await program.methods
+        .updateStreamRewards(0, streamReward, distributionTime)
+        .accounts({
+          rewardManager: governanceKeypair.publicKey,
+          globalState,
+          rewardSchedule,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'RewardExceedsFunds');
+    }
+  });
+
+  it('Fund the vault and update stream rewards', async () => {
+    await mintTo(
+      connection,
+      governanceKeypair,
+      rewardMint,
+      governanceRewardATA,
+      governanceKeypair,
+      BigInt(streamReward.toString())
+    );
+
+    await program.methods
+      .fundStreamRewards(0, streamReward)
+      .accounts({
+        funder: governanceKeypair.publicKey,
+        globalState,
+        rewardVault,
+        funderTokenAccount: governanceRewardATA,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    await program.methods
+      .updateStreamRewards(0, streamReward, distributionTime)
+      .accounts({
+        rewardManager: governanceKeypair.publicKey,
+        globalState,
+        rewardSchedule,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const gs = await program.account.globalState.fetch(globalState);
+
+    assertBNEqual(gs.rewardStreams[0].reward, streamReward);
+    assertBNEqual(gs.rewardStreams[0].distributionTime, distributionTime);
+    assertBNEqual(gs.rewardStreams[0].totalDistributedReward, streamReward);
+    assertBNEqual(gs.rewardStreams[0].totalFunded, streamReward);
+  });
+
+  it('claim stream rewards by userOne', async () => {
+    await delay(5 * 1000);
+
+    const { streamRewards } = await userPedningRewards(program, userOne, [meshMint]);
+
+    assert.equal(streamRewards.length, 1);
+    assertKeysEqual(streamRewards[0].mint, rewardMint);
+    assert.isAbove(streamRewards[0].reward, 0);
+
+    await program.methods
+      .claim()
+      .accounts({
+        user: userOne.publicKey,
+        globalState,
+        rewardSchedule,
+        fundsController,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
+      })
+      .remainingAccounts([
+        { pubkey: meshPool, isSigner: false, isWritable: true },
+        { pubkey: userOneInfo, isSigner: false, isWritable: true },
+        { pubkey: rewardVault, isSigner: false, isWritable: true },
+        { pubkey: userOneRewardATA, isSigner: false, isWritable: true },
+      ])
+      .signers([userOne])
+      .rpc();
+
+    const [ui, userRewardAccount] = await Promise.all([
+      program.account.userInfo.fetch(userOneInfo),
+      getAccount(connection, userOneRewardATA),
+    ]);
+
+    assertBNEqual(ui.streamPendingRewards[0], 0);
+    assert.isAtLeast(formatUnits(Number(userRewardAccount.amount)), streamRewards[0].reward);
+  });
+});
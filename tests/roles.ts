@@ -0,0 +1,104 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import { assert } from 'chai';
+
+import { governanceKeypair } from './hooks';
+import { assertKeysEqual } from './genericTests';
+import { findEventAuthority } from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('roles', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const pauserKeypair = anchor.web3.Keypair.generate();
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  async function updatePauseFlags(pauser: anchor.web3.Keypair) {
+    await program.methods
+      .updatePauseFlags(0)
+      .accounts({
+        pauser: pauser.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([pauser])
+      .rpc();
+  }
+
+  type Role = Parameters<typeof program.methods.grantRole>[0];
+
+  async function grantRole(role: Role, account: anchor.web3.PublicKey) {
+    await program.methods
+      .grantRole(role, account)
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair]) //signing txn by governance
+      .rpc();
+  }
+
+  it('Roles are held by governance after initialization', async () => {
+    const gs = await program.account.globalState.fetch(globalState);
+
+    assertKeysEqual(gs.rewardManager, governanceKeypair.publicKey);
+    assertKeysEqual(gs.pauser, governanceKeypair.publicKey);
+    assertKeysEqual(gs.treasurer, governanceKeypair.publicKey);
+  });
+
+  it('Not allow granting the governance role', async () => {
+    try {
+      await grantRole({ governance: {} }, pauserKeypair.publicKey);
+    } catch (error) {
+      const errMsg = 'Role can not be granted or revoked';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Grant pauser role to a separate wallet', async () => {
+    await grantRole({ pauser: {} }, pauserKeypair.publicKey);
+
+    const gs = await program.account.globalState.fetch(globalState);
+
+    assertKeysEqual(gs.pauser, pauserKeypair.publicKey);
+
+    await updatePauseFlags(pauserKeypair);
+
+    try {
+      await updatePauseFlags(governanceKeypair); //governance no longer holds the role
+    } catch (error) {
+      const errMsg = 'Caller not pauser';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Revoke pauser role', async () => {
+    await program.methods
+      .revokeRole({ pauser: {} })
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const gs = await program.account.globalState.fetch(globalState);
+
+    assertKeysEqual(gs.pauser, anchor.web3.PublicKey.default);
+  });
+
+  after(async () => {
+    //give the pauser role back to governance
+    await grantRole({ pauser: {} }, governanceKeypair.publicKey);
+  });
+});
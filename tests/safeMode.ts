@@ -0,0 +1,194 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import { TOKEN_PROGRAM_ID, getAccount, getAssociatedTokenAddressSync } from '@solana/spl-token';
+import { assert } from 'chai';
+
+import { governanceKeypair, indexMeshMint, meshMint, userOne, userTwo } from './hooks';
+import { assertBNEqual } from './genericTests';
+import { parseUnits } from '../utils/formatting';
+import {
+  findEventAuthority,
+  findStakePool,
+  findUserInfo,
+  getNextWithdrawalTicket,
+} from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('safe mode', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const connection = anchor.getProvider().connection;
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  const meshPool = findStakePool(program.programId, meshMint);
+  const userOneInfo = findUserInfo(program.programId, userOne.publicKey, meshMint);
+
+  const userOneMeshATA = getAssociatedTokenAddressSync(
+    meshMint,
+    userOne.publicKey,
+    false,
+    TOKEN_PROGRAM_ID
+  );
+
+  async function updateSafeMode(safeMode: boolean) {
+    await program.methods
+      .updateSafeMode(safeMode)
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair]) //signing txn by governance
+      .rpc();
+  }
+
+  async function emergencyWithdraw(user = userOne, mint = meshMint) {
+    await program.methods
+      .emergencyWithdraw()
+      .accounts({
+        user: user.publicKey,
+        userInfo: findUserInfo(program.programId, user.publicKey, mint),
+        stakePool: findStakePool(program.programId, mint),
+        globalState,
+        fundsController,
+        mint,
+        mintVault: getAssociatedTokenAddressSync(mint, fundsController, true, TOKEN_PROGRAM_ID),
+        userMintTokenAccount: getAssociatedTokenAddressSync(mint, user.publicKey, false, TOKEN_PROGRAM_ID),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  it('Not allow emergency withdraw while safe mode is off', async () => {
+    try {
+      await emergencyWithdraw();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      const errMsg = 'Safe mode is not active';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Activate safe mode and not allow unstake', async () => {
+    await updateSafeMode(true);
+
+    const gs = await program.account.globalState.fetch(globalState);
+
+    assert.isTrue(gs.safeMode);
+
+    try {
+      await program.methods
+        .unstake(parseUnits(1), true)
+        .accounts({
+          base: {
+            fundsController,
+            globalState,
+            rewardSchedule,
+            user: userOne.publicKey,
+            userInfo: userOneInfo,
+            stakePool: meshPool,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          },
+          withdrawalTicket: await getNextWithdrawalTicket(program, userOneInfo),
+          systemProgram: anchor.web3.SystemProgram.programId,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([userOne])
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Safe mode is active';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Withdraw all staked tokens of userOne without updating the reward state', async () => {
+    const [uiPrev, poolPrev, gsPrev, userPrevAccount] = await Promise.all([
+      program.account.userInfo.fetch(userOneInfo),
+      program.account.stakePool.fetch(meshPool),
+      program.account.globalState.fetch(globalState),
+      getAccount(connection, userOneMeshATA),
+    ]);
+
+    await emergencyWithdraw();
+
+    const [ui, pool, gs, userAccount] = await Promise.all([
+      program.account.userInfo.fetch(userOneInfo),
+      program.account.stakePool.fetch(meshPool),
+      program.account.globalState.fetch(globalState),
+      getAccount(connection, userOneMeshATA),
+    ]);
+
+    assertBNEqual(ui.staked, 0);
+    assertBNEqual(ui.weighted, 0);
+    assertBNEqual(ui.accReward, pool.accReward);
+    assert.isTrue(ui.unclaimedOwed.gte(uiPrev.unclaimedOwed));
+    assertBNEqual(pool.totalStaked, poolPrev.totalStaked.sub(uiPrev.staked));
+    assertBNEqual(
+      new anchor.BN((userAccount.amount - userPrevAccount.amount).toString()),
+      uiPrev.staked
+    );
+
+    // reward state stays frozen
+    assertBNEqual(gs.globalAccReward, gsPrev.globalAccReward);
+    assertBNEqual(pool.accReward, poolPrev.accReward);
+  });
+
+  it('Withdraw the locked stake of userTwo and keep its stream rewards and unstake fees owed', async () => {
+    const indexMeshPool = findStakePool(program.programId, indexMeshMint);
+    const userTwoInfo = findUserInfo(program.programId, userTwo.publicKey, indexMeshMint);
+    const scale = new anchor.BN(10).pow(new anchor.BN(18));
+
+    const uiPrev = await program.account.userInfo.fetch(userTwoInfo);
+
+    await emergencyWithdraw(userTwo, indexMeshMint);
+
+    const [ui, pool] = await Promise.all([
+      program.account.userInfo.fetch(userTwoInfo),
+      program.account.stakePool.fetch(indexMeshPool),
+    ]);
+
+    assertBNEqual(ui.staked, 0);
+
+    // stream rewards are settled into the pending rewards against the stored pool accumulators
+    pool.streamAccRewards.forEach((accReward, index) => {
+      assertBNEqual(ui.streamAccRewards[index], accReward);
+      assertBNEqual(
+        ui.streamPendingRewards[index],
+        uiPrev.streamPendingRewards[index].add(
+          accReward.sub(uiPrev.streamAccRewards[index]).mul(uiPrev.weighted).div(scale)
+        )
+      );
+    });
+
+    // redistributed unstake fees are owed, to be compounded on the next fee settlement
+    assertBNEqual(ui.feeAccReward, pool.feeAccReward);
+    assertBNEqual(
+      ui.unclaimedFeeOwed,
+      uiPrev.unclaimedFeeOwed.add(pool.feeAccReward.sub(uiPrev.feeAccReward).mul(uiPrev.staked).div(scale))
+    );
+  });
+
+  after(async () => {
+    await updateSafeMode(false);
+  });
+});
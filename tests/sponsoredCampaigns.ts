@@ -0,0 +1,282 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import {
+  createAssociatedTokenAccount,
+  getAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from '@solana/spl-token';
+import { assert } from 'chai';
+
+import { governanceKeypair, userTwo } from './hooks';
+import { assertBNEqual, assertKeyDefault, assertKeysEqual } from './genericTests';
+import { delay } from '../utils';
+import { parseUnits } from '../utils/formatting';
+import { findEventAuthority } from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('sponsored campaigns', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+  const connection = anchor.getProvider().connection;
+
+  const minSolReward = new anchor.BN(1_000_000);
+  const tokenReward = parseUnits(10);
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  let rewardMint: anchor.web3.PublicKey;
+  let rewardVault: anchor.web3.PublicKey;
+  let sponsorTokenAccount: anchor.web3.PublicKey;
+
+  function findRewardCampaign(id: anchor.BN) {
+    const [rewardCampaign] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('reward_campaign'), id.toArrayLike(Buffer, 'le', 8)],
+      program.programId
+    );
+
+    return rewardCampaign;
+  }
+
+  function campaignConfig(mint: anchor.web3.PublicKey) {
+    const emptyMint = { mint: anchor.web3.PublicKey.default, minReward: new anchor.BN(0) };
+
+    return {
+      minSolReward,
+      minDuration: new anchor.BN(1),
+      maxDuration: new anchor.BN(60),
+      mints: [{ mint, minReward: tokenReward }, emptyMint, emptyMint, emptyMint],
+    };
+  }
+
+  async function getCurrentTime() {
+    return connection.getBlockTime(await connection.getSlot());
+  }
+
+  async function updateCampaignConfig(mint: anchor.web3.PublicKey) {
+    await program.methods
+      .updateCampaignConfig(campaignConfig(mint))
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+  }
+
+  async function sponsorRewardCampaign(reward: anchor.BN, duration: number, isToken: boolean) {
+    const gs = await program.account.globalState.fetch(globalState);
+    const rewardCampaign = findRewardCampaign(gs.nextCampaignId);
+    const startTime = new anchor.BN((await getCurrentTime()) + 1);
+
+    await program.methods
+      .sponsorRewardCampaign(reward, startTime, startTime.addn(duration))
+      .accounts({
+        sponsor: userTwo.publicKey,
+        globalState,
+        rewardSchedule,
+        rewardCampaign,
+        fundsController,
+        rewardVault: isToken ? rewardVault : null,
+        sponsorTokenAccount: isToken ? sponsorTokenAccount : null,
+        tokenProgram: isToken ? TOKEN_PROGRAM_ID : null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userTwo])
+      .rpc();
+
+    return rewardCampaign;
+  }
+
+  async function closeRewardCampaign(rewardCampaign: anchor.web3.PublicKey, isToken: boolean) {
+    await program.methods
+      .closeRewardCampaign()
+      .accounts({
+        closer: governanceKeypair.publicKey,
+        creator: userTwo.publicKey,
+        globalState,
+        rewardSchedule,
+        rewardCampaign,
+        fundsController,
+        rewardVault: isToken ? rewardVault : null,
+        creatorTokenAccount: isToken ? sponsorTokenAccount : null,
+        tokenProgram: isToken ? TOKEN_PROGRAM_ID : null,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+  }
+
+  before(async () => {
+    // the reward stream opened in the reward streams tests
+    const gs = await program.account.globalState.fetch(globalState);
+    rewardMint = gs.rewardStreams[0].mint;
+    [rewardVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('stream_vault'), rewardMint.toBuffer()],
+      program.programId
+    );
+    sponsorTokenAccount = await createAssociatedTokenAccount(
+      connection,
+      governanceKeypair,
+      rewardMint,
+      userTwo.publicKey
+    );
+    await mintTo(
+      connection,
+      governanceKeypair,
+      rewardMint,
+      sponsorTokenAccount,
+      governanceKeypair,
+      BigInt(tokenReward.toString())
+    );
+  });
+
+  it('Not allow sponsoring before the campaign config is set', async () => {
+    try {
+      await sponsorRewardCampaign(minSolReward, 5, false);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidCampaignWindow');
+    }
+  });
+
+  it('Not allow a reward mint without a reward stream', async () => {
+    try {
+      await updateCampaignConfig(anchor.web3.Keypair.generate().publicKey);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidCampaignMint');
+    }
+  });
+
+  it('Update the campaign config', async () => {
+    await updateCampaignConfig(rewardMint);
+
+    const gs = await program.account.globalState.fetch(globalState);
+
+    assertBNEqual(gs.campaignConfig.minSolReward, minSolReward);
+    assertBNEqual(gs.campaignConfig.maxDuration, 60);
+    assertKeysEqual(gs.campaignConfig.mints[0].mint, rewardMint);
+  });
+
+  it('Not allow a sponsored reward lower than the minimum', async () => {
+    try {
+      await sponsorRewardCampaign(minSolReward.subn(1), 5, false);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'CampaignRewardTooLow');
+    }
+  });
+
+  it('Not allow a campaign longer than the maximum duration', async () => {
+    try {
+      await sponsorRewardCampaign(minSolReward, 61, false);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidCampaignWindow');
+    }
+  });
+
+  it('Sponsor a SOL campaign and close it once it ends', async () => {
+    const gsBefore = await program.account.globalState.fetch(globalState);
+    const controllerBefore = await connection.getBalance(fundsController);
+
+    const rewardCampaign = await sponsorRewardCampaign(minSolReward, 5, false);
+
+    const [gs, campaign, controllerAfter] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.rewardCampaign.fetch(rewardCampaign),
+      connection.getBalance(fundsController),
+    ]);
+    const slot = gs.rewardCampaigns[campaign.slot];
+
+    assertKeysEqual(campaign.creator, userTwo.publicKey);
+    assertKeyDefault(campaign.rewardMint);
+    assert.isTrue(slot.sponsored);
+    assert.equal(controllerAfter - controllerBefore, minSolReward.toNumber());
+    assertBNEqual(gs.totalFunded, gsBefore.totalFunded.add(minSolReward));
+    assertBNEqual(gs.totalDistributedReward, gsBefore.totalDistributedReward.add(minSolReward));
+
+    await delay(8 * 1000);
+    await closeRewardCampaign(rewardCampaign, false);
+
+    const [gsAfter, campaignAccount] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      connection.getAccountInfo(rewardCampaign),
+    ]);
+
+    // the stranded campaign rewards are refunded to the sponsor instead of the stranded rewards
+    assertBNEqual(gsAfter.strandedReward, gs.strandedReward);
+    assertKeyDefault(gsAfter.rewardCampaigns[campaign.slot].campaign);
+    assert.isNull(campaignAccount);
+  });
+
+  it('Sponsor a token campaign through its reward stream', async () => {
+    const [gsBefore, vaultBefore] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      getAccount(connection, rewardVault),
+    ]);
+
+    const rewardCampaign = await sponsorRewardCampaign(tokenReward, 5, true);
+
+    const [gs, campaign, vaultAfter] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.rewardCampaign.fetch(rewardCampaign),
+      getAccount(connection, rewardVault),
+    ]);
+
+    assertKeysEqual(campaign.rewardMint, rewardMint);
+    assert.equal(vaultAfter.amount - vaultBefore.amount, BigInt(tokenReward.toString()));
+    assertBNEqual(
+      gs.rewardStreams[0].totalDistributedReward,
+      gsBefore.rewardStreams[0].totalDistributedReward.add(tokenReward)
+    );
+    assertBNEqual(gs.rewardStreams[0].totalFunded, gsBefore.rewardStreams[0].totalFunded.add(tokenReward));
+    assertBNEqual(gs.totalDistributedReward, gsBefore.totalDistributedReward);
+
+    await delay(8 * 1000);
+    await closeRewardCampaign(rewardCampaign, true);
+
+    const gsAfter = await program.account.globalState.fetch(globalState);
+
+    assertKeyDefault(gsAfter.rewardCampaigns[campaign.slot].campaign);
+  });
+
+  it('Not allow sponsors to hold the slots reserved for the reward manager', async () => {
+    const rewardCampaigns = [
+      await sponsorRewardCampaign(minSolReward, 5, false),
+      await sponsorRewardCampaign(minSolReward, 5, false),
+    ];
+
+    try {
+      await sponsorRewardCampaign(minSolReward, 5, false);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'SponsoredCampaignsFull');
+    }
+
+    await delay(8 * 1000);
+
+    for (const rewardCampaign of rewardCampaigns) {
+      await closeRewardCampaign(rewardCampaign, false);
+    }
+  });
+});
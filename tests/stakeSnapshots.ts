@@ -0,0 +1,175 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { getAssociatedTokenAddressSync, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { assert } from 'chai';
+
+import { governanceKeypair, indexMeshMint, userTwo } from './hooks';
+import { assertBNEqual } from './genericTests';
+import { delay } from '../utils';
+import { parseUnits } from '../utils/formatting';
+import { findEventAuthority, findStakePool, findUserInfo } from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('stake snapshots', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const stakeAmount = parseUnits(1);
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  const indexMeshPool = findStakePool(program.programId, indexMeshMint);
+  const userTwoInfo = findUserInfo(program.programId, userTwo.publicKey, indexMeshMint);
+
+  async function balanceAt(timestamp: anchor.BN) {
+    return program.methods
+      .balanceAt(userTwo.publicKey, timestamp)
+      .accounts({ globalState, stakePool: indexMeshPool, userInfo: userTwoInfo })
+      .view();
+  }
+
+  async function stake() {
+    const [gs, ui] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.userInfo.fetch(userTwoInfo),
+    ]);
+
+    // keep the current lock of the user, as it can't be downgraded
+    const lockTier = Math.max(
+      gs.lockTiers.findIndex(lockTier => lockTier.multiplier.eq(ui.lockMultiplier)),
+      0
+    );
+
+    await program.methods
+      .stake(stakeAmount, lockTier)
+      .accounts({
+        base: {
+          fundsController,
+          globalState,
+          rewardSchedule,
+          user: userTwo.publicKey,
+          userInfo: userTwoInfo,
+          stakePool: indexMeshPool,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        mint: indexMeshMint,
+        mintVault: getAssociatedTokenAddressSync(indexMeshMint, fundsController, true, TOKEN_PROGRAM_ID),
+        userMintTokenAccount: getAssociatedTokenAddressSync(
+          indexMeshMint,
+          userTwo.publicKey,
+          false,
+          TOKEN_PROGRAM_ID
+        ),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userTwo])
+      .rpc();
+  }
+
+  it('Not allow balances before a snapshot epoch is opened', async () => {
+    try {
+      await balanceAt(new anchor.BN(0));
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.include(String(error), 'SnapshotUnavailable');
+    }
+  });
+
+  it('Checkpoint the stake changes of the snapshot epoch', async () => {
+    await program.methods
+      .openSnapshotEpoch()
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const [gs, uiBefore] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.userInfo.fetch(userTwoInfo),
+    ]);
+
+    assertBNEqual(gs.snapshotEpoch, 1);
+
+    await delay(2 * 1000);
+    await stake();
+
+    const uiAfter = await program.account.userInfo.fetch(userTwoInfo);
+    const stakeTime = uiAfter.checkpoints[uiAfter.numCheckpoints - 1].time;
+
+    // the first checkpoint keeps the balances held when the epoch was opened
+    assertBNEqual(uiAfter.snapshotEpoch, 1);
+    assert.equal(uiAfter.numCheckpoints, 2);
+
+    const [atStart, beforeStake, afterStake] = await Promise.all([
+      balanceAt(gs.snapshotStartTime),
+      balanceAt(stakeTime.subn(1)),
+      balanceAt(stakeTime),
+    ]);
+
+    assertBNEqual(atStart.staked, uiBefore.staked);
+    assertBNEqual(beforeStake.staked, uiBefore.staked);
+    assertBNEqual(afterStake.staked, uiBefore.staked.add(stakeAmount));
+    assertBNEqual(afterStake.weighted, uiAfter.weighted);
+
+    try {
+      await balanceAt(gs.snapshotStartTime.subn(1));
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.include(String(error), 'SnapshotUnavailable');
+    }
+  });
+
+  it('Merge the oldest checkpoints once they are full', async () => {
+    let uiFull = await program.account.userInfo.fetch(userTwoInfo);
+
+    while (uiFull.numCheckpoints < 16) {
+      await delay(1100);
+      await stake();
+      uiFull = await program.account.userInfo.fetch(userTwoInfo);
+    }
+
+    await delay(1100);
+    await stake();
+
+    const ui = await program.account.userInfo.fetch(userTwoInfo);
+
+    // the two oldest checkpoints after the opening one are merged with the lower balances
+    assert.equal(ui.numCheckpoints, 16);
+    assertBNEqual(ui.checkpoints[0].staked, uiFull.checkpoints[0].staked);
+    assertBNEqual(ui.checkpoints[1].time, uiFull.checkpoints[1].time);
+    assertBNEqual(
+      ui.checkpoints[1].staked,
+      anchor.BN.min(uiFull.checkpoints[1].staked, uiFull.checkpoints[2].staked)
+    );
+    assertBNEqual(ui.checkpoints[2].time, uiFull.checkpoints[3].time);
+    assertBNEqual(ui.checkpoints[15].staked, ui.staked);
+
+    const [atMerged, latest] = await Promise.all([
+      balanceAt(uiFull.checkpoints[2].time),
+      balanceAt(ui.checkpoints[15].time),
+    ]);
+
+    assert.isTrue(atMerged.staked.lte(uiFull.checkpoints[2].staked));
+    assertBNEqual(latest.staked, ui.staked);
+    assertBNEqual(latest.weighted, ui.weighted);
+  });
+});
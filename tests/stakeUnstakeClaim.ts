@@ -1,5 +1,5 @@
 import * as anchor from '@coral-xyz/anchor';
-import { Program } from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
 import {
   getAssociatedTokenAddressSync,
   TOKEN_PROGRAM_ID,
@@ -11,13 +11,22 @@ import { assert } from 'chai';
 
 import { governanceKeypair, meshMint, indexMeshMint, userOne, userTwo } from './hooks';
 import { delay } from '../utils';
-import { parseUnits, limitPrecision } from '../utils/formatting';
-import { GlobalStateClone, UserClone } from '../utils/state';
-import { airdropSol, getConfirmedTransaction } from '../utils/web3';
+import { formatUnits, parseUnits, limitPrecision } from '../utils/formatting';
+import { GlobalStateClone, UserClone, fetchGlobalState, fetchUserState } from '../utils/state';
+import {
+  airdropSol,
+  findEventAuthority,
+  findStakePool,
+  findUserInfo,
+  getConfirmedTransaction,
+  getNextWithdrawalTicket,
+  userPedningRewards,
+} from '../utils/web3';
 import { MeshStaking } from '../target/types/mesh_staking';
 
 describe('stake / unstake / claim', () => {
   const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
 
   const connection = anchor.getProvider().connection;
 
@@ -35,11 +44,19 @@ describe('stake / unstake / claim', () => {
     program.programId
   );
 
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
   const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from('funds_controller')],
     program.programId
   );
 
+  const meshPool = findStakePool(program.programId, meshMint);
+  const indexMeshPool = findStakePool(program.programId, indexMeshMint);
+
   const meshVault = getAssociatedTokenAddressSync(
     meshMint,
     fundsController,
@@ -59,10 +76,8 @@ describe('stake / unstake / claim', () => {
   user01Clone.meshBalance = meshMintAmount;
   user01Clone.indexMeshBalance = indexMeshMintAmount;
 
-  const [userOneInfo] = anchor.web3.PublicKey.findProgramAddressSync(
-    [userOne.publicKey.toBuffer(), Buffer.from('user_info')],
-    program.programId
-  );
+  const userOneMeshInfo = findUserInfo(program.programId, userOne.publicKey, meshMint);
+  const userOneIndexMeshInfo = findUserInfo(program.programId, userOne.publicKey, indexMeshMint);
 
   const userOneMeshATA = getAssociatedTokenAddressSync(
     meshMint,
@@ -83,10 +98,8 @@ describe('stake / unstake / claim', () => {
   user02Clone.meshBalance = meshMintAmount;
   user02Clone.indexMeshBalance = indexMeshMintAmount;
 
-  const [userTwoInfo] = anchor.web3.PublicKey.findProgramAddressSync(
-    [userTwo.publicKey.toBuffer(), Buffer.from('user_info')],
-    program.programId
-  );
+  const userTwoMeshInfo = findUserInfo(program.programId, userTwo.publicKey, meshMint);
+  const userTwoIndexMeshInfo = findUserInfo(program.programId, userTwo.publicKey, indexMeshMint);
 
   const userTwoMeshATA = getAssociatedTokenAddressSync(
     meshMint,
@@ -157,19 +170,23 @@ describe('stake / unstake / claim', () => {
     const stakeAmount = 10;
 
     let txId = await program.methods
-      .stake(parseUnits(stakeAmount))
+      .stake(parseUnits(stakeAmount), 0)
       .accounts({
         base: {
           fundsController,
           globalState,
+          rewardSchedule,
           user: userOne.publicKey,
-          userInfo: userOneInfo,
+          userInfo: userOneMeshInfo,
+          stakePool: meshPool,
           systemProgram: anchor.web3.SystemProgram.programId,
         },
         mint: meshMint,
         mintVault: meshVault,
         userMintTokenAccount: userOneMeshATA,
         tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([userOne])
       .rpc();
@@ -183,8 +200,8 @@ describe('stake / unstake / claim', () => {
       userIndexMeshAccount,
       userNewSOLBal,
     ] = await Promise.all([
-      program.account.globalState.fetch(globalState),
-      program.account.userInfo.fetch(userOneInfo),
+      fetchGlobalState(program, meshMint, indexMeshMint),
+      fetchUserState(program, userOne.publicKey, meshMint, indexMeshMint),
       getAccount(connection, meshVault),
       getAccount(connection, indexMeshVault),
       getAccount(connection, userOneMeshATA),
@@ -212,19 +229,23 @@ describe('stake / unstake / claim', () => {
     const stakeAmount = 5;
 
     const txId = await program.methods
-      .stake(parseUnits(stakeAmount))
+      .stake(parseUnits(stakeAmount), 0)
       .accounts({
         base: {
           fundsController,
           globalState,
+          rewardSchedule,
           user: userOne.publicKey,
-          userInfo: userOneInfo,
+          userInfo: userOneMeshInfo,
+          stakePool: meshPool,
           systemProgram: anchor.web3.SystemProgram.programId,
         },
         mint: meshMint,
         mintVault: meshVault,
         userMintTokenAccount: userOneMeshATA,
         tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([userOne])
       .rpc();
@@ -238,8 +259,8 @@ describe('stake / unstake / claim', () => {
       userIndexMeshAccount,
       userNewSOLBal,
     ] = await Promise.all([
-      program.account.globalState.fetch(globalState),
-      program.account.userInfo.fetch(userOneInfo),
+      fetchGlobalState(program, meshMint, indexMeshMint),
+      fetchUserState(program, userOne.publicKey, meshMint, indexMeshMint),
       getAccount(connection, meshVault),
       getAccount(connection, indexMeshVault),
       getAccount(connection, userOneMeshATA),
@@ -265,20 +286,42 @@ describe('stake / unstake / claim', () => {
 
     const unstakeAmount = 5;
 
+    const withdrawalTicket = await getNextWithdrawalTicket(program, userOneMeshInfo);
+
     const txId = await program.methods
       .unstake(parseUnits(unstakeAmount), false)
       .accounts({
         base: {
           fundsController,
           globalState,
+          rewardSchedule,
           user: userOne.publicKey,
-          userInfo: userOneInfo,
+          userInfo: userOneMeshInfo,
+          stakePool: meshPool,
           systemProgram: anchor.web3.SystemProgram.programId,
         },
+        withdrawalTicket,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userOne])
+      .rpc();
+
+    await program.methods
+      .withdraw()
+      .accounts({
+        user: userOne.publicKey,
+        userInfo: userOneMeshInfo,
+        withdrawalTicket,
+        globalState,
+        fundsController,
         mint: meshMint,
         mintVault: meshVault,
         userMintTokenAccount: userOneMeshATA,
         tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([userOne])
       .rpc();
@@ -292,8 +335,8 @@ describe('stake / unstake / claim', () => {
       userIndexMeshAccount,
       userNewSOLBal,
     ] = await Promise.all([
-      program.account.globalState.fetch(globalState),
-      program.account.userInfo.fetch(userOneInfo),
+      fetchGlobalState(program, meshMint, indexMeshMint),
+      fetchUserState(program, userOne.publicKey, meshMint, indexMeshMint),
       getAccount(connection, meshVault),
       getAccount(connection, indexMeshVault),
       getAccount(connection, userOneMeshATA),
@@ -320,19 +363,23 @@ describe('stake / unstake / claim', () => {
     const stakeAmount = 20;
 
     const txId = await program.methods
-      .stake(parseUnits(stakeAmount))
+      .stake(parseUnits(stakeAmount), 0)
       .accounts({
         base: {
           fundsController,
           globalState,
+          rewardSchedule,
           user: userOne.publicKey,
-          userInfo: userOneInfo,
+          userInfo: userOneIndexMeshInfo,
+          stakePool: indexMeshPool,
           systemProgram: anchor.web3.SystemProgram.programId,
         },
         mint: indexMeshMint,
         mintVault: indexMeshVault,
         userMintTokenAccount: userOneIndexMeshATA,
         tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([userOne])
       .rpc();
@@ -346,8 +393,8 @@ describe('stake / unstake / claim', () => {
       userIndexMeshAccount,
       userNewSOLBal,
     ] = await Promise.all([
-      program.account.globalState.fetch(globalState),
-      program.account.userInfo.fetch(userOneInfo),
+      fetchGlobalState(program, meshMint, indexMeshMint),
+      fetchUserState(program, userOne.publicKey, meshMint, indexMeshMint),
       getAccount(connection, meshVault),
       getAccount(connection, indexMeshVault),
       getAccount(connection, userOneMeshATA),
@@ -373,20 +420,42 @@ describe('stake / unstake / claim', () => {
 
     const unstakeAmount = 10;
 
+    const withdrawalTicket = await getNextWithdrawalTicket(program, userOneIndexMeshInfo);
+
     const txId = await program.methods
       .unstake(parseUnits(unstakeAmount), false)
       .accounts({
         base: {
           fundsController,
           globalState,
+          rewardSchedule,
           user: userOne.publicKey,
-          userInfo: userOneInfo,
+          userInfo: userOneIndexMeshInfo,
+          stakePool: indexMeshPool,
           systemProgram: anchor.web3.SystemProgram.programId,
         },
+        withdrawalTicket,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userOne])
+      .rpc();
+
+    await program.methods
+      .withdraw()
+      .accounts({
+        user: userOne.publicKey,
+        userInfo: userOneIndexMeshInfo,
+        withdrawalTicket,
+        globalState,
+        fundsController,
         mint: indexMeshMint,
         mintVault: indexMeshVault,
         userMintTokenAccount: userOneIndexMeshATA,
         tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([userOne])
       .rpc();
@@ -400,8 +469,8 @@ describe('stake / unstake / claim', () => {
       userIndexMeshAccount,
       userNewSOLBal,
     ] = await Promise.all([
-      program.account.globalState.fetch(globalState),
-      program.account.userInfo.fetch(userOneInfo),
+      fetchGlobalState(program, meshMint, indexMeshMint),
+      fetchUserState(program, userOne.publicKey, meshMint, indexMeshMint),
       getAccount(connection, meshVault),
       getAccount(connection, indexMeshVault),
       getAccount(connection, userOneMeshATA),
@@ -420,8 +489,9 @@ describe('stake / unstake / claim', () => {
     user01Clone.assertBalance(userMeshAccount.amount, userIndexMeshAccount.amount);
     user01Clone.assertCollectedReward(txId, userNewSOLBal - userPrevSOLBal);
 
+    //only the indexMesh pool is harvested, mesh rewards stay pending
     const userRewards = user01Clone.collectedReward[txId];
-    assert.equal(userRewards.meshReward, userRewards.indexMeshReward, 'userOne rewards diff');
+    assert.equal(userRewards.meshReward, 0, 'userOne mesh rewards harvested');
   });
 
   it('stake 30 indexMesh by userTwo', async () => {
@@ -431,19 +501,23 @@ describe('stake / unstake / claim', () => {
     const stakeAmount = 30;
 
     const txId = await program.methods
-      .stake(parseUnits(stakeAmount))
+      .stake(parseUnits(stakeAmount), 0)
       .accounts({
         base: {
           fundsController,
           globalState,
+          rewardSchedule,
           user: userTwo.publicKey,
-          userInfo: userTwoInfo,
+          userInfo: userTwoIndexMeshInfo,
+          stakePool: indexMeshPool,
           systemProgram: anchor.web3.SystemProgram.programId,
         },
         mint: indexMeshMint,
         mintVault: indexMeshVault,
         userMintTokenAccount: userTwoIndexMeshATA,
         tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([userTwo])
       .rpc();
@@ -457,8 +531,8 @@ describe('stake / unstake / claim', () => {
       userIndexMeshAccount,
       userNewSOLBal,
     ] = await Promise.all([
-      program.account.globalState.fetch(globalState),
-      program.account.userInfo.fetch(userTwoInfo),
+      fetchGlobalState(program, meshMint, indexMeshMint),
+      fetchUserState(program, userTwo.publicKey, meshMint, indexMeshMint),
       getAccount(connection, meshVault),
       getAccount(connection, indexMeshVault),
       getAccount(connection, userTwoMeshATA),
@@ -478,6 +552,42 @@ describe('stake / unstake / claim', () => {
     // user02Clone.assertCollectedReward(txId, userNewSOLBal - userPrevSOLBal);
   });
 
+  it('Not allow claiming the same stake pool twice', async () => {
+    try {
+      await program.methods
+        .claim()
+        .accounts({
+          user: userOne.publicKey,
+          globalState,
+          rewardSchedule,
+          fundsController,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          eventAuthority,
+          program: program.programId,
+        })
+        .remainingAccounts([
+          { pubkey: meshPool, isSigner: false, isWritable: true },
+          { pubkey: userOneMeshInfo, isSigner: false, isWritable: true },
+          { pubkey: meshPool, isSigner: false, isWritable: true },
+          { pubkey: userOneMeshInfo, isSigner: false, isWritable: true },
+        ])
+        .signers([userOne])
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidRemainingAccounts');
+    }
+  });
+
+  it('Not allow counting the same stake pool twice in pending rewards', async () => {
+    try {
+      await userPedningRewards(program, userOne, [meshMint, meshMint]);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.include(String(error), 'InvalidRemainingAccounts');
+    }
+  });
+
   it('claim rewards by userOne', async () => {
     const user01Reward = user01Clone.pendingRewards();
     let user01RewardUptoUser02Entry = user01Reward.meshReward + user01Reward.indexMeshReward;
@@ -489,20 +599,26 @@ describe('stake / unstake / claim', () => {
     const txId = await program.methods
       .claim()
       .accounts({
-        base: {
-          fundsController,
-          globalState,
-          user: userOne.publicKey,
-          userInfo: userOneInfo,
-          systemProgram: anchor.web3.SystemProgram.programId,
-        },
+        user: userOne.publicKey,
+        globalState,
+        rewardSchedule,
+        fundsController,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
       })
+      .remainingAccounts([
+        { pubkey: meshPool, isSigner: false, isWritable: true },
+        { pubkey: userOneMeshInfo, isSigner: false, isWritable: true },
+        { pubkey: indexMeshPool, isSigner: false, isWritable: true },
+        { pubkey: userOneIndexMeshInfo, isSigner: false, isWritable: true },
+      ])
       .signers([userOne])
       .rpc();
 
     const [gs, ui, userNewSOLBal] = await Promise.all([
-      program.account.globalState.fetch(globalState),
-      program.account.userInfo.fetch(userOneInfo),
+      fetchGlobalState(program, meshMint, indexMeshMint),
+      fetchUserState(program, userOne.publicKey, meshMint, indexMeshMint),
       connection.getBalance(userOne.publicKey),
     ]);
 
@@ -523,23 +639,62 @@ describe('stake / unstake / claim', () => {
     );
   });
 
+  it('carry the rounding dust of the claimed rewards forward', async () => {
+    const scaleFactor = new anchor.BN('1000000000000000000');
+
+    const [gs, meshPoolState, meshInfo, indexMeshInfo] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.stakePool.fetch(meshPool),
+      program.account.userInfo.fetch(userOneMeshInfo),
+      program.account.userInfo.fetch(userOneIndexMeshInfo),
+    ]);
+
+    // the truncated fractions stay below one unit of their divisor and are paid by a later settlement
+    assert.isTrue(gs.rewardDust.lt(gs.totalWeightedStake));
+    assert.isTrue(meshPoolState.accRewardRemainder.lt(new anchor.BN(1e9)));
+    assert.isTrue(meshInfo.rewardRemainder.lt(scaleFactor));
+    assert.isTrue(indexMeshInfo.rewardRemainder.lt(scaleFactor));
+  });
+
   it('untsake all mesh and close account by userTwo', async () => {
     const stakeAmount = 30;
 
+    const withdrawalTicket = await getNextWithdrawalTicket(program, userTwoIndexMeshInfo);
+
     const txId = await program.methods
       .unstake(parseUnits(stakeAmount), false)
       .accounts({
         base: {
           fundsController,
           globalState,
+          rewardSchedule,
           user: userTwo.publicKey,
-          userInfo: userTwoInfo,
+          userInfo: userTwoIndexMeshInfo,
+          stakePool: indexMeshPool,
           systemProgram: anchor.web3.SystemProgram.programId,
         },
+        withdrawalTicket,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userTwo])
+      .rpc();
+
+    await program.methods
+      .withdraw()
+      .accounts({
+        user: userTwo.publicKey,
+        userInfo: userTwoIndexMeshInfo,
+        withdrawalTicket,
+        globalState,
+        fundsController,
         mint: indexMeshMint,
         mintVault: indexMeshVault,
         userMintTokenAccount: userTwoIndexMeshATA,
         tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([userTwo])
       .rpc();
@@ -553,8 +708,8 @@ describe('stake / unstake / claim', () => {
       userIndexMeshAccount,
       userNewSOLBal,
     ] = await Promise.all([
-      program.account.globalState.fetch(globalState),
-      program.account.userInfo.fetch(userTwoInfo),
+      fetchGlobalState(program, meshMint, indexMeshMint),
+      fetchUserState(program, userTwo.publicKey, meshMint, indexMeshMint),
       getAccount(connection, meshVault),
       getAccount(connection, indexMeshVault),
       getAccount(connection, userTwoMeshATA),
@@ -574,15 +729,15 @@ describe('stake / unstake / claim', () => {
 
     await program.methods
       .closeUserInfo()
-      .accounts({ user: userTwo.publicKey, userInfo: userTwoInfo })
+      .accounts({ user: userTwo.publicKey, userInfo: userTwoIndexMeshInfo })
       .signers([userTwo])
       .rpc();
 
     //no PDA found becuase userInfo pda is closed on all unstake
     try {
-      await program.account.userInfo.fetch(userTwoInfo);
+      await program.account.userInfo.fetch(userTwoIndexMeshInfo);
     } catch (error) {
-      const errMsg = `Account does not exist or has no data ${userTwoInfo.toBase58()}`;
+      const errMsg = `Account does not exist or has no data ${userTwoIndexMeshInfo.toBase58()}`;
       assert.equal(error.message, errMsg);
     }
   });
@@ -593,20 +748,42 @@ describe('stake / unstake / claim', () => {
 
     const unstakeAmount = 10;
 
+    const withdrawalTicket = await getNextWithdrawalTicket(program, userOneIndexMeshInfo);
+
     const txId = await program.methods
       .unstake(parseUnits(unstakeAmount), true)
       .accounts({
         base: {
           fundsController,
           globalState,
+          rewardSchedule,
           user: userOne.publicKey,
-          userInfo: userOneInfo,
+          userInfo: userOneIndexMeshInfo,
+          stakePool: indexMeshPool,
           systemProgram: anchor.web3.SystemProgram.programId,
         },
+        withdrawalTicket,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userOne])
+      .rpc();
+
+    await program.methods
+      .withdraw()
+      .accounts({
+        user: userOne.publicKey,
+        userInfo: userOneIndexMeshInfo,
+        withdrawalTicket,
+        globalState,
+        fundsController,
         mint: indexMeshMint,
         mintVault: indexMeshVault,
         userMintTokenAccount: userOneIndexMeshATA,
         tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([userOne])
       .rpc();
@@ -620,8 +797,8 @@ describe('stake / unstake / claim', () => {
       userIndexMeshAccount,
       userNewSOLBal,
     ] = await Promise.all([
-      program.account.globalState.fetch(globalState),
-      program.account.userInfo.fetch(userOneInfo),
+      fetchGlobalState(program, meshMint, indexMeshMint),
+      fetchUserState(program, userOne.publicKey, meshMint, indexMeshMint),
       getAccount(connection, meshVault),
       getAccount(connection, indexMeshVault),
       getAccount(connection, userOneMeshATA),
@@ -639,5 +816,14 @@ describe('stake / unstake / claim', () => {
     user01Clone.assertState({ ...ui });
     user01Clone.assertBalance(userMeshAccount.amount, userIndexMeshAccount.amount);
     assert.equal(userNewSOLBal - userPrevSOLBal, 0);
+
+    // the unpaid rewards are owed to the user instead of being forfeited
+    const [indexMeshInfo, pendingRewards] = await Promise.all([
+      program.account.userInfo.fetch(userOneIndexMeshInfo),
+      userPedningRewards(program, userOne, [indexMeshMint]),
+    ]);
+
+    assert.isTrue(indexMeshInfo.unclaimedOwed.gtn(0));
+    assert.equal(pendingRewards.totalUnclaimedOwed, formatUnits(indexMeshInfo.unclaimedOwed));
   });
 });
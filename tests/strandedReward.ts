@@ -0,0 +1,98 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import { assert } from 'chai';
+
+import { governanceKeypair } from './hooks';
+import { assertBNEqual, assertKeysEqual } from './genericTests';
+import { parseUnits } from '../utils/formatting';
+import { findEventAuthority, getCpiEvents } from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('stranded reward', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  before(async () => {
+    // The funds were migrated earlier, so the controller needs SOLs to pay out the stranded rewards.
+    await program.methods
+      .fundRewards(parseUnits(5))
+      .accounts({
+        funder: governanceKeypair.publicKey,
+        globalState,
+        fundsController,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+  });
+
+  it('Withdraw stranded reward to an account other than the treasury', async () => {
+    try {
+      await program.methods
+        .withdrawStrandedReward()
+        .accounts({
+          treasurer: governanceKeypair.publicKey,
+          treasury: anchor.web3.Keypair.generate().publicKey,
+          globalState,
+          rewardSchedule,
+          fundsController,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidTreasury');
+    }
+  });
+
+  it('Withdraw the rewards emitted before the first stake to the treasury', async () => {
+    // Rewards were emitted between the first reward cycle and the first stake.
+    const gsBefore = await program.account.globalState.fetch(globalState);
+    assert.isTrue(gsBefore.strandedReward.gtn(0));
+
+    const txId = await program.methods
+      .withdrawStrandedReward()
+      .accounts({
+        treasurer: governanceKeypair.publicKey,
+        treasury: gsBefore.unstakeFee.treasury,
+        globalState,
+        rewardSchedule,
+        fundsController,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const gsAfter = await program.account.globalState.fetch(globalState);
+    const [event] = await getCpiEvents(program, txId);
+
+    assert.equal(event.name, 'StrandedRewardWithdrawn');
+    assertKeysEqual(event.data.treasury as anchor.web3.PublicKey, gsBefore.unstakeFee.treasury);
+    assertBNEqual(gsAfter.strandedReward, 0);
+    assertBNEqual(gsAfter.totalFunded, gsBefore.totalFunded.sub(event.data.amount as anchor.BN));
+    assertBNEqual(
+      gsAfter.totalDistributedReward,
+      gsBefore.totalDistributedReward.sub(event.data.amount as anchor.BN)
+    );
+  });
+});
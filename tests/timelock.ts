@@ -0,0 +1,315 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import { assert } from 'chai';
+
+import { governanceKeypair, indexMeshMint } from './hooks';
+import { assertBNEqual, assertKeysEqual } from './genericTests';
+import { parseUnits } from '../utils/formatting';
+import { findEventAuthority, findStakePool } from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+const MIN_TIMELOCK_DELAY = 24 * 60 * 60;
+const MAX_UNSTAKE_FEE_BPS = 1_000;
+
+const liquidTier = { duration: new anchor.BN(0), multiplier: parseUnits(1) };
+
+describe('timelock', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  const indexMeshPool = findStakePool(program.programId, indexMeshMint);
+
+  function findTimelockAction(id: anchor.BN) {
+    const [timelockAction] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('timelock_action'), id.toArrayLike(Buffer, 'le', 8)],
+      program.programId
+    );
+    return timelockAction;
+  }
+
+  function unstakeFee(feeBps: number) {
+    return {
+      feeBps: new anchor.BN(feeBps),
+      decayDuration: new anchor.BN(0),
+      redistribute: false,
+      treasury: governanceKeypair.publicKey,
+    };
+  }
+
+  it('Not allow a timelock delay lower than the minimum', async () => {
+    try {
+      await program.methods
+        .updateTimelockDelay(new anchor.BN(MIN_TIMELOCK_DELAY - 1))
+        .accounts({
+          governance: governanceKeypair.publicKey,
+          globalState,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Timelock delay lower than minimum';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Enable the timelock', async () => {
+    await program.methods
+      .updateTimelockDelay(new anchor.BN(MIN_TIMELOCK_DELAY))
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const gs = await program.account.globalState.fetch(globalState);
+
+    assertBNEqual(gs.timelockDelay, MIN_TIMELOCK_DELAY);
+  });
+
+  it('Not allow direct weightage update once timelocked', async () => {
+    try {
+      await program.methods
+        .updateWeightage(parseUnits(1))
+        .accounts({
+          governance: governanceKeypair.publicKey,
+          globalState,
+          rewardSchedule,
+          stakePool: indexMeshPool,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Action must be queued in the timelock';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Not allow direct unstake fee update once timelocked', async () => {
+    try {
+      await program.methods
+        .updateUnstakeFee(unstakeFee(100))
+        .accounts({
+          governance: governanceKeypair.publicKey,
+          globalState,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'TimelockRequired');
+    }
+  });
+
+  it('Not allow queueing an unstake fee higher than maximum', async () => {
+    const gs = await program.account.globalState.fetch(globalState);
+
+    try {
+      await program.methods
+        .queueAction({ updateUnstakeFee: { unstakeFee: unstakeFee(MAX_UNSTAKE_FEE_BPS + 1) } })
+        .accounts({
+          proposer: governanceKeypair.publicKey,
+          globalState,
+          timelockAction: findTimelockAction(gs.nextActionId),
+          systemProgram: anchor.web3.SystemProgram.programId,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidUnstakeFee');
+    }
+  });
+
+  it('Not allow direct lock tiers update once timelocked', async () => {
+    try {
+      await program.methods
+        .updateLockTiers([liquidTier])
+        .accounts({
+          governance: governanceKeypair.publicKey,
+          globalState,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'TimelockRequired');
+    }
+  });
+
+  it('Not allow queueing lock tiers without the liquid tier first', async () => {
+    const gs = await program.account.globalState.fetch(globalState);
+    const lockTiers = gs.lockTiers.map(() => ({ duration: new anchor.BN(0), multiplier: new anchor.BN(0) }));
+
+    try {
+      await program.methods
+        .queueAction({ updateLockTiers: { lockTiers } })
+        .accounts({
+          proposer: governanceKeypair.publicKey,
+          globalState,
+          timelockAction: findTimelockAction(gs.nextActionId),
+          systemProgram: anchor.web3.SystemProgram.programId,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidLockTier');
+    }
+  });
+
+  it('Not allow direct stranded reward withdraw once timelocked', async () => {
+    const gs = await program.account.globalState.fetch(globalState);
+
+    try {
+      await program.methods
+        .withdrawStrandedReward()
+        .accounts({
+          treasurer: governanceKeypair.publicKey,
+          treasury: gs.unstakeFee.treasury,
+          globalState,
+          rewardSchedule,
+          fundsController,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'TimelockRequired');
+    }
+  });
+
+  it('Queue a stranded reward withdraw by the treasurer', async () => {
+    const gsPrev = await program.account.globalState.fetch(globalState);
+    const timelockAction = findTimelockAction(gsPrev.nextActionId);
+
+    await program.methods
+      .queueAction({ withdrawStrandedReward: {} })
+      .accounts({
+        proposer: governanceKeypair.publicKey,
+        globalState,
+        timelockAction,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const action = await program.account.timelockAction.fetch(timelockAction);
+
+    assert.isDefined(action.action.withdrawStrandedReward);
+
+    await program.methods
+      .cancelAction()
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        proposer: governanceKeypair.publicKey,
+        globalState,
+        timelockAction,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+  });
+
+  it('Queue, not execute before eta, and cancel a weightage update', async () => {
+    const gsPrev = await program.account.globalState.fetch(globalState);
+    const timelockAction = findTimelockAction(gsPrev.nextActionId);
+
+    await program.methods
+      .queueAction({
+        updateWeightage: { stakePool: indexMeshPool, weightage: parseUnits(1) },
+      })
+      .accounts({
+        proposer: governanceKeypair.publicKey,
+        globalState,
+        timelockAction,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const [gs, action] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.timelockAction.fetch(timelockAction),
+    ]);
+
+    assertBNEqual(gs.nextActionId, gsPrev.nextActionId.addn(1));
+    assertBNEqual(action.id, gsPrev.nextActionId);
+    assertKeysEqual(action.proposer, governanceKeypair.publicKey);
+    assert.isAbove(action.eta.toNumber(), Date.now() / 1000);
+
+    try {
+      await program.methods
+        .executeAction()
+        .accounts({
+          executor: program.provider.publicKey,
+          proposer: governanceKeypair.publicKey,
+          globalState,
+          rewardSchedule,
+          timelockAction,
+          fundsController,
+          stakePool: indexMeshPool,
+          newVersion: null,
+          eventAuthority,
+          program: program.programId,
+        })
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Timelock delay has not passed';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+
+    await program.methods
+      .cancelAction()
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        proposer: governanceKeypair.publicKey,
+        globalState,
+        timelockAction,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const closedAction = await program.provider.connection.getAccountInfo(timelockAction);
+
+    assert.isNull(closedAction);
+  });
+});
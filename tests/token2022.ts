@@ -0,0 +1,169 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotent,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getAccount,
+  getMintLen,
+  mintTo,
+} from '@solana/spl-token';
+
+import { governanceKeypair, userOne } from './hooks';
+import { assertBNEqual } from './genericTests';
+import { parseUnits } from '../utils/formatting';
+import { findEventAuthority, findStakePool, findUserInfo } from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('token-2022 stake mint', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const connection = anchor.getProvider().connection;
+
+  const feeBasisPoints = 100;
+  const mintKeypair = anchor.web3.Keypair.generate();
+  const mint = mintKeypair.publicKey;
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  const stakePool = findStakePool(program.programId, mint);
+  const userOneInfo = findUserInfo(program.programId, userOne.publicKey, mint);
+
+  let mintVault: anchor.web3.PublicKey;
+  let userOneATA: anchor.web3.PublicKey;
+
+  before(async () => {
+    // create a token-2022 mint with the transfer fee extension
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const mintRentExemptBalance = await connection.getMinimumBalanceForRentExemption(mintLen);
+
+    const tx = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: program.provider.publicKey,
+        newAccountPubkey: mint,
+        space: mintLen,
+        lamports: mintRentExemptBalance,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        mint,
+        governanceKeypair.publicKey,
+        governanceKeypair.publicKey,
+        feeBasisPoints,
+        BigInt(parseUnits(1000).toString()),
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(
+        mint,
+        9,
+        governanceKeypair.publicKey,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+
+    await program.provider.sendAndConfirm(tx, [mintKeypair]);
+
+    [mintVault, userOneATA] = await Promise.all([
+      createAssociatedTokenAccountIdempotent(
+        connection,
+        governanceKeypair,
+        mint,
+        fundsController,
+        {},
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        true
+      ),
+      createAssociatedTokenAccountIdempotent(
+        connection,
+        governanceKeypair,
+        mint,
+        userOne.publicKey,
+        {},
+        TOKEN_2022_PROGRAM_ID
+      ),
+    ]);
+
+    await mintTo(
+      connection,
+      governanceKeypair,
+      mint,
+      userOneATA,
+      governanceKeypair,
+      BigInt(parseUnits(100).toString()),
+      [],
+      {},
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    await program.methods
+      .addStakePool(parseUnits(1))
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        rewardSchedule,
+        mint,
+        stakePool,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+  });
+
+  it('stake 10 tokens by userOne and credit the amount received after the transfer fee', async () => {
+    const stakeAmount = parseUnits(10);
+    const receivedAmount = stakeAmount.sub(stakeAmount.muln(feeBasisPoints).divn(10_000));
+
+    await program.methods
+      .stake(stakeAmount, 0)
+      .accounts({
+        base: {
+          fundsController,
+          globalState,
+          rewardSchedule,
+          user: userOne.publicKey,
+          userInfo: userOneInfo,
+          stakePool,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        mint,
+        mintVault,
+        userMintTokenAccount: userOneATA,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userOne])
+      .rpc();
+
+    const [ui, pool, vaultAccount] = await Promise.all([
+      program.account.userInfo.fetch(userOneInfo),
+      program.account.stakePool.fetch(stakePool),
+      getAccount(connection, mintVault, undefined, TOKEN_2022_PROGRAM_ID),
+    ]);
+
+    assertBNEqual(ui.staked, receivedAmount);
+    assertBNEqual(pool.totalStaked, receivedAmount);
+    assertBNEqual(new anchor.BN(vaultAccount.amount.toString()), receivedAmount);
+  });
+});
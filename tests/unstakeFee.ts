@@ -0,0 +1,163 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import {
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotent,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from '@solana/spl-token';
+import { assert } from 'chai';
+
+import { governanceKeypair, meshMint, userOne } from './hooks';
+import { assertBNEqual } from './genericTests';
+import { parseUnits } from '../utils/formatting';
+import {
+  findEventAuthority,
+  findStakePool,
+  findUserInfo,
+  getNextWithdrawalTicket,
+} from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('unstake fee', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const connection = anchor.getProvider().connection;
+
+  const feeBps = new anchor.BN(100);
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  const meshPool = findStakePool(program.programId, meshMint);
+  const userOneInfo = findUserInfo(program.programId, userOne.publicKey, meshMint);
+
+  const meshVault = getAssociatedTokenAddressSync(
+    meshMint,
+    fundsController,
+    true,
+    TOKEN_PROGRAM_ID
+  );
+
+  async function updateUnstakeFee(feeBps: anchor.BN) {
+    await program.methods
+      .updateUnstakeFee({
+        feeBps,
+        decayDuration: new anchor.BN(0),
+        redistribute: false,
+        treasury: governanceKeypair.publicKey,
+      })
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair]) //signing txn by governance
+      .rpc();
+  }
+
+  it('Not allow an unstake fee higher than maximum', async () => {
+    try {
+      await updateUnstakeFee(new anchor.BN(1_001));
+    } catch (error) {
+      const errMsg = 'Unstake fee higher than maximum';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Set a 1% unstake fee and read the schedule', async () => {
+    await updateUnstakeFee(feeBps);
+
+    const schedule = await program.methods
+      .unstakeFeeSchedule()
+      .accounts({ globalState, userInfo: userOneInfo })
+      .view();
+
+    assertBNEqual(schedule.feeBps, feeBps);
+    assertBNEqual(schedule.currentFeeBps, feeBps);
+    assert.isFalse(schedule.redistribute);
+  });
+
+  it('Deduct the fee on unstake and collect it to the treasury', async () => {
+    const withdrawalTicket = await getNextWithdrawalTicket(program, userOneInfo);
+    const amount = parseUnits(1);
+    const fee = amount.mul(feeBps).divn(10_000);
+
+    await program.methods
+      .unstake(amount, false)
+      .accounts({
+        base: {
+          fundsController,
+          globalState,
+          rewardSchedule,
+          user: userOne.publicKey,
+          userInfo: userOneInfo,
+          stakePool: meshPool,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        withdrawalTicket,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userOne])
+      .rpc();
+
+    const [ticket, pool] = await Promise.all([
+      program.account.withdrawalTicket.fetch(withdrawalTicket),
+      program.account.stakePool.fetch(meshPool),
+    ]);
+
+    assertBNEqual(ticket.amount, amount.sub(fee));
+    assertBNEqual(pool.accruedFees, fee);
+
+    const treasuryTokenAccount = await createAssociatedTokenAccountIdempotent(
+      connection,
+      governanceKeypair,
+      meshMint,
+      governanceKeypair.publicKey
+    );
+
+    await program.methods
+      .collectUnstakeFees()
+      .accounts({
+        collector: program.provider.publicKey,
+        globalState,
+        stakePool: meshPool,
+        fundsController,
+        mint: meshMint,
+        mintVault: meshVault,
+        treasuryTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        program: program.programId,
+      })
+      .rpc();
+
+    const [poolAfter, treasuryAccount] = await Promise.all([
+      program.account.stakePool.fetch(meshPool),
+      getAccount(connection, treasuryTokenAccount),
+    ]);
+
+    assertBNEqual(poolAfter.accruedFees, 0);
+    assertBNEqual(new anchor.BN(treasuryAccount.amount.toString()), fee);
+  });
+
+  after(async () => {
+    await updateUnstakeFee(new anchor.BN(0));
+  });
+});
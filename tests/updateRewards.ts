@@ -1,15 +1,17 @@
 import * as anchor from '@coral-xyz/anchor';
-import { Program } from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import { assert } from 'chai';
 
 import { governanceKeypair } from './hooks';
 import { assertBNEqual } from './genericTests';
 import { delay } from '../utils';
 import { parseUnits } from '../utils/formatting';
-import { getConfirmedTransaction } from '../utils/web3';
+import { airdropSol, findEventAuthority, getConfirmedTransaction } from '../utils/web3';
 import { MeshStaking } from '../target/types/mesh_staking';
 
 describe('update_rewards', () => {
   const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
 
   const connection = anchor.getProvider().connection;
 
@@ -18,15 +20,75 @@ describe('update_rewards', () => {
     program.programId
   );
 
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
   const reward = parseUnits(10);
+  const fundAmount = parseUnits(25);
   const distributionTime = new anchor.BN(30 * 24 * 60 * 60);
 
+  before(async () => {
+    await airdropSol(connection, governanceKeypair.publicKey, 30);
+  });
+
+  it('Not allow rewards exceeding the funded balance', async () => {
+    try {
+      await program.methods
+        .updateRewards(reward, distributionTime, false)
+        .accounts({
+          rewardManager: governanceKeypair.publicKey,
+          globalState,
+          rewardSchedule,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Reward exceeds funded balance';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('fund rewards', async () => {
+    const fcPrevSOLBalance = await connection.getBalance(fundsController);
+
+    await program.methods
+      .fundRewards(fundAmount)
+      .accounts({
+        funder: governanceKeypair.publicKey,
+        globalState,
+        fundsController,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const gs = await program.account.globalState.fetch(globalState);
+    const fcNewSOLBalance = await connection.getBalance(fundsController);
+
+    assertBNEqual(gs.totalFunded, fundAmount);
+    assertBNEqual(new anchor.BN(fcNewSOLBalance - fcPrevSOLBalance), fundAmount);
+  });
+
   it('set rewards', async () => {
     const txId = await program.methods
-      .updateRewards(reward, distributionTime)
+      .updateRewards(reward, distributionTime, false)
       .accounts({
-        governance: governanceKeypair.publicKey,
+        rewardManager: governanceKeypair.publicKey,
         globalState,
+        rewardSchedule,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([governanceKeypair])
       .rpc();
@@ -47,10 +109,13 @@ describe('update_rewards', () => {
     const gsPrev = await program.account.globalState.fetch(globalState);
 
     const txId = await program.methods
-      .updateRewards(newReward, newDistributionTime)
+      .updateRewards(newReward, newDistributionTime, false)
       .accounts({
-        governance: governanceKeypair.publicKey,
+        rewardManager: governanceKeypair.publicKey,
         globalState,
+        rewardSchedule,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([governanceKeypair])
       .rpc();
@@ -77,10 +142,13 @@ describe('update_rewards', () => {
     const gsRev = await program.account.globalState.fetch(globalState);
 
     const txId = await program.methods
-      .updateRewards(newReward, distributionTime)
+      .updateRewards(newReward, distributionTime, false)
       .accounts({
-        governance: governanceKeypair.publicKey,
+        rewardManager: governanceKeypair.publicKey,
         globalState,
+        rewardSchedule,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([governanceKeypair])
       .rpc();
@@ -91,6 +159,45 @@ describe('update_rewards', () => {
     assertBNEqual(gs.reward, newReward);
     assertBNEqual(gs.distributionTime, distributionTime);
     assertBNEqual(gs.periodEndTime, txn.blockTime + distributionTime.toNumber());
-    assertBNEqual(gs.lastUpdatedTime, gsRev.periodEndTime);
+    // the idle time since the previous period end does not accrue rewards
+    assertBNEqual(gs.globalAccReward, gsRev.globalAccReward);
+    assertBNEqual(gs.lastUpdatedTime, txn.blockTime);
+  });
+
+  it('Not allow extending the period end beyond the funded balance', async () => {
+    try {
+      await program.methods
+        .updatePeriodEnd(distributionTime.muln(2))
+        .accounts({
+          rewardManager: governanceKeypair.publicKey,
+          globalState,
+          rewardSchedule,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Reward exceeds funded balance';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Fail with a math error instead of a panic on overflow', async () => {
+    try {
+      await program.methods
+        .updatePeriodEnd(new anchor.BN('18446744073709551615')) //u64::MAX
+        .accounts({
+          rewardManager: governanceKeypair.publicKey,
+          globalState,
+          rewardSchedule,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'MathOverflow');
+    }
   });
 });
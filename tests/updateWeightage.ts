@@ -1,34 +1,58 @@
 import * as anchor from '@coral-xyz/anchor';
 import { Program } from '@coral-xyz/anchor';
+import { assert } from 'chai';
 
-import { governanceKeypair } from './hooks';
-import { assertBNEqual } from './genericTests';
+import { governanceKeypair, indexMeshMint } from './hooks';
+import { assertBNEqual, assertKeysEqual } from './genericTests';
 import { parseUnits } from '../utils/formatting';
+import { findEventAuthority, findStakePool, getCpiEvents } from '../utils/web3';
 import { MeshStaking } from '../target/types/mesh_staking';
 
 describe('update weightage', () => {
   const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
 
   const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from('global_state')],
     program.programId
   );
 
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const indexMeshPool = findStakePool(program.programId, indexMeshMint);
+
   it('Set weightage for indexMesh', async () => {
     const weightage = parseUnits(0.5);
-    await program.methods
+    const txId = await program.methods
       .updateWeightage(weightage)
       .accounts({
         governance: governanceKeypair.publicKey,
         globalState,
+        rewardSchedule,
+        stakePool: indexMeshPool,
+        eventAuthority,
+        program: program.programId,
       })
       .signers([governanceKeypair]) //signing txn by governance
       .rpc();
 
-    const gs = await program.account.globalState.fetch(globalState);
+    const [gs, pool] = await Promise.all([
+      program.account.globalState.fetch(globalState),
+      program.account.stakePool.fetch(indexMeshPool),
+    ]);
+
+    assertBNEqual(pool.weightage, weightage);
+    assertBNEqual(pool.accReward, 0);
+    assertBNEqual(gs.globalAccReward, 0);
+
+    const [event] = await getCpiEvents(program, txId);
 
-    assertBNEqual(gs.weightage, weightage);
-    assertBNEqual(gs.globalAccRewardMesh, 0);
-    assertBNEqual(gs.globalAccRewardIndexMesh, 0);
+    assert.equal(event.name, 'WeightageUpdated');
+    assertKeysEqual(event.data.mint as anchor.web3.PublicKey, indexMeshMint);
+    assertBNEqual(event.data.weightage as anchor.BN, weightage);
+    assertBNEqual(event.data.totalWeightedStake as anchor.BN, gs.totalWeightedStake);
   });
 });
@@ -0,0 +1,122 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import { assert } from 'chai';
+
+import { indexMeshMint, meshMint, userTwo } from './hooks';
+import { assertBNEqual, assertKeysEqual } from './genericTests';
+import { parseUnits } from '../utils/formatting';
+import { findEventAuthority, findStakePool, findUserInfo } from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('voter weight', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const realm = anchor.web3.Keypair.generate().publicKey;
+  const governingTokenMint = meshMint;
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [voterWeightRecord] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from('voter-weight-record'),
+      realm.toBuffer(),
+      governingTokenMint.toBuffer(),
+      userTwo.publicKey.toBuffer(),
+    ],
+    program.programId
+  );
+
+  const [maxVoterWeightRecord] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('max-voter-weight-record'), realm.toBuffer(), governingTokenMint.toBuffer()],
+    program.programId
+  );
+
+  const indexMeshPool = findStakePool(program.programId, indexMeshMint);
+  const userTwoInfo = findUserInfo(program.programId, userTwo.publicKey, indexMeshMint);
+
+  async function updateVoterWeightRecord(pairs: number) {
+    const remainingAccounts = Array.from({ length: pairs }).flatMap(() => [
+      { pubkey: indexMeshPool, isSigner: false, isWritable: false },
+      { pubkey: userTwoInfo, isSigner: false, isWritable: false },
+    ]);
+
+    await program.methods
+      .updateVoterWeightRecord(realm, governingTokenMint)
+      .accounts({
+        user: userTwo.publicKey,
+        globalState,
+        voterWeightRecord,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([userTwo])
+      .rpc();
+  }
+
+  it('Not allow counting a stake pool twice', async () => {
+    try {
+      await updateVoterWeightRecord(2);
+      assert.fail('Expected to throw');
+    } catch (error) {
+      assert.equal((error as AnchorError).error.errorCode.code, 'InvalidRemainingAccounts');
+    }
+  });
+
+  it('Write the voter weight from the stake at the snapshot epoch start scaled by the pool weightage', async () => {
+    await updateVoterWeightRecord(1);
+
+    const gs = await program.account.globalState.fetch(globalState);
+    const [record, pool, ui, balance] = await Promise.all([
+      program.account.voterWeightRecord.fetch(voterWeightRecord),
+      program.account.stakePool.fetch(indexMeshPool),
+      program.account.userInfo.fetch(userTwoInfo),
+      program.methods
+        .balanceAt(userTwo.publicKey, gs.snapshotStartTime)
+        .accounts({ globalState, stakePool: indexMeshPool, userInfo: userTwoInfo })
+        .view(),
+    ]);
+
+    assertKeysEqual(record.realm, realm);
+    assertKeysEqual(record.governingTokenMint, governingTokenMint);
+    assertKeysEqual(record.governingTokenOwner, userTwo.publicKey);
+    assertBNEqual(record.voterWeight, balance.staked.mul(pool.weightage).div(parseUnits(1)));
+    // the stake added after the snapshot epoch was opened doesn't count
+    assert.isTrue(balance.staked.lt(ui.staked));
+    assert.isNotNull(record.voterWeightExpiry);
+    assert.isNull(record.weightAction);
+  });
+
+  it('Write the max voter weight from the stake of every pool', async () => {
+    const pools = await program.account.stakePool.all();
+
+    await program.methods
+      .updateMaxVoterWeightRecord(realm, governingTokenMint)
+      .accounts({
+        payer: userTwo.publicKey,
+        globalState,
+        maxVoterWeightRecord,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .remainingAccounts(
+        pools.map(({ publicKey }) => ({ pubkey: publicKey, isSigner: false, isWritable: false }))
+      )
+      .signers([userTwo])
+      .rpc();
+
+    const record = await program.account.maxVoterWeightRecord.fetch(maxVoterWeightRecord);
+    const maxVoterWeight = pools.reduce(
+      (total, { account }) => total.add(account.totalStaked.mul(account.weightage).div(parseUnits(1))),
+      new anchor.BN(0)
+    );
+
+    assertBNEqual(record.maxVoterWeight, maxVoterWeight);
+  });
+});
@@ -0,0 +1,231 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program, AnchorError } from '@coral-xyz/anchor';
+import { getAssociatedTokenAddressSync, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { assert } from 'chai';
+
+import { governanceKeypair, meshMint, userOne } from './hooks';
+import { assertBNEqual, assertKeysEqual } from './genericTests';
+import { parseUnits } from '../utils/formatting';
+import {
+  findEventAuthority,
+  findStakePool,
+  findUserInfo,
+  getConfirmedTransaction,
+  getNextWithdrawalTicket,
+} from '../utils/web3';
+import { MeshStaking } from '../target/types/mesh_staking';
+
+describe('withdrawal tickets', () => {
+  const program = anchor.workspace.MeshStaking as Program<MeshStaking>;
+  const eventAuthority = findEventAuthority(program.programId);
+
+  const connection = anchor.getProvider().connection;
+
+  const unbondingCooldown = new anchor.BN(60);
+
+  const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('global_state')],
+    program.programId
+  );
+
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
+    program.programId
+  );
+
+  const [fundsController] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('funds_controller')],
+    program.programId
+  );
+
+  const meshPool = findStakePool(program.programId, meshMint);
+  const userOneInfo = findUserInfo(program.programId, userOne.publicKey, meshMint);
+
+  const meshVault = getAssociatedTokenAddressSync(
+    meshMint,
+    fundsController,
+    true,
+    TOKEN_PROGRAM_ID
+  );
+
+  const userOneMeshATA = getAssociatedTokenAddressSync(
+    meshMint,
+    userOne.publicKey,
+    false,
+    TOKEN_PROGRAM_ID
+  );
+
+  let withdrawalTicket: anchor.web3.PublicKey;
+
+  it('Not allow others to update unbonding cooldown', async () => {
+    try {
+      await program.methods
+        .updateUnbondingCooldown(unbondingCooldown)
+        .accounts({
+          governance: program.provider.publicKey, //not the actual governance
+          globalState,
+          eventAuthority,
+          program: program.programId,
+        })
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Caller not governance';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Not allow an unbonding cooldown higher than the maximum', async () => {
+    try {
+      await program.methods
+        .updateUnbondingCooldown(new anchor.BN(30 * 24 * 60 * 60 + 1))
+        .accounts({
+          governance: governanceKeypair.publicKey,
+          globalState,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([governanceKeypair])
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Unbonding cooldown higher than maximum';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('Set unbonding cooldown', async () => {
+    await program.methods
+      .updateUnbondingCooldown(unbondingCooldown)
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+
+    const gs = await program.account.globalState.fetch(globalState);
+
+    assertBNEqual(gs.unbondingCooldown, unbondingCooldown);
+  });
+
+  it('unstake 2 mesh by userOne into a withdrawal ticket', async () => {
+    const uiPrev = await program.account.userInfo.fetch(userOneInfo);
+    withdrawalTicket = await getNextWithdrawalTicket(program, userOneInfo);
+
+    const unstakeAmount = parseUnits(2);
+
+    const txId = await program.methods
+      .unstake(unstakeAmount, false)
+      .accounts({
+        base: {
+          fundsController,
+          globalState,
+          rewardSchedule,
+          user: userOne.publicKey,
+          userInfo: userOneInfo,
+          stakePool: meshPool,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        withdrawalTicket,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userOne])
+      .rpc();
+
+    const [ui, ticket] = await Promise.all([
+      program.account.userInfo.fetch(userOneInfo),
+      program.account.withdrawalTicket.fetch(withdrawalTicket),
+    ]);
+    const tx = await getConfirmedTransaction(connection, txId);
+
+    assertBNEqual(ui.staked, uiPrev.staked.sub(unstakeAmount));
+    assertBNEqual(ui.openTickets, uiPrev.openTickets.addn(1));
+    assertKeysEqual(ticket.user, userOne.publicKey);
+    assertKeysEqual(ticket.pool, meshPool);
+    assertKeysEqual(ticket.mint, meshMint);
+    assertBNEqual(ticket.amount, unstakeAmount);
+    assertBNEqual(ticket.unlockTime, tx.blockTime + unbondingCooldown.toNumber());
+
+    const tickets = await program.methods
+      .pendingWithdrawals(userOne.publicKey)
+      .accounts({ globalState })
+      .remainingAccounts([{ pubkey: withdrawalTicket, isSigner: false, isWritable: false }])
+      .view();
+
+    assert.equal(tickets.length, 1);
+    assertBNEqual(tickets[0].amount, unstakeAmount);
+    assert.isFalse(tickets[0].isWithdrawable);
+  });
+
+  it('Not allow withdraw before cooldown', async () => {
+    try {
+      await program.methods
+        .withdraw()
+        .accounts({
+          user: userOne.publicKey,
+          userInfo: userOneInfo,
+          withdrawalTicket,
+          globalState,
+          fundsController,
+          mint: meshMint,
+          mintVault: meshVault,
+          userMintTokenAccount: userOneMeshATA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          eventAuthority,
+          program: program.programId,
+        })
+        .signers([userOne])
+        .rpc();
+    } catch (error) {
+      const errMsg = 'Unbonding cooldown has not passed';
+      assert.equal((error as AnchorError).error.errorMessage, errMsg);
+    }
+  });
+
+  it('cancel withdrawal ticket and stake again', async () => {
+    const uiPrev = await program.account.userInfo.fetch(userOneInfo);
+    const ticket = await program.account.withdrawalTicket.fetch(withdrawalTicket);
+
+    await program.methods
+      .cancelWithdrawal()
+      .accounts({
+        base: {
+          fundsController,
+          globalState,
+          rewardSchedule,
+          user: userOne.publicKey,
+          userInfo: userOneInfo,
+          stakePool: meshPool,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        withdrawalTicket,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([userOne])
+      .rpc();
+
+    const ui = await program.account.userInfo.fetch(userOneInfo);
+
+    assertBNEqual(ui.staked, uiPrev.staked.add(ticket.amount));
+    assertBNEqual(ui.openTickets, uiPrev.openTickets.subn(1));
+    assert.isNull(await program.account.withdrawalTicket.fetchNullable(withdrawalTicket));
+  });
+
+  after(async () => {
+    //reset cooldown for the following tests
+    await program.methods
+      .updateUnbondingCooldown(new anchor.BN(0))
+      .accounts({
+        governance: governanceKeypair.publicKey,
+        globalState,
+        eventAuthority,
+        program: program.programId,
+      })
+      .signers([governanceKeypair])
+      .rpc();
+  });
+});
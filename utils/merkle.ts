@@ -0,0 +1,58 @@
+import * as anchor from '@coral-xyz/anchor';
+import { keccak_256 } from '@noble/hashes/sha3';
+
+export type DistributionLeaf = {
+  user: anchor.web3.PublicKey;
+  amount: anchor.BN;
+};
+
+export function hashLeaf(index: number, leaf: DistributionLeaf) {
+  return Buffer.from(
+    keccak_256(
+      Buffer.concat([
+        new anchor.BN(index).toArrayLike(Buffer, 'le', 8),
+        leaf.user.toBuffer(),
+        leaf.amount.toArrayLike(Buffer, 'le', 8),
+      ])
+    )
+  );
+}
+
+function hashPair(a: Buffer, b: Buffer) {
+  return Buffer.from(keccak_256(Buffer.compare(a, b) <= 0 ? Buffer.concat([a, b]) : Buffer.concat([b, a])));
+}
+
+// Build the merkle tree of the distribution leaves, with the pairs of nodes sorted before hashing
+// and the odd node of a level carried up as is.
+export function buildMerkleTree(leaves: DistributionLeaf[]) {
+  const levels = [leaves.map((leaf, index) => hashLeaf(index, leaf))];
+
+  while (levels[levels.length - 1].length > 1) {
+    const nodes = levels[levels.length - 1];
+    const parents: Buffer[] = [];
+
+    for (let i = 0; i < nodes.length; i += 2) {
+      parents.push(i + 1 < nodes.length ? hashPair(nodes[i], nodes[i + 1]) : nodes[i]);
+    }
+
+    levels.push(parents);
+  }
+
+  const getProof = (index: number) => {
+    const proof: number[][] = [];
+
+    for (const nodes of levels.slice(0, -1)) {
+      const sibling = index ^ 1;
+
+      if (sibling < nodes.length) {
+        proof.push([...nodes[sibling]]);
+      }
+
+      index = Math.floor(index / 2);
+    }
+
+    return proof;
+  };
+
+  return { root: [...levels[levels.length - 1][0]], getProof };
+}
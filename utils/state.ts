@@ -1,8 +1,8 @@
 import * as anchor from '@coral-xyz/anchor';
-import * as borsh from 'borsh';
 import { assert } from 'chai';
 
-import { limitPrecision, formatUnits } from './formatting';
+import { limitPrecision } from './formatting';
+import { findStakePool, findUserInfo } from './web3';
 import { MeshStaking } from '../target/types/mesh_staking';
 
 interface ProgramGlobalState {
@@ -119,17 +119,27 @@ export class UserClone {
     return { meshReward, indexMeshReward };
   }
 
-  public harvestReward(txId: string) {
-    this.collectedReward[txId] = this.pendingRewards();
+  public harvestReward(txId: string, mesh: boolean, indexMesh: boolean) {
+    const { meshReward, indexMeshReward } = this.pendingRewards();
 
-    this.accRewardMesh = this.globalState.globalAccRewardMesh;
-    this.accRewardIndexMesh = this.globalState.globalAccRewardIndexMesh;
+    this.collectedReward[txId] = {
+      meshReward: mesh ? meshReward : 0,
+      indexMeshReward: indexMesh ? indexMeshReward : 0,
+    };
+
+    if (mesh) {
+      this.accRewardMesh = this.globalState.globalAccRewardMesh;
+    }
+
+    if (indexMesh) {
+      this.accRewardIndexMesh = this.globalState.globalAccRewardIndexMesh;
+    }
   }
 
   public stakeMesh(txId: string, currentTime: number, amount: number) {
     this.globalState.updateRewardPerShare(currentTime);
 
-    this.harvestReward(txId);
+    this.harvestReward(txId, true, false);
 
     this.globalState.totalStakedMesh += amount;
     this.stakedMesh += amount;
@@ -138,7 +148,7 @@ export class UserClone {
   public unstakeMesh(txId: string, currentTime: number, amount: number) {
     this.globalState.updateRewardPerShare(currentTime);
 
-    this.harvestReward(txId);
+    this.harvestReward(txId, true, false);
 
     this.globalState.totalStakedMesh -= amount;
     this.stakedMesh -= amount;
@@ -147,7 +157,7 @@ export class UserClone {
   public stakeIndexMesh(txId: string, currentTime: number, amount: number) {
     this.globalState.updateRewardPerShare(currentTime);
 
-    this.harvestReward(txId);
+    this.harvestReward(txId, false, true);
 
     this.globalState.totalStakedIndexMesh += amount;
     this.stakedIndexMesh += amount;
@@ -156,7 +166,7 @@ export class UserClone {
   public unstakeIndexMesh(txId: string, currentTime: number, amount: number) {
     this.globalState.updateRewardPerShare(currentTime);
 
-    this.harvestReward(txId);
+    this.harvestReward(txId, false, true);
 
     this.globalState.totalStakedIndexMesh -= amount;
     this.stakedIndexMesh -= amount;
@@ -165,7 +175,7 @@ export class UserClone {
   public claim(txId: string, currentTime: number) {
     this.globalState.updateRewardPerShare(currentTime);
 
-    this.harvestReward(txId);
+    this.harvestReward(txId, true, true);
   }
 
   public assertState(programState: ProgramUserState) {
@@ -222,73 +232,63 @@ export class UserClone {
   }
 }
 
-function getReturnData(logs: string[]) {
-  const prefix = 'Program return: ';
-  let returnLog = logs.find(log => log.startsWith(prefix));
-  returnLog = returnLog.slice(prefix.length);
-
-  const [, data] = returnLog.split(' ', 2);
-  return Buffer.from(data, 'base64');
-}
-
-class Assignable {
-  constructor(properties) {
-    Object.keys(properties).map(key => {
-      this[key] = properties[key];
-    });
-  }
-}
-
-function decode_struct(returnType: any, fields: string[][], logs: string[]) {
-  const buffer = getReturnData(logs);
-  const schema = new Map([
-    [
-      returnType,
-      {
-        kind: 'struct',
-        fields,
-      },
-    ],
-  ]);
-
-  return borsh.deserialize(schema, returnType, buffer);
+function syncedPoolAccReward(
+  pool: { accReward: anchor.BN; globalAccRewardCheckpoint: anchor.BN; weightage: anchor.BN },
+  globalAccReward: anchor.BN
+) {
+  return pool.accReward.add(
+    globalAccReward.sub(pool.globalAccRewardCheckpoint).mul(pool.weightage).div(new anchor.BN(1e9))
+  );
 }
 
-export async function userPedningRewards(
+/**
+ * Fetch the stake pools of $MESH & $indexMESH in the shape of the former global state,
+ * with the pool accumulators synced up to the global accumulator.
+ */
+export async function fetchGlobalState(
   program: anchor.Program<MeshStaking>,
-  user: anchor.web3.Keypair
-) {
+  meshMint: anchor.web3.PublicKey,
+  indexMeshMint: anchor.web3.PublicKey
+): Promise<ProgramGlobalState> {
   const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from('global_state')],
     program.programId
   );
 
-  const [userInfo] = anchor.web3.PublicKey.findProgramAddressSync(
-    [user.publicKey.toBuffer(), Buffer.from('user_info')],
-    program.programId
-  );
+  const [gs, meshPool, indexMeshPool] = await Promise.all([
+    program.account.globalState.fetch(globalState),
+    program.account.stakePool.fetch(findStakePool(program.programId, meshMint)),
+    program.account.stakePool.fetch(findStakePool(program.programId, indexMeshMint)),
+  ]);
 
-  const result = await program.methods
-    .pendingRewards(user.publicKey)
-    .accounts({ globalState, userInfo })
-    .simulate();
+  return {
+    globalAccRewardMesh: syncedPoolAccReward(meshPool, gs.globalAccReward),
+    globalAccRewardIndexMesh: syncedPoolAccReward(indexMeshPool, gs.globalAccReward),
+    totalStakedMesh: meshPool.totalStaked,
+    totalStakedIndexMesh: indexMeshPool.totalStaked,
+  };
+}
 
-  class RewardReturnType extends Assignable {
-    mesh_reward: anchor.BN;
-    index_mesh_reward: anchor.BN;
-  }
+/**
+ * Fetch the userInfo PDAs of $MESH & $indexMESH pools in the shape of the former user state.
+ */
+export async function fetchUserState(
+  program: anchor.Program<MeshStaking>,
+  user: anchor.web3.PublicKey,
+  meshMint: anchor.web3.PublicKey,
+  indexMeshMint: anchor.web3.PublicKey
+): Promise<ProgramUserState> {
+  const [meshInfo, indexMeshInfo] = await Promise.all([
+    program.account.userInfo.fetchNullable(findUserInfo(program.programId, user, meshMint)),
+    program.account.userInfo.fetchNullable(findUserInfo(program.programId, user, indexMeshMint)),
+  ]);
 
-  const data = decode_struct(
-    RewardReturnType,
-    [
-      ['mesh_reward', 'u64'],
-      ['index_mesh_reward', 'u64'],
-    ],
-    [...result.raw]
-  ) as RewardReturnType;
+  const zero = new anchor.BN(0);
 
   return {
-    meshReward: formatUnits(data.mesh_reward),
-    indexMeshReward: formatUnits(data.index_mesh_reward),
+    accRewardMesh: meshInfo ? meshInfo.accReward : zero,
+    accRewardIndexMesh: indexMeshInfo ? indexMeshInfo.accReward : zero,
+    stakedMesh: meshInfo ? meshInfo.staked : zero,
+    stakedIndexMesh: indexMeshInfo ? indexMeshInfo.staked : zero,
   };
 }
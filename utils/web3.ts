@@ -33,6 +33,21 @@ export async function getConfirmedTransaction(connection: anchor.web3.Connection
   return await getConfirmedTransaction(connection, txId);
 }
 
+export async function getCpiEvents(program: anchor.Program<MeshStaking>, txId: string) {
+  const tx = await getConfirmedTransaction(program.provider.connection, txId);
+  const accountKeys = tx.transaction.message.accountKeys;
+
+  return tx.meta.innerInstructions
+    .flatMap(({ instructions }) => instructions)
+    .filter(ix => accountKeys[ix.programIdIndex].equals(program.programId))
+    .map(ix => {
+      // Skip the 8 bytes event instruction tag, followed by the event discriminator and data.
+      const eventData = anchor.utils.bytes.bs58.decode(ix.data).subarray(8);
+      return program.coder.events.decode(anchor.utils.bytes.base64.encode(eventData));
+    })
+    .filter(event => event !== null);
+}
+
 export function getReturnData(logs: string[]) {
   const prefix = 'Program return: ';
   let returnLog = logs.find(log => log.startsWith(prefix));
@@ -65,41 +80,93 @@ export function decode_struct(returnType: any, fields: string[][], logs: string[
   return borsh.deserialize(schema, returnType, buffer);
 }
 
+export function findEventAuthority(programId: anchor.web3.PublicKey) {
+  const [eventAuthority] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('__event_authority')],
+    programId
+  );
+
+  return eventAuthority;
+}
+
+export function findStakePool(programId: anchor.web3.PublicKey, mint: anchor.web3.PublicKey) {
+  const [stakePool] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('stake_pool'), mint.toBuffer()],
+    programId
+  );
+
+  return stakePool;
+}
+
+export function findUserInfo(
+  programId: anchor.web3.PublicKey,
+  user: anchor.web3.PublicKey,
+  mint: anchor.web3.PublicKey
+) {
+  const [userInfo] = anchor.web3.PublicKey.findProgramAddressSync(
+    [user.toBuffer(), findStakePool(programId, mint).toBuffer(), Buffer.from('user_info')],
+    programId
+  );
+
+  return userInfo;
+}
+
 export async function userPedningRewards(
   program: anchor.Program<MeshStaking>,
-  user: anchor.web3.Keypair
+  user: anchor.web3.Keypair,
+  mints: anchor.web3.PublicKey[]
 ) {
   const [globalState] = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from('global_state')],
     program.programId
   );
 
-  const [userInfo] = anchor.web3.PublicKey.findProgramAddressSync(
-    [user.publicKey.toBuffer(), Buffer.from('user_info')],
+  const [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('reward_schedule')],
     program.programId
   );
 
-  const result = await program.methods
-    .pendingRewards(user.publicKey)
-    .accounts({ globalState, userInfo })
-    .simulate();
-
-  class RewardReturnType extends Assignable {
-    mesh_reward: anchor.BN;
-    index_mesh_reward: anchor.BN;
-  }
+  const remainingAccounts = mints.flatMap(mint => [
+    { pubkey: findStakePool(program.programId, mint), isSigner: false, isWritable: false },
+    {
+      pubkey: findUserInfo(program.programId, user.publicKey, mint),
+      isSigner: false,
+      isWritable: false,
+    },
+  ]);
 
-  const data = decode_struct(
-    RewardReturnType,
-    [
-      ['mesh_reward', 'u64'],
-      ['index_mesh_reward', 'u64'],
-    ],
-    [...result.raw]
-  ) as RewardReturnType;
+  const data = await program.methods
+    .pendingRewards(user.publicKey)
+    .accounts({ globalState, rewardSchedule })
+    .remainingAccounts(remainingAccounts)
+    .view();
 
   return {
-    meshReward: formatUnits(data.mesh_reward),
-    indexMeshReward: formatUnits(data.index_mesh_reward),
+    totalReward: formatUnits(data.totalReward),
+    totalUnclaimedOwed: formatUnits(data.totalUnclaimedOwed),
+    poolRewards: data.poolRewards.map(({ mint, reward, unclaimedOwed }) => ({
+      mint,
+      reward: formatUnits(reward),
+      unclaimedOwed: formatUnits(unclaimedOwed),
+    })),
+    streamRewards: data.streamRewards.map(({ mint, reward }) => ({
+      mint,
+      reward: formatUnits(reward),
+    })),
   };
 }
+
+export async function getNextWithdrawalTicket(
+  program: anchor.Program<MeshStaking>,
+  userInfo: anchor.web3.PublicKey
+) {
+  const ui = await program.account.userInfo.fetchNullable(userInfo);
+  const ticketId = ui ? ui.nextTicketId : new anchor.BN(0);
+
+  const [withdrawalTicket] = anchor.web3.PublicKey.findProgramAddressSync(
+    [userInfo.toBuffer(), Buffer.from('withdrawal_ticket'), ticketId.toArrayLike(Buffer, 'le', 8)],
+    program.programId
+  );
+
+  return withdrawalTicket;
+}